@@ -0,0 +1,114 @@
+-- QuizNote 자체 호스팅 서버의 SQLite 스키마
+--
+-- 앱 모델(src/models)의 필드와 이름을 맞춥니다. 시각은 `YYYY-MM-DDTHH:MM:SS.ffffffZ` 문자열로 저장해
+-- 문자열 비교가 시각 비교와 같게 합니다. 모든 데이터 테이블에는 `user_id`가 있고, 서버가 요청한 사용자의
+-- 행만 읽고 쓰게 합니다 (Supabase의 행 보안 정책 대신).
+
+CREATE TABLE IF NOT EXISTS settings (
+    key TEXT PRIMARY KEY,
+    value TEXT NOT NULL
+);
+
+-- 인증 (GoTrue)
+CREATE TABLE IF NOT EXISTS users (
+    id TEXT PRIMARY KEY,
+    email TEXT NOT NULL UNIQUE COLLATE NOCASE,
+    password_hash TEXT,
+    email_confirmed_at TEXT,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+-- 메일로 보낸 링크(token)와 6자리 코드(otp). kind: magiclink, signup, recovery
+-- password_hash: 가입 확인 메일이면 그 가입 요청의 비밀번호. 확인할 때 사용자에게 적용합니다.
+CREATE TABLE IF NOT EXISTS one_time_tokens (
+    token TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    kind TEXT NOT NULL,
+    otp TEXT NOT NULL,
+    password_hash TEXT,
+    code_challenge TEXT,
+    redirect_to TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+-- 사용자별로 틀린 인증 코드 수. 여러 번 틀리면 그 사용자의 one_time_tokens를 모두 지웁니다.
+CREATE TABLE IF NOT EXISTS otp_failures (
+    user_id TEXT PRIMARY KEY REFERENCES users (id) ON DELETE CASCADE,
+    count INTEGER NOT NULL
+);
+
+-- 링크를 누른 뒤 앱이 `grant_type=pkce`로 바꿀 인증 코드
+CREATE TABLE IF NOT EXISTS flow_states (
+    auth_code TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    code_challenge TEXT NOT NULL,
+    created_at INTEGER NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS refresh_tokens (
+    token TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    session_id TEXT NOT NULL,
+    revoked INTEGER NOT NULL DEFAULT 0,
+    created_at INTEGER NOT NULL
+);
+
+-- 데이터 (PostgREST)
+CREATE TABLE IF NOT EXISTS certificates (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    question_count INTEGER NOT NULL DEFAULT 0,
+    created_at TEXT NOT NULL,
+    deleted_at TEXT,
+    imported INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS questions (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    certificate_id TEXT NOT NULL REFERENCES certificates (id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    explanation TEXT NOT NULL DEFAULT '',
+    created_at TEXT NOT NULL,
+    last_attempt TEXT,
+    attempt_count INTEGER NOT NULL DEFAULT 0,
+    correct_count INTEGER NOT NULL DEFAULT 0,
+    deleted_at TEXT
+);
+CREATE INDEX IF NOT EXISTS questions_certificate_id ON questions (certificate_id);
+
+CREATE TABLE IF NOT EXISTS question_options (
+    id TEXT PRIMARY KEY,
+    user_id TEXT NOT NULL REFERENCES users (id) ON DELETE CASCADE,
+    question_id TEXT NOT NULL REFERENCES questions (id) ON DELETE CASCADE,
+    content TEXT NOT NULL,
+    is_correct INTEGER NOT NULL DEFAULT 0,
+    explanation TEXT NOT NULL DEFAULT '',
+    display_order INTEGER NOT NULL DEFAULT 0
+);
+CREATE INDEX IF NOT EXISTS question_options_question_id ON question_options (question_id);
+
+-- `certificates.question_count`는 휴지통에 있지 않은 문제 수입니다.
+CREATE TRIGGER IF NOT EXISTS questions_count_after_insert AFTER INSERT ON questions
+BEGIN
+    UPDATE certificates SET question_count =
+        (SELECT count(*) FROM questions WHERE certificate_id = NEW.certificate_id AND deleted_at IS NULL)
+    WHERE id = NEW.certificate_id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS questions_count_after_update AFTER UPDATE OF certificate_id, deleted_at ON questions
+BEGIN
+    UPDATE certificates SET question_count =
+        (SELECT count(*) FROM questions WHERE certificate_id = certificates.id AND deleted_at IS NULL)
+    WHERE id IN (OLD.certificate_id, NEW.certificate_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS questions_count_after_delete AFTER DELETE ON questions
+BEGIN
+    UPDATE certificates SET question_count =
+        (SELECT count(*) FROM questions WHERE certificate_id = OLD.certificate_id AND deleted_at IS NULL)
+    WHERE id = OLD.certificate_id;
+END;
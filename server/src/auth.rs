@@ -0,0 +1,734 @@
+// 인증 (GoTrue 호환 `/auth/v1`)
+//
+// 앱이 쓰는 흐름만 구현합니다.
+// - 로그인 링크·인증 코드: `POST /otp` → (링크) `GET /verify` → `POST /token?grant_type=pkce`, (코드) `POST /verify`
+// - 비밀번호: `POST /signup`, `POST /token?grant_type=password`, `POST /recover`, `PUT /user`
+// - 세션: `POST /token?grant_type=refresh_token`, `GET /user`, `POST /logout`
+// 메일은 보내지 않고 링크와 코드를 로그에 남깁니다.
+
+use crate::db::{self, random_token, unix_now};
+use crate::error::AuthError;
+use crate::jwt::{self, Claims};
+use crate::AppState;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+use axum::extract::{Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::{IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use rand::Rng;
+use rusqlite::{params, Connection, OptionalExtension};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// 메일 링크와 코드의 유효 시간
+const ONE_TIME_TOKEN_TTL_SECS: i64 = 60 * 60;
+/// 링크를 누른 뒤 앱이 인증 코드를 세션으로 바꿔야 하는 시간
+const FLOW_STATE_TTL_SECS: i64 = 10 * 60;
+/// 같은 사용자에게 메일을 다시 보낼 수 있을 때까지 기다리는 시간
+const EMAIL_INTERVAL_SECS: i64 = 60;
+const MIN_PASSWORD_LENGTH: usize = 6;
+/// 인증 코드를 이만큼 틀리면 그 사용자에게 보낸 링크와 코드를 모두 무효로 합니다.
+const MAX_OTP_FAILURES: i64 = 5;
+/// 한 이메일로 코드 확인이나 비밀번호 로그인에 실패할 수 있는 횟수 (`LOGIN_WINDOW_SECS` 동안)
+const MAX_LOGIN_FAILURES: u32 = 10;
+const LOGIN_WINDOW_SECS: i64 = 10 * 60;
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/otp", post(otp))
+        .route("/verify", get(verify_link).post(verify_code))
+        .route("/token", post(token))
+        .route("/signup", post(signup))
+        .route("/recover", post(recover))
+        .route("/user", get(get_user).put(update_user))
+        .route("/logout", post(logout))
+}
+
+/// `Authorization: Bearer <access token>`의 사용자. 토큰이 없거나 잘못되었으면 `None`
+pub fn authenticate(state: &AppState, headers: &HeaderMap) -> Option<Claims> {
+    let token = headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer ").or_else(|| value.strip_prefix("bearer ")))?;
+    jwt::decode::<Claims>(&state.config.jwt_secret, token.trim()).filter(|claims| claims.role == "authenticated")
+}
+
+/// 이메일별 로그인 실패 횟수. 코드와 비밀번호를 계속 바꿔 넣어 보는 요청을 막습니다.
+/// 메모리에만 두므로 서버를 다시 시작하면 초기화됩니다.
+#[derive(Default)]
+pub struct LoginThrottle {
+    /// 키(`verify:<email>`, `password:<email>`) → (처음 실패한 시각, 실패 횟수)
+    failures: Mutex<HashMap<String, (i64, u32)>>,
+}
+
+impl LoginThrottle {
+    fn failures(&self) -> std::sync::MutexGuard<'_, HashMap<String, (i64, u32)>> {
+        self.failures.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 실패 횟수를 다 썼으면 남은 대기 시간을 담아 429를 돌려줍니다.
+    fn check(&self, key: &str) -> Result<(), AuthError> {
+        let now = unix_now();
+        let mut failures = self.failures();
+        failures.retain(|_, (since, _)| *since + LOGIN_WINDOW_SECS > now);
+        match failures.get(key) {
+            Some((since, count)) if *count >= MAX_LOGIN_FAILURES => Err(AuthError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                "over_request_rate_limit",
+                format!("For security purposes, you can only request this after {} seconds.", since + LOGIN_WINDOW_SECS - now),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn record_failure(&self, key: &str) {
+        let now = unix_now();
+        self.failures().entry(key.to_string()).or_insert((now, 0)).1 += 1;
+    }
+
+    fn clear(&self, key: &str) {
+        self.failures().remove(key);
+    }
+}
+
+struct User {
+    id: String,
+    email: String,
+    password_hash: Option<String>,
+    email_confirmed_at: Option<String>,
+    created_at: String,
+    updated_at: String,
+}
+
+impl User {
+    const COLUMNS: &'static str = "id, email, password_hash, email_confirmed_at, created_at, updated_at";
+
+    fn from_row(row: &rusqlite::Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            id: row.get(0)?,
+            email: row.get(1)?,
+            password_hash: row.get(2)?,
+            email_confirmed_at: row.get(3)?,
+            created_at: row.get(4)?,
+            updated_at: row.get(5)?,
+        })
+    }
+
+    fn find_by_email(conn: &Connection, email: &str) -> rusqlite::Result<Option<Self>> {
+        let sql = format!("SELECT {} FROM users WHERE email = ?1", Self::COLUMNS);
+        conn.query_row(&sql, [email], Self::from_row).optional()
+    }
+
+    fn find(conn: &Connection, id: &str) -> rusqlite::Result<Option<Self>> {
+        let sql = format!("SELECT {} FROM users WHERE id = ?1", Self::COLUMNS);
+        conn.query_row(&sql, [id], Self::from_row).optional()
+    }
+
+    fn create(conn: &Connection, email: &str, password_hash: Option<String>, confirmed: bool) -> rusqlite::Result<Self> {
+        let now = db::now();
+        let user = Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            email: email.to_string(),
+            password_hash,
+            email_confirmed_at: confirmed.then(|| now.clone()),
+            created_at: now.clone(),
+            updated_at: now,
+        };
+        conn.execute(
+            "INSERT INTO users (id, email, password_hash, email_confirmed_at, created_at, updated_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![user.id, user.email, user.password_hash, user.email_confirmed_at, user.created_at, user.updated_at],
+        )?;
+        tracing::info!("created user {} <{}>", user.id, user.email);
+        Ok(user)
+    }
+
+    /// 메일 링크나 코드로 이메일 주인임이 확인됐을 때 부릅니다. 확인 전의 비밀번호는 누가 정했는지 알 수
+    /// 없으므로(남의 이메일로 먼저 가입해 두는 공격), 가입 확인이면 그 메일을 보낸 가입 요청의 비밀번호로
+    /// 바꾸고 로그인 링크·코드나 재설정 링크이면 비밀번호를 지웁니다.
+    fn confirm_email(&mut self, conn: &Connection, token: &OneTimeToken) -> rusqlite::Result<()> {
+        if self.email_confirmed_at.is_none() {
+            let now = db::now();
+            let password_hash = token.password_hash.clone().filter(|_| token.kind == "signup");
+            conn.execute(
+                "UPDATE users SET email_confirmed_at = ?1, password_hash = ?2, updated_at = ?1 WHERE id = ?3",
+                params![now, password_hash, self.id],
+            )?;
+            self.email_confirmed_at = Some(now.clone());
+            self.password_hash = password_hash;
+            self.updated_at = now;
+        }
+        Ok(())
+    }
+
+    fn set_password(&mut self, conn: &Connection, password_hash: String) -> rusqlite::Result<()> {
+        let now = db::now();
+        conn.execute(
+            "UPDATE users SET password_hash = ?1, updated_at = ?2 WHERE id = ?3",
+            params![password_hash, now, self.id],
+        )?;
+        self.password_hash = Some(password_hash);
+        self.updated_at = now;
+        Ok(())
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "aud": "authenticated",
+            "role": "authenticated",
+            "email": self.email,
+            "email_confirmed_at": self.email_confirmed_at,
+            "created_at": self.created_at,
+            "updated_at": self.updated_at,
+        })
+    }
+}
+
+/// 새 세션(Access Token + Refresh Token)을 만듭니다.
+fn issue_session(state: &AppState, conn: &Connection, user: &User, session_id: Option<String>) -> Result<Value, AuthError> {
+    let session_id = session_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    let refresh_token = random_token(24);
+    conn.execute(
+        "INSERT INTO refresh_tokens (token, user_id, session_id, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![refresh_token, user.id, session_id, unix_now()],
+    )?;
+
+    let now = unix_now();
+    let expires_at = now + state.config.access_token_ttl;
+    let claims = Claims {
+        sub: user.id.clone(),
+        email: user.email.clone(),
+        role: "authenticated".to_string(),
+        aud: "authenticated".to_string(),
+        iat: now,
+        exp: expires_at,
+        session_id,
+    };
+    Ok(json!({
+        "access_token": jwt::encode(&state.config.jwt_secret, &claims),
+        "token_type": "bearer",
+        "expires_in": state.config.access_token_ttl,
+        "expires_at": expires_at,
+        "refresh_token": refresh_token,
+        "user": user.to_json(),
+    }))
+}
+
+fn validate_email(email: &str) -> Result<String, AuthError> {
+    let email = email.trim().to_lowercase();
+    let valid = email
+        .split_once('@')
+        .is_some_and(|(local, domain)| !local.is_empty() && domain.contains('.') && !email.contains(char::is_whitespace));
+    if valid {
+        Ok(email)
+    } else {
+        Err(AuthError::bad_request("validation_failed", "Unable to validate email address: invalid format"))
+    }
+}
+
+fn hash_password(password: &str) -> Result<String, AuthError> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(AuthError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "weak_password",
+            format!("Password should be at least {} characters.", MIN_PASSWORD_LENGTH),
+        ));
+    }
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|_| AuthError::new(StatusCode::INTERNAL_SERVER_ERROR, "unexpected_failure", "Failed to hash password"))
+}
+
+fn verify_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash).is_ok_and(|hash| Argon2::default().verify_password(password.as_bytes(), &hash).is_ok())
+}
+
+/// PKCE는 S256만 받습니다. 앱은 항상 S256을 씁니다.
+fn code_challenge(challenge: Option<String>, method: Option<String>) -> Result<Option<String>, AuthError> {
+    match (challenge, method) {
+        (None, _) => Ok(None),
+        (Some(challenge), method) if method.as_deref().is_none_or(|m| m.eq_ignore_ascii_case("s256")) => Ok(Some(challenge)),
+        _ => Err(AuthError::bad_request("validation_failed", "Code challenge method must be s256")),
+    }
+}
+
+/// 돌아갈 주소가 앱 주소나 허용된 주소 아래가 아니면 앱 주소로 돌려보냅니다.
+fn allowed_redirect(state: &AppState, redirect_to: Option<&str>) -> String {
+    let config = &state.config;
+    redirect_to
+        .filter(|url| {
+            std::iter::once(&config.site_url)
+                .chain(&config.redirect_urls)
+                .any(|allowed| is_under(url, allowed))
+        })
+        .unwrap_or(&config.site_url)
+        .to_string()
+}
+
+/// `http://localhost:8080`은 `http://localhost:8080/quiz-note/`는 허용하지만 `http://localhost:8080.example.com`은 허용하지 않습니다.
+fn is_under(url: &str, allowed: &str) -> bool {
+    let allowed = allowed.trim_end_matches('/');
+    url.strip_prefix(allowed)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['/', '?', '#']))
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// 로그인·가입 확인·비밀번호 재설정 메일 대신 링크와 코드를 로그에 남깁니다.
+/// 가입 확인 메일에는 그 가입 요청의 비밀번호(`password_hash`)를 묶어 둡니다.
+fn send_email(
+    state: &AppState,
+    conn: &Connection,
+    user: &User,
+    kind: &str,
+    password_hash: Option<&str>,
+    code_challenge: Option<String>,
+    redirect_to: Option<&str>,
+) -> Result<(), AuthError> {
+    let last_sent: Option<i64> = conn.query_row(
+        "SELECT max(created_at) FROM one_time_tokens WHERE user_id = ?1",
+        [&user.id],
+        |row| row.get(0),
+    )?;
+    if let Some(wait) = last_sent.map(|at| at + EMAIL_INTERVAL_SECS - unix_now()).filter(|wait| *wait > 0) {
+        return Err(AuthError::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            "over_email_send_rate_limit",
+            format!("For security purposes, you can only request this after {} seconds.", wait),
+        ));
+    }
+
+    let token = random_token(24);
+    let otp = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+    let redirect_to = allowed_redirect(state, redirect_to);
+    conn.execute(
+        "INSERT INTO one_time_tokens (token, user_id, kind, otp, password_hash, code_challenge, redirect_to, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![token, user.id, kind, otp, password_hash, code_challenge, redirect_to, unix_now()],
+    )?;
+
+    let link = format!(
+        "{}/auth/v1/verify?token={}&type={}&redirect_to={}",
+        state.config.public_url.trim_end_matches('/'),
+        token,
+        kind,
+        percent_encode(&redirect_to)
+    );
+    tracing::info!("{} email for <{}>: {} (code {})", kind, user.email, link, otp);
+    Ok(())
+}
+
+struct OneTimeToken {
+    user_id: String,
+    kind: String,
+    password_hash: Option<String>,
+    code_challenge: Option<String>,
+    redirect_to: String,
+    created_at: i64,
+}
+
+/// 메일 링크나 코드를 한 번 쓰고 지웁니다. 만료되었으면 `None`
+fn consume_token(conn: &Connection, column: &str, value: &str, kinds: &[&str], user_id: Option<&str>) -> rusqlite::Result<Option<OneTimeToken>> {
+    let sql = format!(
+        "SELECT token, user_id, kind, password_hash, code_challenge, redirect_to, created_at FROM one_time_tokens WHERE {} = ?1 AND (?2 IS NULL OR user_id = ?2) ORDER BY created_at DESC",
+        column
+    );
+    let found = conn
+        .query_row(&sql, params![value, user_id], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                OneTimeToken {
+                    user_id: row.get(1)?,
+                    kind: row.get(2)?,
+                    password_hash: row.get(3)?,
+                    code_challenge: row.get(4)?,
+                    redirect_to: row.get(5)?,
+                    created_at: row.get(6)?,
+                },
+            ))
+        })
+        .optional()?;
+    let Some((token, found)) = found else {
+        return Ok(None);
+    };
+    if !kinds.contains(&found.kind.as_str()) {
+        return Ok(None);
+    }
+    conn.execute("DELETE FROM one_time_tokens WHERE token = ?1", [&token])?;
+    Ok((found.created_at + ONE_TIME_TOKEN_TTL_SECS > unix_now()).then_some(found))
+}
+
+/// 틀린 인증 코드를 셉니다. `MAX_OTP_FAILURES`번 틀리면 그 사용자의 링크와 코드를 모두 지워
+/// 새 메일을 요청해야 하게 합니다.
+fn record_otp_failure(conn: &Connection, user_id: &str) -> rusqlite::Result<()> {
+    let failures: i64 = conn.query_row(
+        "INSERT INTO otp_failures (user_id, count) VALUES (?1, 1) ON CONFLICT (user_id) DO UPDATE SET count = count + 1 RETURNING count",
+        [user_id],
+        |row| row.get(0),
+    )?;
+    if failures >= MAX_OTP_FAILURES {
+        conn.execute("DELETE FROM one_time_tokens WHERE user_id = ?1", [user_id])?;
+        conn.execute("DELETE FROM otp_failures WHERE user_id = ?1", [user_id])?;
+        tracing::warn!("invalidated one-time tokens of user {} after {} wrong codes", user_id, failures);
+    }
+    Ok(())
+}
+
+/// 링크·코드의 `type`이 가리키는 토큰 종류
+fn token_kinds(kind: &str) -> &'static [&'static str] {
+    match kind {
+        "recovery" => &["recovery"],
+        "signup" => &["signup"],
+        _ => &["magiclink", "signup"],
+    }
+}
+
+#[derive(Deserialize)]
+struct RedirectQuery {
+    redirect_to: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct OtpRequest {
+    email: String,
+    #[serde(default = "default_true")]
+    create_user: bool,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+async fn otp(
+    State(state): State<AppState>,
+    Query(query): Query<RedirectQuery>,
+    Json(request): Json<OtpRequest>,
+) -> Result<Json<Value>, AuthError> {
+    let email = validate_email(&request.email)?;
+    let challenge = code_challenge(request.code_challenge, request.code_challenge_method)?;
+    let conn = state.db();
+    let user = match User::find_by_email(&conn, &email)? {
+        Some(user) => user,
+        None if request.create_user => User::create(&conn, &email, None, false)?,
+        None => {
+            return Err(AuthError::new(StatusCode::UNPROCESSABLE_ENTITY, "otp_disabled", "Signups not allowed for otp"));
+        }
+    };
+    send_email(&state, &conn, &user, "magiclink", None, challenge, query.redirect_to.as_deref())?;
+    Ok(Json(json!({})))
+}
+
+#[derive(Deserialize)]
+struct VerifyLinkQuery {
+    token: String,
+    #[serde(rename = "type", default)]
+    kind: String,
+    redirect_to: Option<String>,
+}
+
+/// 메일의 링크. PKCE로 요청했으면 `?code=`를, 아니면 `#access_token=`을 붙여 앱으로 돌려보냅니다.
+async fn verify_link(State(state): State<AppState>, Query(query): Query<VerifyLinkQuery>) -> Result<Response, AuthError> {
+    let conn = state.db();
+    let Some(token) = consume_token(&conn, "token", &query.token, token_kinds(&query.kind), None)? else {
+        let redirect_to = allowed_redirect(&state, query.redirect_to.as_deref());
+        let separator = if redirect_to.contains('?') { '&' } else { '?' };
+        let url = format!(
+            "{}{}error=access_denied&error_code=otp_expired&error_description={}",
+            redirect_to,
+            separator,
+            percent_encode("Email link is invalid or has expired")
+        );
+        return Ok(Redirect::to(&url).into_response());
+    };
+    let mut user = User::find(&conn, &token.user_id)?.ok_or_else(|| AuthError::bad_request("user_not_found", "User not found"))?;
+    user.confirm_email(&conn, &token)?;
+
+    let url = match token.code_challenge {
+        Some(challenge) => {
+            let auth_code = uuid::Uuid::new_v4().to_string();
+            conn.execute(
+                "INSERT INTO flow_states (auth_code, user_id, code_challenge, created_at) VALUES (?1, ?2, ?3, ?4)",
+                params![auth_code, user.id, challenge, unix_now()],
+            )?;
+            let separator = if token.redirect_to.contains('?') { '&' } else { '?' };
+            format!("{}{}code={}", token.redirect_to, separator, auth_code)
+        }
+        None => {
+            let session = issue_session(&state, &conn, &user, None)?;
+            format!(
+                "{}#access_token={}&expires_at={}&expires_in={}&refresh_token={}&token_type=bearer&type={}",
+                token.redirect_to,
+                session["access_token"].as_str().unwrap_or_default(),
+                session["expires_at"],
+                session["expires_in"],
+                session["refresh_token"].as_str().unwrap_or_default(),
+                token.kind
+            )
+        }
+    };
+    Ok(Redirect::to(&url).into_response())
+}
+
+#[derive(Deserialize)]
+struct VerifyCodeRequest {
+    #[serde(rename = "type")]
+    kind: String,
+    email: String,
+    token: String,
+}
+
+/// 메일의 6자리 코드로 로그인합니다. 틀린 코드는 사용자별(`record_otp_failure`)과
+/// 이메일별(`LoginThrottle`)로 셉니다.
+async fn verify_code(State(state): State<AppState>, Json(request): Json<VerifyCodeRequest>) -> Result<Json<Value>, AuthError> {
+    let expired = || AuthError::new(StatusCode::FORBIDDEN, "otp_expired", "Token has expired or is invalid");
+    let email = validate_email(&request.email)?;
+    let throttle_key = format!("verify:{}", email);
+    state.login_throttle.check(&throttle_key)?;
+    let conn = state.db();
+    let Some(mut user) = User::find_by_email(&conn, &email)? else {
+        state.login_throttle.record_failure(&throttle_key);
+        return Err(expired());
+    };
+    let Some(token) = consume_token(&conn, "otp", request.token.trim(), token_kinds(&request.kind), Some(&user.id))? else {
+        record_otp_failure(&conn, &user.id)?;
+        state.login_throttle.record_failure(&throttle_key);
+        return Err(expired());
+    };
+    conn.execute("DELETE FROM otp_failures WHERE user_id = ?1", [&user.id])?;
+    state.login_throttle.clear(&throttle_key);
+    user.confirm_email(&conn, &token)?;
+    Ok(Json(issue_session(&state, &conn, &user, None)?))
+}
+
+#[derive(Deserialize)]
+struct TokenQuery {
+    grant_type: String,
+}
+
+#[derive(Deserialize)]
+struct TokenRequest {
+    auth_code: Option<String>,
+    code_verifier: Option<String>,
+    email: Option<String>,
+    password: Option<String>,
+    refresh_token: Option<String>,
+}
+
+async fn token(
+    State(state): State<AppState>,
+    Query(query): Query<TokenQuery>,
+    Json(request): Json<TokenRequest>,
+) -> Result<Json<Value>, AuthError> {
+    let conn = state.db();
+    let session = match query.grant_type.as_str() {
+        "pkce" => {
+            let auth_code = request.auth_code.unwrap_or_default();
+            let verifier = request.code_verifier.unwrap_or_default();
+            let flow: Option<(String, String, i64)> = conn
+                .query_row(
+                    "SELECT user_id, code_challenge, created_at FROM flow_states WHERE auth_code = ?1",
+                    [&auth_code],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+                )
+                .optional()?;
+            let Some((user_id, challenge, created_at)) = flow else {
+                return Err(AuthError::new(StatusCode::NOT_FOUND, "flow_state_not_found", "invalid flow state, no valid flow state found"));
+            };
+            if created_at + FLOW_STATE_TTL_SECS < unix_now() {
+                conn.execute("DELETE FROM flow_states WHERE auth_code = ?1", [&auth_code])?;
+                return Err(AuthError::new(StatusCode::NOT_FOUND, "flow_state_expired", "invalid flow state, flow state has expired"));
+            }
+            if URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())) != challenge {
+                return Err(AuthError::new(
+                    StatusCode::FORBIDDEN,
+                    "bad_code_verifier",
+                    "code challenge does not match previously saved code verifier",
+                ));
+            }
+            conn.execute("DELETE FROM flow_states WHERE auth_code = ?1", [&auth_code])?;
+            let user = User::find(&conn, &user_id)?.ok_or_else(|| AuthError::bad_request("user_not_found", "User not found"))?;
+            issue_session(&state, &conn, &user, None)?
+        }
+        "password" => {
+            let invalid = || AuthError::bad_request("invalid_credentials", "Invalid login credentials");
+            let email = validate_email(&request.email.unwrap_or_default())?;
+            let password = request.password.unwrap_or_default();
+            let throttle_key = format!("password:{}", email);
+            state.login_throttle.check(&throttle_key)?;
+            let user = User::find_by_email(&conn, &email)?
+                .filter(|user| user.password_hash.as_deref().is_some_and(|hash| verify_password(hash, &password)));
+            let Some(user) = user else {
+                state.login_throttle.record_failure(&throttle_key);
+                return Err(invalid());
+            };
+            state.login_throttle.clear(&throttle_key);
+            if user.email_confirmed_at.is_none() {
+                return Err(AuthError::bad_request("email_not_confirmed", "Email not confirmed"));
+            }
+            issue_session(&state, &conn, &user, None)?
+        }
+        "refresh_token" => {
+            let not_found = || AuthError::bad_request("refresh_token_not_found", "Invalid Refresh Token: Refresh Token Not Found");
+            let refresh_token = request.refresh_token.unwrap_or_default();
+            let found: Option<(String, String)> = conn
+                .query_row(
+                    "SELECT user_id, session_id FROM refresh_tokens WHERE token = ?1 AND revoked = 0",
+                    [&refresh_token],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .optional()?;
+            let (user_id, session_id) = found.ok_or_else(not_found)?;
+            conn.execute("UPDATE refresh_tokens SET revoked = 1 WHERE token = ?1", [&refresh_token])?;
+            let user = User::find(&conn, &user_id)?.ok_or_else(not_found)?;
+            issue_session(&state, &conn, &user, Some(session_id))?
+        }
+        other => {
+            return Err(AuthError::bad_request(
+                "validation_failed",
+                format!("unsupported_grant_type: {}", other),
+            ));
+        }
+    };
+    Ok(Json(session))
+}
+
+#[derive(Deserialize)]
+struct SignupRequest {
+    email: String,
+    password: String,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+}
+
+/// 이메일 확인을 건너뛰면 바로 세션을, 아니면 확인 메일을 보내고 사용자만 돌려줍니다.
+async fn signup(
+    State(state): State<AppState>,
+    Query(query): Query<RedirectQuery>,
+    Json(request): Json<SignupRequest>,
+) -> Result<Json<Value>, AuthError> {
+    let email = validate_email(&request.email)?;
+    let challenge = code_challenge(request.code_challenge, request.code_challenge_method)?;
+    let password_hash = hash_password(&request.password)?;
+    let conn = state.db();
+
+    let user = match User::find_by_email(&conn, &email)? {
+        Some(user) if user.email_confirmed_at.is_some() => {
+            return Err(AuthError::new(StatusCode::UNPROCESSABLE_ENTITY, "user_already_exists", "User already registered"));
+        }
+        // 확인하지 않은 가입(또는 링크만 요청한 사용자)은 비밀번호를 그대로 두고 확인 메일만 다시 보냅니다.
+        // 새 비밀번호는 이 메일로 확인해야 적용됩니다. (`confirm_email`)
+        Some(user) => user,
+        None => User::create(&conn, &email, Some(password_hash.clone()), state.config.autoconfirm)?,
+    };
+
+    if user.email_confirmed_at.is_some() {
+        return Ok(Json(issue_session(&state, &conn, &user, None)?));
+    }
+    send_email(&state, &conn, &user, "signup", Some(&password_hash), challenge, query.redirect_to.as_deref())?;
+    Ok(Json(user.to_json()))
+}
+
+#[derive(Deserialize)]
+struct RecoverRequest {
+    email: String,
+    code_challenge: Option<String>,
+    code_challenge_method: Option<String>,
+}
+
+/// 가입하지 않은 이메일이어도 같은 응답을 돌려줍니다.
+async fn recover(
+    State(state): State<AppState>,
+    Query(query): Query<RedirectQuery>,
+    Json(request): Json<RecoverRequest>,
+) -> Result<Json<Value>, AuthError> {
+    let email = validate_email(&request.email)?;
+    let challenge = code_challenge(request.code_challenge, request.code_challenge_method)?;
+    let conn = state.db();
+    if let Some(user) = User::find_by_email(&conn, &email)? {
+        send_email(&state, &conn, &user, "recovery", None, challenge, query.redirect_to.as_deref())?;
+    }
+    Ok(Json(json!({})))
+}
+
+fn current_user(state: &AppState, conn: &Connection, headers: &HeaderMap) -> Result<User, AuthError> {
+    let claims = authenticate(state, headers).ok_or_else(AuthError::unauthorized)?;
+    User::find(conn, &claims.sub)?.ok_or_else(|| AuthError::new(StatusCode::FORBIDDEN, "user_not_found", "User from sub claim in JWT does not exist"))
+}
+
+async fn get_user(State(state): State<AppState>, headers: HeaderMap) -> Result<Json<Value>, AuthError> {
+    let conn = state.db();
+    Ok(Json(current_user(&state, &conn, &headers)?.to_json()))
+}
+
+#[derive(Deserialize)]
+struct UpdateUserRequest {
+    email: Option<String>,
+    password: Option<String>,
+}
+
+async fn update_user(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<UpdateUserRequest>,
+) -> Result<Json<Value>, AuthError> {
+    if request.email.is_some() {
+        return Err(AuthError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "validation_failed",
+            "Changing the email address is not supported",
+        ));
+    }
+    let conn = state.db();
+    let mut user = current_user(&state, &conn, &headers)?;
+    if let Some(password) = request.password {
+        if user.password_hash.as_deref().is_some_and(|hash| verify_password(hash, &password)) {
+            return Err(AuthError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                "same_password",
+                "New password should be different from the old password.",
+            ));
+        }
+        user.set_password(&conn, hash_password(&password)?)?;
+    }
+    Ok(Json(user.to_json()))
+}
+
+#[derive(Deserialize)]
+struct LogoutQuery {
+    #[serde(default)]
+    scope: Option<String>,
+}
+
+/// 기본(`global`)은 모든 기기의 세션을, `local`은 이 세션만 끝냅니다.
+async fn logout(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<LogoutQuery>,
+) -> Result<StatusCode, AuthError> {
+    let claims = authenticate(&state, &headers).ok_or_else(AuthError::unauthorized)?;
+    let conn = state.db();
+    if query.scope.as_deref() == Some("local") {
+        conn.execute("UPDATE refresh_tokens SET revoked = 1 WHERE session_id = ?1", [&claims.session_id])?;
+    } else {
+        conn.execute("UPDATE refresh_tokens SET revoked = 1 WHERE user_id = ?1", [&claims.sub])?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,57 @@
+// SQLite 연결과 스키마
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rand::RngCore;
+use rusqlite::{Connection, OptionalExtension};
+use std::path::Path;
+
+const SCHEMA: &str = include_str!("../schema.sql");
+
+/// DB 파일을 열고 스키마를 만듭니다. 경로가 `:memory:`이면 메모리 DB를 엽니다.
+pub fn open(path: impl AsRef<Path>) -> rusqlite::Result<Connection> {
+    let conn = Connection::open(path)?;
+    conn.pragma_update(None, "foreign_keys", true)?;
+    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.execute_batch(SCHEMA)?;
+    Ok(conn)
+}
+
+/// 토큰 서명 키. 처음 실행할 때 만들어 DB에 두므로 다시 시작해도 세션과 anon key가 그대로입니다.
+pub fn jwt_secret(conn: &Connection) -> rusqlite::Result<Vec<u8>> {
+    let stored: Option<String> = conn
+        .query_row("SELECT value FROM settings WHERE key = 'jwt_secret'", [], |row| row.get(0))
+        .optional()?;
+    if let Some(secret) = stored {
+        return Ok(secret.into_bytes());
+    }
+    let secret = random_token(32);
+    conn.execute("INSERT INTO settings (key, value) VALUES ('jwt_secret', ?1)", [&secret])?;
+    Ok(secret.into_bytes())
+}
+
+/// URL에 그대로 넣을 수 있는 임의의 문자열 (16진수)
+pub fn random_token(bytes: usize) -> String {
+    let mut buffer = vec![0u8; bytes];
+    rand::thread_rng().fill_bytes(&mut buffer);
+    buffer.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn now() -> String {
+    format_timestamp(Utc::now())
+}
+
+pub fn unix_now() -> i64 {
+    Utc::now().timestamp()
+}
+
+/// 시각을 저장하는 형식. 자릿수를 고정해 문자열 순서가 시각 순서와 같습니다.
+pub fn format_timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Micros, true)
+}
+
+/// RFC 3339 시각을 저장 형식으로 바꿉니다.
+pub fn normalize_timestamp(value: &str) -> Option<String> {
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|at| format_timestamp(at.with_timezone(&Utc)))
+}
@@ -0,0 +1,119 @@
+// 오류 응답
+//
+// GoTrue와 PostgREST는 오류 본문 모양이 다르고, 앱은 각각의 모양을 읽습니다.
+// - GoTrue: `{ "code": 400, "error_code": "invalid_credentials", "msg": "..." }`
+// - PostgREST: `{ "code": "23505", "message": "...", "details": null, "hint": null }`
+
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use rusqlite::ErrorCode;
+use serde_json::json;
+
+/// `/auth/v1` 오류
+#[derive(Debug)]
+pub struct AuthError {
+    pub status: StatusCode,
+    pub error_code: &'static str,
+    pub msg: String,
+}
+
+impl AuthError {
+    pub fn new(status: StatusCode, error_code: &'static str, msg: impl Into<String>) -> Self {
+        Self { status, error_code, msg: msg.into() }
+    }
+
+    pub fn bad_request(error_code: &'static str, msg: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, error_code, msg)
+    }
+
+    pub fn unauthorized() -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "no_authorization", "This endpoint requires a valid Bearer token")
+    }
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let body = json!({ "code": self.status.as_u16(), "error_code": self.error_code, "msg": self.msg });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+impl From<rusqlite::Error> for AuthError {
+    fn from(error: rusqlite::Error) -> Self {
+        tracing::error!("database error: {}", error);
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "unexpected_failure", "Database error")
+    }
+}
+
+/// `/rest/v1` 오류
+#[derive(Debug)]
+pub struct RestError {
+    pub status: StatusCode,
+    pub code: &'static str,
+    pub message: String,
+}
+
+impl RestError {
+    pub fn new(status: StatusCode, code: &'static str, message: impl Into<String>) -> Self {
+        Self { status, code, message: message.into() }
+    }
+
+    pub fn unauthorized() -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, "PGRST301", "JWT expired or invalid")
+    }
+
+    pub fn undefined_column(table: &str, column: &str) -> Self {
+        Self::new(
+            StatusCode::BAD_REQUEST,
+            "42703",
+            format!("column {}.{} does not exist", table, column),
+        )
+    }
+
+    pub fn invalid_input(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, "22P02", message)
+    }
+}
+
+impl IntoResponse for RestError {
+    fn into_response(self) -> Response {
+        let body = json!({ "code": self.code, "message": self.message, "details": null, "hint": null });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+/// 제약 조건 위반은 PostgreSQL의 오류 코드로 바꿔 돌려줍니다.
+impl From<rusqlite::Error> for RestError {
+    fn from(error: rusqlite::Error) -> Self {
+        if let rusqlite::Error::SqliteFailure(failure, message) = &error {
+            if failure.code == ErrorCode::ConstraintViolation {
+                let message = message.clone().unwrap_or_else(|| error.to_string());
+                return match failure.extended_code {
+                    rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY | rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE => {
+                        Self::new(StatusCode::CONFLICT, "23505", format!("duplicate key value violates unique constraint: {}", message))
+                    }
+                    rusqlite::ffi::SQLITE_CONSTRAINT_FOREIGNKEY => {
+                        Self::new(StatusCode::CONFLICT, "23503", "insert or update violates foreign key constraint")
+                    }
+                    rusqlite::ffi::SQLITE_CONSTRAINT_NOTNULL => {
+                        Self::new(StatusCode::BAD_REQUEST, "23502", format!("null value violates not-null constraint: {}", message))
+                    }
+                    _ => Self::new(StatusCode::BAD_REQUEST, "23514", message),
+                };
+            }
+        }
+        tracing::error!("database error: {}", error);
+        Self::new(StatusCode::INTERNAL_SERVER_ERROR, "XX000", "Database error")
+    }
+}
+
+/// `apikey`가 없거나 이 서버의 키가 아닐 때
+pub struct ApiKeyError;
+
+impl IntoResponse for ApiKeyError {
+    fn into_response(self) -> Response {
+        let body = json!({ "message": "Invalid API key", "hint": "Use the anon key printed when the server starts." });
+        (StatusCode::UNAUTHORIZED, Json(body)).into_response()
+    }
+}
@@ -0,0 +1,60 @@
+// HS256 JWT
+//
+// Access Token과 anon key를 만들고 확인합니다. 앱은 Access Token의 `exp`를 읽어 갱신 시점을 정하므로
+// GoTrue와 같은 클레임 이름을 씁니다.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use hmac::{Hmac, Mac};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const HEADER: &str = r#"{"alg":"HS256","typ":"JWT"}"#;
+
+/// 로그인한 사용자의 Access Token 클레임
+#[derive(Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub email: String,
+    pub role: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub session_id: String,
+}
+
+pub fn encode(secret: &[u8], claims: &impl Serialize) -> String {
+    let payload = serde_json::to_vec(claims).expect("claims are serializable");
+    let message = format!("{}.{}", URL_SAFE_NO_PAD.encode(HEADER), URL_SAFE_NO_PAD.encode(payload));
+    let mut mac = HmacSha256::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(message.as_bytes());
+    format!("{}.{}", message, URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes()))
+}
+
+/// 서명이 맞고 만료되지 않았으면 클레임을 돌려줍니다. `exp`가 없는 토큰(anon key)은 만료되지 않습니다.
+pub fn decode<T: DeserializeOwned>(secret: &[u8], token: &str) -> Option<T> {
+    let (message, signature) = token.rsplit_once('.')?;
+    let (header, payload) = message.split_once('.')?;
+    let header: serde_json::Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).ok()?).ok()?;
+    if header["alg"] != "HS256" {
+        return None;
+    }
+
+    let mut mac = HmacSha256::new_from_slice(secret).ok()?;
+    mac.update(message.as_bytes());
+    mac.verify_slice(&URL_SAFE_NO_PAD.decode(signature).ok()?).ok()?;
+
+    let payload = URL_SAFE_NO_PAD.decode(payload).ok()?;
+    let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+    if claims["exp"].as_i64().is_some_and(|exp| exp < chrono::Utc::now().timestamp()) {
+        return None;
+    }
+    serde_json::from_value(claims).ok()
+}
+
+/// 비밀 키에서 항상 같은 값이 나오도록 시각 없이 만듭니다.
+pub fn anon_key(secret: &[u8]) -> String {
+    encode(secret, &serde_json::json!({ "iss": "quiz-note-server", "role": "anon" }))
+}
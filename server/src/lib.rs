@@ -0,0 +1,117 @@
+// QuizNote 자체 호스팅 백엔드
+//
+// Supabase 없이 QuizNote를 쓰기 위한 서버입니다. 앱의 `SupabaseClient`와 `AuthService`가 부르는
+// PostgREST(`/rest/v1`)와 GoTrue(`/auth/v1`) 엔드포인트만 같은 모양으로 구현하고, 데이터는 SQLite에 저장합니다.
+// 메일은 보내지 않고 로그인 링크와 인증 코드를 로그에 남깁니다.
+//
+// 통합 테스트에서는 `AppState::new`로 메모리 DB를 열고 `serve`에 포트 0으로 연 리스너를 넘겨 씁니다.
+
+pub mod auth;
+pub mod db;
+pub mod error;
+pub mod jwt;
+pub mod rest;
+
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, HeaderName, Method};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::Router;
+use rusqlite::Connection;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tokio::net::TcpListener;
+use tower_http::cors::{Any, CorsLayer};
+use tower_http::trace::TraceLayer;
+
+pub struct Config {
+    /// 토큰 서명 키. anon key도 이 키로 서명합니다.
+    pub jwt_secret: Vec<u8>,
+    /// 바깥에서 이 서버에 접속하는 주소. 메일 링크에 씁니다.
+    pub public_url: String,
+    /// 앱 주소. `redirect_to`가 없거나 허용되지 않은 주소면 여기로 돌려보냅니다.
+    pub site_url: String,
+    /// `site_url` 말고 돌아갈 수 있는 주소 (그 주소 아래의 경로도 허용)
+    pub redirect_urls: Vec<String>,
+    /// 가입할 때 이메일 확인을 건너뛰고 바로 로그인시킬지
+    pub autoconfirm: bool,
+    /// Access Token 유효 시간(초)
+    pub access_token_ttl: i64,
+}
+
+#[derive(Clone)]
+pub struct AppState {
+    db: Arc<Mutex<Connection>>,
+    pub config: Arc<Config>,
+    login_throttle: Arc<auth::LoginThrottle>,
+}
+
+impl AppState {
+    pub fn new(db: Connection, config: Config) -> Self {
+        Self {
+            db: Arc::new(Mutex::new(db)),
+            config: Arc::new(config),
+            login_throttle: Arc::default(),
+        }
+    }
+
+    pub fn db(&self) -> MutexGuard<'_, Connection> {
+        self.db.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 앱의 `config.json`에 넣을 anon key
+    pub fn anon_key(&self) -> String {
+        jwt::anon_key(&self.config.jwt_secret)
+    }
+}
+
+pub fn router(state: AppState) -> Router {
+    let cors = CorsLayer::new()
+        .allow_origin(Any)
+        .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE])
+        // `Authorization`은 `*`에 포함되지 않으므로 직접 적습니다.
+        .allow_headers([
+            header::AUTHORIZATION,
+            header::CONTENT_TYPE,
+            HeaderName::from_static("apikey"),
+            HeaderName::from_static("prefer"),
+            HeaderName::from_static("x-client-info"),
+        ]);
+
+    Router::new()
+        .nest("/auth/v1", auth::router())
+        .nest("/rest/v1", rest::router())
+        .layer(middleware::from_fn_with_state(state.clone(), require_api_key))
+        .layer(TraceLayer::new_for_http())
+        .layer(cors)
+        .with_state(state)
+}
+
+pub async fn serve(listener: TcpListener, state: AppState) -> std::io::Result<()> {
+    axum::serve(listener, router(state)).await
+}
+
+/// Supabase의 API 게이트웨이처럼 `apikey` 헤더(또는 쿼리)에 이 서버의 키가 있어야 통과시킵니다.
+/// 메일 링크(`GET /auth/v1/verify`)는 브라우저가 바로 여는 주소라 키 없이 받습니다.
+async fn require_api_key(State(state): State<AppState>, headers: HeaderMap, request: Request, next: Next) -> Response {
+    if request.method() == Method::GET && request.uri().path() == "/auth/v1/verify" {
+        return next.run(request).await;
+    }
+    let from_query = request.uri().query().and_then(|query| {
+        query
+            .split('&')
+            .find_map(|pair| pair.strip_prefix("apikey="))
+            .map(str::to_string)
+    });
+    let key = headers
+        .get("apikey")
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+        .or(from_query);
+
+    match key {
+        Some(key) if jwt::decode::<serde_json::Value>(&state.config.jwt_secret, &key).is_some() => {
+            next.run(request).await
+        }
+        _ => error::ApiKeyError.into_response(),
+    }
+}
@@ -0,0 +1,49 @@
+// QuizNote 자체 호스팅 서버 실행 파일
+//
+// 환경 변수
+// - QUIZ_NOTE_SERVER_ADDR: 들을 주소 (기본 127.0.0.1:54321)
+// - QUIZ_NOTE_SERVER_DB: SQLite 파일 (기본 quiz-note.sqlite3, `:memory:`이면 메모리)
+// - QUIZ_NOTE_JWT_SECRET: 토큰 서명 키 (없으면 처음 실행할 때 만들어 DB에 저장)
+// - QUIZ_NOTE_PUBLIC_URL: 메일 링크에 쓸 이 서버의 주소 (기본 http://<ADDR>)
+// - QUIZ_NOTE_SITE_URL: 앱 주소 (기본 http://localhost:8080)
+// - QUIZ_NOTE_REDIRECT_URLS: 앱 주소 말고 돌아갈 수 있는 주소 (쉼표로 구분)
+// - QUIZ_NOTE_AUTOCONFIRM: true면 비밀번호 가입 때 이메일 확인을 건너뜀
+
+use quiz_note_server::{db, AppState, Config};
+use std::env;
+use tokio::net::TcpListener;
+use tracing_subscriber::EnvFilter;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
+
+    let addr = env::var("QUIZ_NOTE_SERVER_ADDR").unwrap_or_else(|_| "127.0.0.1:54321".to_string());
+    let db_path = env::var("QUIZ_NOTE_SERVER_DB").unwrap_or_else(|_| "quiz-note.sqlite3".to_string());
+    let conn = db::open(&db_path)?;
+
+    let jwt_secret = match env::var("QUIZ_NOTE_JWT_SECRET") {
+        Ok(secret) if !secret.is_empty() => secret.into_bytes(),
+        _ => db::jwt_secret(&conn)?,
+    };
+    let config = Config {
+        jwt_secret,
+        public_url: env::var("QUIZ_NOTE_PUBLIC_URL").unwrap_or_else(|_| format!("http://{}", addr)),
+        site_url: env::var("QUIZ_NOTE_SITE_URL").unwrap_or_else(|_| "http://localhost:8080".to_string()),
+        redirect_urls: env::var("QUIZ_NOTE_REDIRECT_URLS")
+            .map(|urls| urls.split(',').map(|url| url.trim().to_string()).filter(|url| !url.is_empty()).collect())
+            .unwrap_or_default(),
+        autoconfirm: env::var("QUIZ_NOTE_AUTOCONFIRM").is_ok_and(|value| value == "true" || value == "1"),
+        access_token_ttl: 60 * 60,
+    };
+    let public_url = config.public_url.clone();
+    let state = AppState::new(conn, config);
+
+    let listener = TcpListener::bind(&addr).await?;
+    tracing::info!("listening on http://{} (database: {})", listener.local_addr()?, db_path);
+    tracing::info!("config.json: {{ \"url\": \"{}\", \"anon_key\": \"{}\" }}", public_url, state.anon_key());
+    quiz_note_server::serve(listener, state).await?;
+    Ok(())
+}
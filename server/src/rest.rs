@@ -0,0 +1,570 @@
+// 데이터 (PostgREST 호환 `/rest/v1`)
+//
+// `SupabaseClient`가 쓰는 요청만 받습니다.
+// - `GET /<table>?<column>=<op>.<value>&select=*,question_options(*)&limit=<n>`
+// - `POST /<table>` (객체 하나 또는 배열), `PATCH /<table>?<filters>`, `DELETE /<table>?<filters>`
+// - `POST /rpc/delete_account` (계정 삭제, `{"dry_run": true}`이면 확인만)
+// 연산자는 eq, neq, lt, lte, gt, gte, is(null/true/false)와 `not.` 접두사를 받습니다.
+// 모든 요청은 로그인한 사용자의 행으로 제한되고(`user_id`), 새 행의 `user_id`는 토큰의 사용자로 채웁니다.
+// 로그인하지 않은 요청(anon key)은 Supabase의 행 보안 정책처럼 읽기는 빈 결과, 쓰기는 401입니다.
+
+use crate::auth::authenticate;
+use crate::db;
+use crate::error::RestError;
+use crate::{jwt, AppState};
+use axum::extract::{Path, Query, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{params_from_iter, Connection};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+pub fn router() -> Router<AppState> {
+    Router::new()
+        .route("/rpc/delete_account", post(delete_account))
+        .route("/:table", get(select).post(insert).patch(update).delete(delete))
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Text,
+    Integer,
+    Boolean,
+    Timestamp,
+}
+
+struct Column {
+    name: &'static str,
+    kind: Kind,
+    nullable: bool,
+    /// POST로 넣을 수 있는지
+    insert: bool,
+    /// PATCH로 바꿀 수 있는지
+    update: bool,
+}
+
+const fn column(name: &'static str, kind: Kind, insert: bool, update: bool) -> Column {
+    Column { name, kind, nullable: false, insert, update }
+}
+
+const fn nullable(name: &'static str, kind: Kind, insert: bool, update: bool) -> Column {
+    Column { name, kind, nullable: true, insert, update }
+}
+
+struct Table {
+    name: &'static str,
+    columns: &'static [Column],
+    /// 부모 테이블을 가리키는 열. 새 행의 부모도 같은 사용자의 것이어야 합니다.
+    parent: Option<(&'static str, &'static str)>,
+    /// `select`로 함께 읽을 수 있는 자식 테이블과 그 테이블에서 이 테이블을 가리키는 열
+    children: &'static [(&'static str, &'static str)],
+}
+
+const TABLES: &[Table] = &[
+    Table {
+        name: "certificates",
+        columns: &[
+            column("id", Kind::Text, true, false),
+            column("user_id", Kind::Text, false, false),
+            column("name", Kind::Text, true, true),
+            column("description", Kind::Text, true, true),
+            column("question_count", Kind::Integer, false, false),
+            column("created_at", Kind::Timestamp, false, false),
+            nullable("deleted_at", Kind::Timestamp, true, true),
+            column("imported", Kind::Boolean, true, true),
+        ],
+        parent: None,
+        children: &[("questions", "certificate_id")],
+    },
+    Table {
+        name: "questions",
+        columns: &[
+            column("id", Kind::Text, true, false),
+            column("user_id", Kind::Text, false, false),
+            column("certificate_id", Kind::Text, true, false),
+            column("content", Kind::Text, true, true),
+            column("explanation", Kind::Text, true, true),
+            column("created_at", Kind::Timestamp, false, false),
+            nullable("last_attempt", Kind::Timestamp, true, true),
+            column("attempt_count", Kind::Integer, true, true),
+            column("correct_count", Kind::Integer, true, true),
+            nullable("deleted_at", Kind::Timestamp, true, true),
+        ],
+        parent: Some(("certificate_id", "certificates")),
+        children: &[("question_options", "question_id")],
+    },
+    Table {
+        name: "question_options",
+        columns: &[
+            column("id", Kind::Text, true, false),
+            column("user_id", Kind::Text, false, false),
+            column("question_id", Kind::Text, true, false),
+            column("content", Kind::Text, true, true),
+            column("is_correct", Kind::Boolean, true, true),
+            column("explanation", Kind::Text, true, true),
+            column("display_order", Kind::Integer, true, true),
+        ],
+        parent: Some(("question_id", "questions")),
+        children: &[],
+    },
+];
+
+impl Table {
+    fn find(name: &str) -> Result<&'static Table, RestError> {
+        TABLES.iter().find(|table| table.name == name).ok_or_else(|| {
+            RestError::new(
+                StatusCode::NOT_FOUND,
+                "42P01",
+                format!("relation \"public.{}\" does not exist", name),
+            )
+        })
+    }
+
+    fn column(&self, name: &str) -> Result<&'static Column, RestError> {
+        self.columns
+            .iter()
+            .find(|column| column.name == name)
+            .ok_or_else(|| RestError::undefined_column(self.name, name))
+    }
+
+    fn has_column(&self, name: &str) -> bool {
+        self.columns.iter().any(|column| column.name == name)
+    }
+}
+
+impl Column {
+    /// JSON 값을 DB 값으로 바꿉니다.
+    fn to_sql(&self, value: &Value) -> Result<SqlValue, RestError> {
+        let invalid = || RestError::invalid_input(format!("invalid input for column \"{}\": {}", self.name, value));
+        match (self.kind, value) {
+            (_, Value::Null) if self.nullable => Ok(SqlValue::Null),
+            (Kind::Text, Value::String(text)) => Ok(SqlValue::Text(text.clone())),
+            (Kind::Integer, Value::Number(number)) => number.as_i64().map(SqlValue::Integer).ok_or_else(invalid),
+            (Kind::Boolean, Value::Bool(flag)) => Ok(SqlValue::Integer(*flag as i64)),
+            (Kind::Timestamp, Value::String(text)) => db::normalize_timestamp(text).map(SqlValue::Text).ok_or_else(invalid),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// 쿼리 문자열의 필터 값을 DB 값으로 바꿉니다.
+    fn parse(&self, value: &str) -> Result<SqlValue, RestError> {
+        let invalid = || RestError::invalid_input(format!("invalid input for column \"{}\": \"{}\"", self.name, value));
+        match self.kind {
+            Kind::Text => Ok(SqlValue::Text(value.to_string())),
+            Kind::Integer => value.parse().map(SqlValue::Integer).map_err(|_| invalid()),
+            Kind::Boolean => match value {
+                "true" => Ok(SqlValue::Integer(1)),
+                "false" => Ok(SqlValue::Integer(0)),
+                _ => Err(invalid()),
+            },
+            Kind::Timestamp => db::normalize_timestamp(value).map(SqlValue::Text).ok_or_else(invalid),
+        }
+    }
+
+    /// DB 값을 JSON 값으로 바꿉니다.
+    fn to_json(&self, value: SqlValue) -> Value {
+        match (self.kind, value) {
+            (_, SqlValue::Null) => Value::Null,
+            (Kind::Boolean, SqlValue::Integer(flag)) => Value::Bool(flag != 0),
+            (_, SqlValue::Integer(number)) => Value::from(number),
+            (_, SqlValue::Real(number)) => Value::from(number),
+            (_, SqlValue::Text(text)) => Value::String(text),
+            (_, SqlValue::Blob(_)) => Value::Null,
+        }
+    }
+}
+
+/// `WHERE` 절과 매개변수. 첫 조건은 항상 `user_id = <로그인한 사용자>`이고, 로그인하지 않았으면 NULL이라
+/// 어떤 행과도 맞지 않습니다.
+struct Filter {
+    clauses: Vec<String>,
+    values: Vec<SqlValue>,
+}
+
+impl Filter {
+    fn for_user(user_id: Option<&str>) -> Self {
+        Self {
+            clauses: vec!["user_id = ?".to_string()],
+            values: vec![user_id.map_or(SqlValue::Null, |id| SqlValue::Text(id.to_string()))],
+        }
+    }
+
+    /// `<column>=[not.]<op>.<value>`
+    fn add(&mut self, table: &Table, name: &str, expression: &str) -> Result<(), RestError> {
+        let column = table.column(name)?;
+        let (negate, expression) = match expression.strip_prefix("not.") {
+            Some(rest) => (true, rest),
+            None => (false, expression),
+        };
+        let (operator, value) = expression
+            .split_once('.')
+            .ok_or_else(|| RestError::new(StatusCode::BAD_REQUEST, "PGRST100", format!("failed to parse filter ({})", expression)))?;
+
+        let clause = match operator {
+            "is" => {
+                let test = match value {
+                    "null" => "IS NULL",
+                    "true" => "= 1",
+                    "false" => "= 0",
+                    _ => return Err(RestError::new(StatusCode::BAD_REQUEST, "PGRST100", format!("failed to parse filter (is.{})", value))),
+                };
+                format!("{} {}", column.name, test)
+            }
+            _ => {
+                let sql_operator = match operator {
+                    "eq" => "=",
+                    "neq" => "<>",
+                    "lt" => "<",
+                    "lte" => "<=",
+                    "gt" => ">",
+                    "gte" => ">=",
+                    _ => return Err(RestError::new(StatusCode::BAD_REQUEST, "PGRST100", format!("unknown operator ({})", operator))),
+                };
+                self.values.push(column.parse(value)?);
+                format!("{} {} ?", column.name, sql_operator)
+            }
+        };
+        self.clauses.push(if negate { format!("NOT ({})", clause) } else { clause });
+        Ok(())
+    }
+
+    fn sql(&self) -> String {
+        self.clauses.join(" AND ")
+    }
+}
+
+/// `select`의 항목: 열 이름 목록(비어 있으면 `*`)과 함께 읽을 자식 테이블
+struct Selection {
+    columns: Vec<&'static Column>,
+    children: Vec<(&'static Table, &'static str, Selection)>,
+}
+
+impl Selection {
+    fn parse(table: &'static Table, select: &str) -> Result<Self, RestError> {
+        let mut selection = Selection { columns: Vec::new(), children: Vec::new() };
+        let mut all = select.trim().is_empty();
+        for item in split_top_level(select) {
+            let item = item.trim();
+            if item == "*" {
+                all = true;
+            } else if let Some((name, inner)) = item.strip_suffix(')').and_then(|item| item.split_once('(')) {
+                let (child, foreign_key) = table
+                    .children
+                    .iter()
+                    .find(|(child, _)| *child == name)
+                    .map(|(child, foreign_key)| (Table::find(child), *foreign_key))
+                    .ok_or_else(|| {
+                        RestError::new(
+                            StatusCode::BAD_REQUEST,
+                            "PGRST200",
+                            format!("Could not find a relationship between '{}' and '{}'", table.name, name),
+                        )
+                    })?;
+                let child = child?;
+                selection.children.push((child, foreign_key, Selection::parse(child, inner)?));
+            } else if !item.is_empty() {
+                selection.columns.push(table.column(item)?);
+            }
+        }
+        if all {
+            selection.columns = table.columns.iter().collect();
+        }
+        Ok(selection)
+    }
+
+    /// 행을 읽어 JSON 객체로 만듭니다. 자식 테이블은 행마다 따로 읽어 배열로 붙입니다.
+    fn query(&self, conn: &Connection, table: &Table, filter: &Filter, suffix: &str) -> Result<Vec<Value>, RestError> {
+        // 자식을 찾으려면 `id`가 필요하므로 선택하지 않았어도 읽습니다.
+        let mut columns: Vec<&Column> = self.columns.clone();
+        if !self.children.is_empty() && !columns.iter().any(|column| column.name == "id") {
+            columns.push(table.column("id")?);
+        }
+        let names: Vec<&str> = columns.iter().map(|column| column.name).collect();
+        let sql = format!("SELECT {} FROM {} WHERE {}{}", names.join(", "), table.name, filter.sql(), suffix);
+
+        let mut statement = conn.prepare(&sql)?;
+        let rows = statement.query_map(params_from_iter(filter.values.iter()), |row| {
+            (0..columns.len()).map(|i| row.get::<_, SqlValue>(i)).collect::<rusqlite::Result<Vec<_>>>()
+        })?;
+
+        let user_id = &filter.values[0];
+        let mut objects = Vec::new();
+        for row in rows {
+            let mut object = Map::new();
+            let mut id = None;
+            for (column, value) in columns.iter().zip(row?) {
+                if column.name == "id" {
+                    id = Some(value.clone());
+                }
+                if self.columns.iter().any(|selected| selected.name == column.name) {
+                    object.insert(column.name.to_string(), column.to_json(value));
+                }
+            }
+            for (child, foreign_key, selection) in &self.children {
+                let child_filter = Filter {
+                    clauses: vec!["user_id = ?".to_string(), format!("{} = ?", foreign_key)],
+                    values: vec![user_id.clone(), id.clone().unwrap_or(SqlValue::Null)],
+                };
+                let rows = selection.query(conn, child, &child_filter, "")?;
+                object.insert(child.name.to_string(), Value::Array(rows));
+            }
+            objects.push(Value::Object(object));
+        }
+        Ok(objects)
+    }
+}
+
+/// 괄호 안의 쉼표는 나누지 않습니다. (`*,question_options(id,content)`)
+fn split_top_level(select: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in select.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&select[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&select[start..]);
+    parts
+}
+
+/// 쿼리 문자열에서 `select`, `limit`, `offset`, `order`를 빼고 나머지를 필터로 읽습니다.
+struct ReadQuery {
+    select: String,
+    filter: Filter,
+    suffix: String,
+}
+
+impl ReadQuery {
+    fn parse(table: &Table, user_id: Option<&str>, params: Vec<(String, String)>) -> Result<Self, RestError> {
+        let mut query = ReadQuery {
+            select: "*".to_string(),
+            filter: Filter::for_user(user_id),
+            suffix: String::new(),
+        };
+        let mut order = String::new();
+        let mut limit = String::new();
+        for (key, value) in params {
+            match key.as_str() {
+                "select" => query.select = value,
+                "limit" | "offset" => {
+                    let number: u64 = value.parse().map_err(|_| RestError::invalid_input(format!("invalid {}: {}", key, value)))?;
+                    limit.push_str(&format!(" {} {}", key.to_uppercase(), number));
+                }
+                "order" => {
+                    let terms = value
+                        .split(',')
+                        .map(|term| {
+                            let mut parts = term.split('.');
+                            let column = table.column(parts.next().unwrap_or_default())?;
+                            let direction = match parts.next() {
+                                Some("desc") => "DESC",
+                                _ => "ASC",
+                            };
+                            Ok(format!("{} {}", column.name, direction))
+                        })
+                        .collect::<Result<Vec<_>, RestError>>()?;
+                    order = format!(" ORDER BY {}", terms.join(", "));
+                }
+                "apikey" => {}
+                _ => query.filter.add(table, &key, &value)?,
+            }
+        }
+        // SQLite는 OFFSET만 쓸 수 없으므로 LIMIT이 없으면 -1(제한 없음)을 넣습니다.
+        if limit.contains("OFFSET") && !limit.contains("LIMIT") {
+            limit = format!(" LIMIT -1{}", limit);
+        }
+        query.suffix = format!("{}{}", order, limit);
+        Ok(query)
+    }
+}
+
+fn user_id(state: &AppState, headers: &HeaderMap) -> Result<String, RestError> {
+    authenticate(state, headers).map(|claims| claims.sub).ok_or_else(RestError::unauthorized)
+}
+
+/// 읽기 요청의 사용자. `Authorization`이 없거나 anon key이면 `None`
+fn reader(state: &AppState, headers: &HeaderMap) -> Result<Option<String>, RestError> {
+    let Some(authorization) = headers.get("authorization") else {
+        return Ok(None);
+    };
+    if let Some(claims) = authenticate(state, headers) {
+        return Ok(Some(claims.sub));
+    }
+    let is_anon = authorization
+        .to_str()
+        .ok()
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .and_then(|token| jwt::decode::<Value>(&state.config.jwt_secret, token.trim()))
+        .is_some_and(|claims| claims["role"] == "anon");
+    if is_anon {
+        Ok(None)
+    } else {
+        Err(RestError::unauthorized())
+    }
+}
+
+async fn select(
+    State(state): State<AppState>,
+    Path(table): Path<String>,
+    Query(params): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<Value>>, RestError> {
+    let table = Table::find(&table)?;
+    let user_id = reader(&state, &headers)?;
+    let query = ReadQuery::parse(table, user_id.as_deref(), params)?;
+    let selection = Selection::parse(table, &query.select)?;
+    let conn = state.db();
+    Ok(Json(selection.query(&conn, table, &query.filter, &query.suffix)?))
+}
+
+/// 객체 하나 또는 배열을 한 트랜잭션으로 넣습니다.
+async fn insert(
+    State(state): State<AppState>,
+    Path(table): Path<String>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<StatusCode, RestError> {
+    let table = Table::find(&table)?;
+    let user_id = user_id(&state, &headers)?;
+    let rows = match body {
+        Value::Array(rows) => rows,
+        row => vec![row],
+    };
+
+    let mut conn = state.db();
+    let transaction = conn.transaction()?;
+    for row in rows {
+        let Value::Object(row) = row else {
+            return Err(RestError::invalid_input("request body must be an object or an array of objects"));
+        };
+        let mut names = vec!["user_id"];
+        let mut values = vec![SqlValue::Text(user_id.clone())];
+        if table.has_column("created_at") {
+            names.push("created_at");
+            values.push(SqlValue::Text(db::now()));
+        }
+        for (name, value) in &row {
+            let column = table.column(name)?;
+            if !column.insert {
+                return Err(writable_error(table, column));
+            }
+            names.push(column.name);
+            values.push(column.to_sql(value)?);
+        }
+        if let Some((foreign_key, parent)) = table.parent {
+            check_parent(&transaction, parent, row.get(foreign_key), &user_id)?;
+        }
+
+        let placeholders = vec!["?"; names.len()].join(", ");
+        let sql = format!("INSERT INTO {} ({}) VALUES ({})", table.name, names.join(", "), placeholders);
+        transaction.execute(&sql, params_from_iter(values.iter()))?;
+    }
+    transaction.commit()?;
+    Ok(StatusCode::CREATED)
+}
+
+async fn update(
+    State(state): State<AppState>,
+    Path(table): Path<String>,
+    Query(params): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+    Json(body): Json<Value>,
+) -> Result<StatusCode, RestError> {
+    let table = Table::find(&table)?;
+    let user_id = user_id(&state, &headers)?;
+    let query = ReadQuery::parse(table, Some(&user_id), params)?;
+    let Value::Object(row) = body else {
+        return Err(RestError::invalid_input("request body must be an object"));
+    };
+    if row.is_empty() {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+
+    let mut assignments = Vec::new();
+    let mut values = Vec::new();
+    for (name, value) in &row {
+        let column = table.column(name)?;
+        if !column.update {
+            return Err(writable_error(table, column));
+        }
+        assignments.push(format!("{} = ?", column.name));
+        values.push(column.to_sql(value)?);
+    }
+    values.extend(query.filter.values.iter().cloned());
+
+    let sql = format!("UPDATE {} SET {} WHERE {}", table.name, assignments.join(", "), query.filter.sql());
+    state.db().execute(&sql, params_from_iter(values.iter()))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 자식 행은 외래 키(`ON DELETE CASCADE`)로 함께 지워집니다.
+async fn delete(
+    State(state): State<AppState>,
+    Path(table): Path<String>,
+    Query(params): Query<Vec<(String, String)>>,
+    headers: HeaderMap,
+) -> Result<StatusCode, RestError> {
+    let table = Table::find(&table)?;
+    let user_id = user_id(&state, &headers)?;
+    let query = ReadQuery::parse(table, Some(&user_id), params)?;
+    let sql = format!("DELETE FROM {} WHERE {}", table.name, query.filter.sql());
+    state.db().execute(&sql, params_from_iter(query.filter.values.iter()))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[derive(Deserialize)]
+struct DeleteAccountRequest {
+    #[serde(default)]
+    dry_run: bool,
+}
+
+/// Supabase 마이그레이션의 `public.delete_account()`와 같습니다. 사용자를 지우면 자격증·문제·보기와
+/// Refresh Token이 외래 키로 함께 지워져, 다른 기기도 다음 토큰 갱신 때 로그아웃됩니다.
+async fn delete_account(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Json(request): Json<DeleteAccountRequest>,
+) -> Result<StatusCode, RestError> {
+    let user_id = user_id(&state, &headers)?;
+    if !request.dry_run {
+        state.db().execute("DELETE FROM users WHERE id = ?1", [&user_id])?;
+    }
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// 다른 사용자의 자격증·문제 아래에는 행을 만들 수 없습니다. (없는 부모는 외래 키 오류로 둡니다)
+fn check_parent(conn: &Connection, parent: &str, id: Option<&Value>, user_id: &str) -> Result<(), RestError> {
+    let Some(Value::String(id)) = id else {
+        return Ok(());
+    };
+    let owner: Option<String> = rusqlite::OptionalExtension::optional(conn.query_row(
+        &format!("SELECT user_id FROM {} WHERE id = ?1", parent),
+        [id],
+        |row| row.get(0),
+    ))?;
+    match owner {
+        Some(owner) if owner != user_id => Err(RestError::new(
+            StatusCode::FORBIDDEN,
+            "42501",
+            format!("new row violates row-level security policy for table \"{}\"", parent),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn writable_error(table: &Table, column: &Column) -> RestError {
+    RestError::new(
+        StatusCode::BAD_REQUEST,
+        "42501",
+        format!("permission denied for column {}.{}", table.name, column.name),
+    )
+}
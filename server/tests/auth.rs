@@ -0,0 +1,233 @@
+// `/auth/v1` 흐름: 메일 링크(PKCE), 인증 코드, 비밀번호 가입·로그인, 토큰 갱신
+
+mod common;
+
+use common::{code_challenge, Session, TestServer, PASSWORD, SITE_URL};
+use reqwest::{Method, StatusCode};
+use serde_json::json;
+
+const VERIFIER: &str = "integration-test-code-verifier-0123456789-abcdefghijklmnopqrstuvwxyz";
+
+#[tokio::test]
+async fn email_link_is_exchanged_for_a_session_with_pkce() {
+    let server = TestServer::start().await;
+    let email = "link@example.com";
+    let (status, body) = server
+        .auth(
+            &format!("otp?redirect_to={}/", SITE_URL),
+            json!({ "email": email, "code_challenge": code_challenge(VERIFIER), "code_challenge_method": "s256" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    // 메일의 링크는 브라우저가 바로 여는 주소라 `apikey` 없이도 열립니다.
+    let (token, _) = server.last_email(email);
+    let response = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .unwrap()
+        .get(format!("{}/auth/v1/verify?token={}&type=magiclink", server.url, token))
+        .send()
+        .await
+        .unwrap();
+    assert!(response.status().is_redirection());
+    let location = response.headers()["location"].to_str().unwrap().to_string();
+    assert!(location.starts_with(SITE_URL), "{}", location);
+    let (_, auth_code) = location.split_once("code=").expect("redirect has ?code=");
+
+    let (status, body) = server
+        .auth("token?grant_type=pkce", json!({ "auth_code": auth_code, "code_verifier": "another verifier" }))
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error_code"], "bad_code_verifier");
+
+    let (status, body) =
+        server.auth("token?grant_type=pkce", json!({ "auth_code": auth_code, "code_verifier": VERIFIER })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let session = Session::from_json(&body);
+    assert_eq!(body["user"]["email"], email);
+
+    let response = server.request(Method::GET, "/auth/v1/user", Some(&session.access_token)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // 인증 코드와 링크는 한 번만 쓸 수 있습니다.
+    let (status, body) =
+        server.auth("token?grant_type=pkce", json!({ "auth_code": auth_code, "code_verifier": VERIFIER })).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    assert_eq!(body["error_code"], "flow_state_not_found");
+}
+
+#[tokio::test]
+async fn email_code_signs_in_once() {
+    let server = TestServer::start().await;
+    let email = "code@example.com";
+    server.auth("otp", json!({ "email": email })).await;
+    let (_, otp) = server.last_email(email);
+
+    let (status, body) = server.auth("verify", json!({ "type": "email", "email": email, "token": otp })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["user"]["email"], email);
+    assert!(body["user"]["email_confirmed_at"].is_string());
+
+    let (status, body) = server.auth("verify", json!({ "type": "email", "email": email, "token": otp })).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["error_code"], "otp_expired");
+}
+
+#[tokio::test]
+async fn wrong_codes_invalidate_outstanding_codes() {
+    let server = TestServer::start().await;
+    let email = "guess@example.com";
+    server.auth("otp", json!({ "email": email })).await;
+    let (_, otp) = server.last_email(email);
+    let wrong = format!("{:06}", (otp.parse::<u32>().unwrap() + 1) % 1_000_000);
+
+    for _ in 0..5 {
+        let (status, body) = server.auth("verify", json!({ "type": "email", "email": email, "token": wrong })).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error_code"], "otp_expired");
+    }
+    let (status, _) = server.auth("verify", json!({ "type": "email", "email": email, "token": otp })).await;
+    assert_eq!(status, StatusCode::FORBIDDEN, "the right code must stop working after five wrong ones");
+}
+
+#[tokio::test]
+async fn repeated_code_failures_are_throttled_per_email() {
+    let server = TestServer::start().await;
+    let email = "unknown@example.com";
+    for _ in 0..10 {
+        let (status, _) = server.auth("verify", json!({ "type": "email", "email": email, "token": "000000" })).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+    let (status, body) = server.auth("verify", json!({ "type": "email", "email": email, "token": "000000" })).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(body["error_code"], "over_request_rate_limit");
+}
+
+#[tokio::test]
+async fn password_sign_up_requires_confirmation_before_sign_in() {
+    let server = TestServer::start().await;
+    let email = "password@example.com";
+    let (status, body) = server
+        .auth(
+            &format!("signup?redirect_to={}/", SITE_URL),
+            json!({ "email": email, "password": PASSWORD, "code_challenge": code_challenge(VERIFIER), "code_challenge_method": "s256" }),
+        )
+        .await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert!(body.get("access_token").is_none());
+    assert_eq!(body["email"], email);
+
+    let credentials = json!({ "email": email, "password": PASSWORD });
+    let (status, body) = server.auth("token?grant_type=password", credentials.clone()).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "email_not_confirmed");
+
+    let (token, _) = server.last_email(email);
+    let response = server.request(Method::GET, &format!("/auth/v1/verify?token={}&type=signup", token), None).send().await.unwrap();
+    assert!(response.status().is_redirection());
+
+    let (status, body) = server.auth("token?grant_type=password", credentials).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body["user"]["email"], email);
+
+    let (status, body) = server.auth("token?grant_type=password", json!({ "email": email, "password": "wrong password" })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "invalid_credentials");
+}
+
+// 남의 이메일로 먼저 가입해 둔 비밀번호가 주인이 이메일을 확인한 뒤에도 남으면 안 됩니다.
+#[tokio::test]
+async fn email_code_confirmation_drops_a_password_set_before_confirming() {
+    let server = TestServer::start().await;
+    let email = "victim@example.com";
+    let attacker = json!({ "email": email, "password": "attacker password" });
+    let (status, body) = server.auth("signup", attacker.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    server.skip_email_interval();
+    server.auth("otp", json!({ "email": email })).await;
+    let (_, otp) = server.last_email(email);
+    let (status, body) = server.auth("verify", json!({ "type": "email", "email": email, "token": otp })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    let (status, body) = server.auth("token?grant_type=password", attacker).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "invalid_credentials");
+}
+
+#[tokio::test]
+async fn signing_up_again_applies_the_password_of_the_confirmed_mail() {
+    let server = TestServer::start().await;
+    let email = "again@example.com";
+    let attacker = json!({ "email": email, "password": "attacker password" });
+    server.auth("signup", attacker.clone()).await;
+    server.skip_email_interval();
+    let owner = json!({ "email": email, "password": PASSWORD });
+    let (status, body) = server.auth("signup", owner.clone()).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+
+    // 다시 가입해도 확인하기 전에는 비밀번호가 바뀌지 않습니다.
+    let (_, body) = server.auth("token?grant_type=password", owner.clone()).await;
+    assert_eq!(body["error_code"], "invalid_credentials");
+
+    let (token, _) = server.last_email(email);
+    let response = server.request(Method::GET, &format!("/auth/v1/verify?token={}&type=signup", token), None).send().await.unwrap();
+    assert!(response.status().is_redirection());
+
+    let (status, body) = server.auth("token?grant_type=password", owner).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let (status, body) = server.auth("token?grant_type=password", attacker).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "invalid_credentials");
+}
+
+#[tokio::test]
+async fn password_sign_in_is_throttled_after_repeated_failures() {
+    let server = TestServer::start_with(true).await;
+    let email = "throttle@example.com";
+    server.sign_up(email).await;
+    for _ in 0..10 {
+        let (status, _) = server.auth("token?grant_type=password", json!({ "email": email, "password": "wrong password" })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+    let (status, body) = server.auth("token?grant_type=password", json!({ "email": email, "password": PASSWORD })).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(body["error_code"], "over_request_rate_limit");
+}
+
+#[tokio::test]
+async fn refresh_tokens_rotate_and_cannot_be_reused() {
+    let server = TestServer::start_with(true).await;
+    let first = server.sign_up("refresh@example.com").await;
+
+    let (status, body) = server.auth("token?grant_type=refresh_token", json!({ "refresh_token": first.refresh_token })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    let second = Session::from_json(&body);
+    assert_ne!(second.refresh_token, first.refresh_token);
+    assert_eq!(second.user_id, first.user_id);
+
+    let (status, body) = server.auth("token?grant_type=refresh_token", json!({ "refresh_token": first.refresh_token })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["error_code"], "refresh_token_not_found");
+
+    let (status, body) = server.auth("token?grant_type=refresh_token", json!({ "refresh_token": second.refresh_token })).await;
+    assert_eq!(status, StatusCode::OK, "{}", body);
+}
+
+#[tokio::test]
+async fn global_logout_revokes_every_session() {
+    let server = TestServer::start_with(true).await;
+    let email = "logout@example.com";
+    let first = server.sign_up(email).await;
+    let (_, body) = server.auth("token?grant_type=password", json!({ "email": email, "password": PASSWORD })).await;
+    let second = Session::from_json(&body);
+
+    let response = server.request(Method::POST, "/auth/v1/logout", Some(&first.access_token)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    for session in [first, second] {
+        let (status, _) = server.auth("token?grant_type=refresh_token", json!({ "refresh_token": session.refresh_token })).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,130 @@
+// 통합 테스트 도우미
+//
+// 테스트마다 메모리 DB로 서버를 새로 띄우고(포트 0), 앱처럼 `apikey` 헤더를 붙여 HTTP로 요청합니다.
+// 메일은 보내지 않으므로 링크와 코드는 DB의 `one_time_tokens`에서 읽습니다.
+
+#![allow(dead_code)]
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use quiz_note_server::{db, AppState, Config};
+use reqwest::{Method, RequestBuilder, StatusCode};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use tokio::net::TcpListener;
+
+pub const SITE_URL: &str = "http://localhost:8080";
+pub const PASSWORD: &str = "correct horse battery";
+
+pub struct TestServer {
+    pub state: AppState,
+    pub url: String,
+    pub anon_key: String,
+    client: reqwest::Client,
+}
+
+/// 로그인 응답에서 테스트에 쓰는 부분
+pub struct Session {
+    pub access_token: String,
+    pub refresh_token: String,
+    pub user_id: String,
+}
+
+impl Session {
+    pub fn from_json(body: &Value) -> Self {
+        Self {
+            access_token: body["access_token"].as_str().expect("access_token").to_string(),
+            refresh_token: body["refresh_token"].as_str().expect("refresh_token").to_string(),
+            user_id: body["user"]["id"].as_str().expect("user.id").to_string(),
+        }
+    }
+}
+
+impl TestServer {
+    pub async fn start() -> Self {
+        Self::start_with(false).await
+    }
+
+    /// `autoconfirm`이 true면 비밀번호 가입이 바로 세션을 돌려줍니다.
+    pub async fn start_with(autoconfirm: bool) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let config = Config {
+            jwt_secret: b"integration-test-secret".to_vec(),
+            public_url: url.clone(),
+            site_url: SITE_URL.to_string(),
+            redirect_urls: Vec::new(),
+            autoconfirm,
+            access_token_ttl: 60 * 60,
+        };
+        let state = AppState::new(db::open(":memory:").unwrap(), config);
+        tokio::spawn(quiz_note_server::serve(listener, state.clone()));
+
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .unwrap();
+        Self { anon_key: state.anon_key(), state, url, client }
+    }
+
+    /// `apikey`를 붙인 요청. `token`이 있으면 `Authorization: Bearer`도 붙입니다.
+    pub fn request(&self, method: Method, path: &str, token: Option<&str>) -> RequestBuilder {
+        let request = self.client.request(method, format!("{}{}", self.url, path)).header("apikey", &self.anon_key);
+        match token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        }
+    }
+
+    /// `/auth/v1/<path>`에 JSON을 보내고 (상태, 본문)을 돌려줍니다.
+    pub async fn auth(&self, path: &str, body: Value) -> (StatusCode, Value) {
+        let response = self.request(Method::POST, &format!("/auth/v1/{}", path), None).json(&body).send().await.unwrap();
+        read(response).await
+    }
+
+    pub async fn rest(&self, method: Method, path: &str, token: Option<&str>, body: Option<Value>) -> (StatusCode, Value) {
+        let mut request = self.request(method, &format!("/rest/v1/{}", path), token);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+        read(request.send().await.unwrap()).await
+    }
+
+    /// 가입 확인 없이 쓸 수 있는 비밀번호 계정을 만들고 로그인합니다. (`start_with(true)`로 띄운 서버)
+    pub async fn sign_up(&self, email: &str) -> Session {
+        let (status, body) = self.auth("signup", json!({ "email": email, "password": PASSWORD })).await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        Session::from_json(&body)
+    }
+
+    /// 보낸 메일을 1분 전으로 돌려, 같은 사용자에게 바로 다시 메일을 보낼 수 있게 합니다.
+    pub fn skip_email_interval(&self) {
+        self.state
+            .db()
+            .execute("UPDATE one_time_tokens SET created_at = created_at - 60", [])
+            .unwrap();
+    }
+
+    /// 그 이메일로 마지막에 보낸 메일의 (링크 토큰, 6자리 코드)
+    pub fn last_email(&self, email: &str) -> (String, String) {
+        self.state
+            .db()
+            .query_row(
+                "SELECT token, otp FROM one_time_tokens JOIN users ON users.id = one_time_tokens.user_id
+                 WHERE users.email = ?1 ORDER BY one_time_tokens.created_at DESC",
+                [email],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .expect("no email was sent")
+    }
+}
+
+async fn read(response: reqwest::Response) -> (StatusCode, Value) {
+    let status = response.status();
+    let text = response.text().await.unwrap();
+    (status, serde_json::from_str(&text).unwrap_or(Value::Null))
+}
+
+/// PKCE code verifier의 S256 challenge
+pub fn code_challenge(verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
@@ -0,0 +1,145 @@
+// `/rest/v1` 행 격리: 다른 사용자의 행은 읽거나 바꾸거나 그 아래에 행을 만들 수 없고,
+// 로그인하지 않은 요청은 빈 결과만 읽습니다.
+
+mod common;
+
+use common::{Session, TestServer};
+use reqwest::{Method, StatusCode};
+use serde_json::{json, Value};
+
+const CERTIFICATE_ID: &str = "11111111-1111-1111-1111-111111111111";
+const QUESTION_ID: &str = "22222222-2222-2222-2222-222222222222";
+const OPTION_ID: &str = "33333333-3333-3333-3333-333333333333";
+
+/// 자격증 하나, 문제 하나, 보기 하나를 만듭니다.
+async fn create_rows(server: &TestServer, owner: &Session) {
+    let token = Some(owner.access_token.as_str());
+    let rows = [
+        ("certificates", json!({ "id": CERTIFICATE_ID, "name": "정보처리기사" })),
+        ("questions", json!({ "id": QUESTION_ID, "certificate_id": CERTIFICATE_ID, "content": "문제" })),
+        ("question_options", json!({ "id": OPTION_ID, "question_id": QUESTION_ID, "content": "보기", "is_correct": true })),
+    ];
+    for (table, row) in rows {
+        let (status, body) = server.rest(Method::POST, table, token, Some(row)).await;
+        assert_eq!(status, StatusCode::CREATED, "{}: {}", table, body);
+    }
+}
+
+async fn certificate_name(server: &TestServer, owner: &Session) -> Value {
+    let (status, body) = server
+        .rest(Method::GET, &format!("certificates?id=eq.{}", CERTIFICATE_ID), Some(&owner.access_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    body[0]["name"].clone()
+}
+
+#[tokio::test]
+async fn owner_reads_rows_with_embedded_children() {
+    let server = TestServer::start_with(true).await;
+    let alice = server.sign_up("alice@example.com").await;
+    create_rows(&server, &alice).await;
+
+    let (status, body) = server
+        .rest(Method::GET, "questions?select=*,question_options(*)", Some(&alice.access_token), None)
+        .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["id"], QUESTION_ID);
+    assert_eq!(body[0]["user_id"], alice.user_id.as_str());
+    assert_eq!(body[0]["question_options"][0]["id"], OPTION_ID);
+    assert_eq!(certificate_name(&server, &alice).await, "정보처리기사");
+}
+
+#[tokio::test]
+async fn other_user_cannot_read_or_change_rows() {
+    let server = TestServer::start_with(true).await;
+    let alice = server.sign_up("alice@example.com").await;
+    let bob = server.sign_up("bob@example.com").await;
+    create_rows(&server, &alice).await;
+    let bob_token = Some(bob.access_token.as_str());
+
+    for path in [
+        "certificates".to_string(),
+        format!("certificates?id=eq.{}", CERTIFICATE_ID),
+        "questions?select=*,question_options(*)".to_string(),
+        format!("question_options?question_id=eq.{}", QUESTION_ID),
+    ] {
+        let (status, body) = server.rest(Method::GET, &path, bob_token, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, json!([]), "{}", path);
+    }
+
+    let filter = format!("certificates?id=eq.{}", CERTIFICATE_ID);
+    server.rest(Method::PATCH, &filter, bob_token, Some(json!({ "name": "바뀐 이름" }))).await;
+    server.rest(Method::DELETE, &filter, bob_token, None).await;
+    assert_eq!(certificate_name(&server, &alice).await, "정보처리기사");
+
+    let (status, body) = server
+        .rest(
+            Method::POST,
+            "questions",
+            bob_token,
+            Some(json!({ "id": "44444444-4444-4444-4444-444444444444", "certificate_id": CERTIFICATE_ID, "content": "끼워 넣은 문제" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "42501");
+
+    let (status, body) = server
+        .rest(
+            Method::POST,
+            "question_options",
+            bob_token,
+            Some(json!({ "id": "55555555-5555-5555-5555-555555555555", "question_id": QUESTION_ID, "content": "끼워 넣은 보기" })),
+        )
+        .await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert_eq!(body["code"], "42501");
+
+    let (_, body) = server.rest(Method::GET, "questions?select=*,question_options(*)", Some(&alice.access_token), None).await;
+    assert_eq!(body.as_array().unwrap().len(), 1);
+    assert_eq!(body[0]["question_options"].as_array().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn anonymous_reads_are_empty_and_writes_are_rejected() {
+    let server = TestServer::start_with(true).await;
+    let alice = server.sign_up("alice@example.com").await;
+    create_rows(&server, &alice).await;
+    let anon_key = server.anon_key.clone();
+
+    for token in [None, Some(anon_key.as_str())] {
+        for table in ["certificates", "questions", "question_options"] {
+            let (status, body) = server.rest(Method::GET, table, token, None).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body, json!([]), "{}", table);
+        }
+        let (status, _) = server
+            .rest(Method::POST, "certificates", token, Some(json!({ "id": "66666666-6666-6666-6666-666666666666", "name": "익명" })))
+            .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
+
+#[tokio::test]
+async fn delete_account_dry_run_keeps_every_row() {
+    let server = TestServer::start_with(true).await;
+    let alice = server.sign_up("alice@example.com").await;
+    create_rows(&server, &alice).await;
+    let token = Some(alice.access_token.as_str());
+
+    let (status, _) = server.rest(Method::POST, "rpc/delete_account", token, Some(json!({ "dry_run": true }))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    assert_eq!(certificate_name(&server, &alice).await, "정보처리기사");
+
+    let (status, _) = server.rest(Method::POST, "rpc/delete_account", token, Some(json!({}))).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = server.auth("token?grant_type=refresh_token", json!({ "refresh_token": alice.refresh_token })).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn requests_without_the_api_key_are_rejected() {
+    let server = TestServer::start().await;
+    let response = reqwest::get(format!("{}/rest/v1/certificates", server.url)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
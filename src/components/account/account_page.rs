@@ -0,0 +1,180 @@
+use crate::components::auth::use_user;
+use crate::components::data::management::download_file;
+use crate::routes::Route;
+use crate::services::AccountService;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{window, HtmlInputElement};
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[function_component(AccountPage)]
+pub fn account_page() -> Html {
+    let user = use_user();
+    let message = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+    let is_loading = use_state(|| false);
+    // 삭제 1단계: 확인란에 이메일을 그대로 입력해야 삭제 버튼이 켜집니다.
+    let confirm_email = use_state(String::new);
+
+    let Some(user) = user else {
+        // 게스트 모드에는 서버 계정이 없습니다.
+        return html! {
+            <div class="px-4 py-5 sm:p-6">
+                <h2 class="text-2xl font-bold text-gray-900 mb-6">{"계정"}</h2>
+                <div class="bg-white shadow rounded-lg p-6 text-sm text-gray-600">
+                    {"게스트 모드에서는 데이터가 이 브라우저에만 저장됩니다. 내보내기는 "}
+                    <Link<Route> to={Route::Data} classes="text-blue-600 hover:text-blue-800">{"데이터 관리"}</Link<Route>>
+                    {"에서 할 수 있습니다."}
+                </div>
+            </div>
+        };
+    };
+
+    let on_export = {
+        let message = message.clone();
+        let error = error.clone();
+        let is_loading = is_loading.clone();
+        Callback::from(move |_| {
+            let message = message.clone();
+            let error = error.clone();
+            let is_loading = is_loading.clone();
+            is_loading.set(true);
+            error.set(None);
+            message.set(Some("계정 데이터를 모으는 중...".to_string()));
+
+            spawn_local(async move {
+                match AccountService::export().await {
+                    Ok(json_str) => {
+                        let now = chrono::Local::now();
+                        let filename = format!("quiznote_account_{}.json", now.format("%Y%m%d_%H%M%S"));
+                        download_file(json_str.as_bytes(), "application/json", &filename);
+                        message.set(Some(format!("'{}' 파일로 내보내기 성공!", filename)));
+                    }
+                    Err(e) => {
+                        message.set(None);
+                        error.set(Some(format!("내보내기 실패: {}", e)));
+                    }
+                }
+                is_loading.set(false);
+            });
+        })
+    };
+
+    let on_confirm_input = {
+        let confirm_email = confirm_email.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            confirm_email.set(input.value());
+        })
+    };
+
+    let email_matches = confirm_email.trim().eq_ignore_ascii_case(&user.email);
+
+    let on_delete = {
+        let message = message.clone();
+        let error = error.clone();
+        let is_loading = is_loading.clone();
+        Callback::from(move |_| {
+            if !email_matches {
+                return;
+            }
+            // 삭제 2단계: 마지막으로 한 번 더 묻습니다.
+            let confirmation = "계정과 모든 데이터를 영구 삭제하고 모든 기기에서 로그아웃합니다. 되돌릴 수 없습니다. 계속하시겠습니까?";
+            if !window().unwrap().confirm_with_message(confirmation).unwrap_or(false) {
+                return;
+            }
+            let message = message.clone();
+            let error = error.clone();
+            let is_loading = is_loading.clone();
+            is_loading.set(true);
+            error.set(None);
+            message.set(Some("계정을 삭제하는 중...".to_string()));
+
+            spawn_local(async move {
+                match AccountService::delete_account().await {
+                    // 로그아웃되면서 로그인 화면으로 바뀝니다.
+                    Ok(()) => {
+                        let _ = window().unwrap().alert_with_message("계정이 삭제되었습니다.");
+                    }
+                    Err(e) => {
+                        message.set(None);
+                        error.set(Some(e));
+                        is_loading.set(false);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="px-4 py-5 sm:p-6">
+            <h2 class="text-2xl font-bold text-gray-900 mb-6">{"계정"}</h2>
+
+            <div class="bg-white shadow rounded-lg p-6 space-y-6">
+                <div>
+                    <h3 class="text-lg font-medium text-gray-900">{"로그인한 계정"}</h3>
+                    <p class="mt-1 text-sm text-gray-600">{&user.email}</p>
+                </div>
+
+                <div class="border-t border-gray-200"></div>
+
+                <div>
+                    <h3 class="text-lg font-medium text-gray-900">{"내 데이터 모두 내려받기"}</h3>
+                    <p class="mt-1 text-sm text-gray-600">
+                        {"휴지통에 있는 것을 포함한 모든 자격증과 문제, 문제별 풀이 기록과 통계, 이 브라우저의 설정을 JSON 파일 하나로 내려받습니다. 이 파일은 데이터 관리 화면에서 풀이 기록과 함께 다시 가져올 수 있습니다. (휴지통에 있던 항목은 가져오지 않습니다)"}
+                    </p>
+                    <button
+                        onclick={on_export}
+                        disabled={*is_loading}
+                        class="mt-3 inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md shadow-sm text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50"
+                    >
+                        {"모두 내려받기"}
+                    </button>
+                </div>
+
+                <div class="border-t border-gray-200"></div>
+
+                <div>
+                    <h3 class="text-lg font-medium text-red-700">{"계정 삭제"}</h3>
+                    <p class="mt-1 text-sm text-gray-600">
+                        {"계정과 모든 자격증·문제·풀이 기록, 첨부 이미지, 이 브라우저의 로컬 스냅샷을 영구 삭제하고 모든 기기에서 로그아웃합니다. 되돌릴 수 없으니 먼저 데이터를 내려받아 두세요."}
+                    </p>
+                    <label class="mt-3 block text-sm text-gray-700">
+                        {"확인을 위해 이메일 주소를 입력하세요"}
+                        <input type="email" autocomplete="off" placeholder={user.email.clone()}
+                            value={(*confirm_email).clone()} oninput={on_confirm_input}
+                            class="mt-1 block w-full max-w-sm border border-gray-300 rounded-md px-3 py-2 text-sm" />
+                    </label>
+                    <button
+                        onclick={on_delete}
+                        disabled={*is_loading || !email_matches}
+                        class="mt-3 inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md shadow-sm text-white bg-red-600 hover:bg-red-700 disabled:opacity-50"
+                    >
+                        {"계정 삭제"}
+                    </button>
+                </div>
+
+                {if let Some(msg) = &*message {
+                    html! {
+                        <div class="p-4 bg-gray-50 rounded-lg text-center">
+                            if *is_loading {
+                                <div class="flex items-center justify-center">
+                                    <div class="animate-spin rounded-full h-5 w-5 border-b-2 border-blue-600 mr-3"></div>
+                                    <span>{msg}</span>
+                                </div>
+                            } else {
+                                <p class="text-gray-700">{msg}</p>
+                            }
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }}
+
+                if let Some(e) = &*error {
+                    <div class="p-4 bg-red-50 rounded-lg text-sm text-red-800">{e}</div>
+                }
+            </div>
+        </div>
+    }
+}
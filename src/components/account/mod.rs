@@ -0,0 +1,3 @@
+pub mod account_page;
+
+pub use account_page::AccountPage;
\ No newline at end of file
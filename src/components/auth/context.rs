@@ -0,0 +1,169 @@
+// 로그인 상태 컨텍스트
+//
+// `AuthProvider`가 세션을 한 번 읽어 두고 `AuthService`의 세션 변경 알림을 받아 갱신합니다.
+// 컴포넌트는 LocalStorage를 직접 읽지 않고 `use_session`·`use_user`·`use_is_guest`·`use_has_pending_guest_data`로 세션을 받아,
+// 로그인·토큰 갱신·로그아웃 때 새로고침 없이 다시 그려집니다. 다른 탭에서 바뀐 세션도
+// `storage` 이벤트로 받아 반영하고, Access Token은 만료되기 전에 미리 갱신합니다.
+
+use gloo::events::EventListener;
+use gloo::timers::callback::Timeout;
+use gloo::timers::future::TimeoutFuture;
+use rand::Rng;
+use std::rc::Rc;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yew_router::Routable;
+use crate::routes::{take_return_to, Route};
+use crate::services::auth::{AuthCallback, Session, User};
+use crate::services::AuthService;
+
+/// 여러 탭이 같은 순간에 갱신하지 않도록 갱신 시각을 이 범위(ms)에서 흩뜨립니다.
+const REFRESH_JITTER_MS: u32 = 5_000;
+/// 네트워크 오류 등으로 갱신에 실패했을 때 다시 시도하기까지 기다리는 시간(ms)
+const REFRESH_RETRY_MS: u32 = 30_000;
+
+#[derive(Clone, PartialEq)]
+pub struct AuthContext {
+    pub session: Option<Rc<Session>>,
+    /// 로그인하지 않고 브라우저에만 저장하는 게스트 모드
+    pub is_guest: bool,
+    /// 게스트로 쓰다가 로그인해, 아직 계정으로 옮기지 않은 게스트 데이터가 있는지
+    pub has_pending_guest_data: bool,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct AuthProviderProps {
+    pub children: Children,
+}
+
+/// 앱을 열 때 로그인 링크 콜백과 만료된 토큰을 처리한 뒤 자식을 그립니다.
+#[function_component(AuthProvider)]
+pub fn auth_provider(props: &AuthProviderProps) -> Html {
+    let session = use_state_eq(|| None::<Rc<Session>>);
+    let is_guest = use_state_eq(|| false);
+    let has_pending_guest_data = use_state_eq(|| false);
+    let is_checking = use_state(|| true);
+
+    {
+        let session = session.clone();
+        let is_guest = is_guest.clone();
+        let has_pending_guest_data = has_pending_guest_data.clone();
+        let is_checking = is_checking.clone();
+        use_effect_with((), move |_| {
+            let subscription = {
+                let session = session.clone();
+                let is_guest = is_guest.clone();
+                let has_pending_guest_data = has_pending_guest_data.clone();
+                AuthService::subscribe(Callback::from(move |next: Option<Session>| {
+                    session.set(next.map(Rc::new));
+                    is_guest.set(AuthService::is_guest());
+                    has_pending_guest_data.set(AuthService::has_pending_guest_data());
+                }))
+            };
+
+            spawn_local(async move {
+                // 로그인 링크 콜백 처리
+                // 라우터가 뜨기 전에 주소를 바꿔, 처음부터 갈 화면이 보이게 합니다.
+                match AuthService::handle_auth_callback().await {
+                    Ok(Some(AuthCallback::SignedIn)) => replace_route(&take_return_to()),
+                    Ok(Some(AuthCallback::PasswordRecovery)) => replace_route(&Route::ResetPassword),
+                    Ok(None) => {}
+                    Err(e) => web_sys::console::error_1(&format!("Auth callback error: {}", e).into()),
+                }
+
+                // 저장된 세션의 Access Token이 만료되었으면 Refresh Token으로 갱신합니다.
+                // Refresh Token이 거절되면 `AuthService`가 세션을 지우고 알려 줍니다. 서버 오류로 실패하면
+                // 세션을 그대로 두고, 아래 갱신 타이머가 곧바로 다시 시도합니다.
+                if AuthService::get_session().is_some() && AuthService::is_token_expired() {
+                    if let Err(e) = AuthService::refresh_token().await {
+                        web_sys::console::error_1(&format!("Failed to refresh token: {}", e).into());
+                    }
+                }
+
+                session.set(AuthService::get_session().map(Rc::new));
+                is_guest.set(AuthService::is_guest());
+                has_pending_guest_data.set(AuthService::has_pending_guest_data());
+                is_checking.set(false);
+            });
+            // 다른 탭에서 로그인·로그아웃·갱신하면 LocalStorage의 세션이 바뀝니다.
+            let storage_listener = EventListener::new(&web_sys::window().unwrap(), "storage", |event| {
+                if let Some(event) = event.dyn_ref::<web_sys::StorageEvent>() {
+                    AuthService::handle_storage_change(event.key().as_deref());
+                }
+            });
+            move || drop((subscription, storage_listener))
+        });
+    }
+
+    // 세션이 바뀔 때마다 다음 갱신을 예약합니다. 실패하면 세션이 남아 있는 동안 다시 시도하고,
+    // 서버가 세션을 거절하면 `AuthService`가 세션을 지워 로그인 화면으로 돌아갑니다.
+    use_effect_with((*session).clone(), |session| {
+        let timer = session.as_ref().map(|session| {
+            let delay = AuthService::refresh_delay_ms(session)
+                .saturating_add(rand::thread_rng().gen_range(0..REFRESH_JITTER_MS));
+            Timeout::new(delay, || {
+                spawn_local(async {
+                    while let Err(e) = AuthService::refresh_token().await {
+                        web_sys::console::warn_1(&format!("Token refresh failed: {}", e).into());
+                        if AuthService::get_session().is_none() {
+                            break;
+                        }
+                        TimeoutFuture::new(REFRESH_RETRY_MS).await;
+                    }
+                });
+            })
+        });
+        move || drop(timer)
+    });
+
+    if *is_checking {
+        return html! {
+            <div class="min-h-screen flex items-center justify-center">
+                <p class="text-gray-500">{"세션 확인 중..."}</p>
+            </div>
+        };
+    }
+
+    let context = AuthContext {
+        session: (*session).clone(),
+        is_guest: *is_guest,
+        has_pending_guest_data: *has_pending_guest_data,
+    };
+    html! {
+        <ContextProvider<AuthContext> {context}>
+            {props.children.clone()}
+        </ContextProvider<AuthContext>>
+    }
+}
+
+/// 방문 기록을 늘리지 않고 HashRouter 경로를 바꿉니다.
+fn replace_route(route: &Route) {
+    let window = web_sys::window().unwrap();
+    if let Ok(history) = window.history() {
+        let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&format!("#{}", route.to_path())));
+    }
+}
+
+/// 지금 로그인한 세션. 로그인하지 않았으면 `None`
+#[hook]
+pub fn use_session() -> Option<Rc<Session>> {
+    use_context::<AuthContext>().and_then(|context| context.session)
+}
+
+#[hook]
+pub fn use_user() -> Option<User> {
+    use_session().map(|session| session.user.clone())
+}
+
+/// 로그인하지 않은 게스트 모드인지
+#[hook]
+pub fn use_is_guest() -> bool {
+    use_context::<AuthContext>().is_some_and(|context| context.is_guest)
+}
+
+/// 게스트로 쓰다가 로그인해, 아직 계정으로 옮기지 않은 게스트 데이터가 있는지
+#[hook]
+pub fn use_has_pending_guest_data() -> bool {
+    use_context::<AuthContext>().is_some_and(|context| context.has_pending_guest_data)
+}
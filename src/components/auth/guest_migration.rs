@@ -0,0 +1,76 @@
+use yew::prelude::*;
+use wasm_bindgen_futures::spawn_local;
+use crate::services::{AuthService, DataService};
+
+#[derive(Properties, PartialEq)]
+pub struct GuestMigrationProps {
+    /// 옮기기를 마치면 결과 안내를, 나중에 하기로 하면 `None`을 넘깁니다.
+    pub on_done: Callback<Option<String>>,
+}
+
+/// 게스트 모드로 쓰다가 로그인했을 때 게스트 데이터를 계정으로 옮깁니다.
+/// 옮기는 동안에는 다른 화면을 보여주지 않아, 옮긴 데이터가 빠진 목록을 읽지 않게 합니다.
+#[function_component(GuestMigration)]
+pub fn guest_migration(props: &GuestMigrationProps) -> Html {
+    let error = use_state(|| None::<String>);
+    let attempt = use_state(|| 0u32);
+
+    {
+        let error = error.clone();
+        let on_done = props.on_done.clone();
+        use_effect_with(*attempt, move |_| {
+            error.set(None);
+            spawn_local(async move {
+                match DataService::migrate_guest_data().await {
+                    Ok(message) => {
+                        AuthService::finish_guest_migration();
+                        on_done.emit(Some(message).filter(|m| !m.is_empty()));
+                    }
+                    Err(e) => error.set(Some(e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_retry = {
+        let attempt = attempt.clone();
+        Callback::from(move |_| attempt.set(*attempt + 1))
+    };
+    let on_later = {
+        let on_done = props.on_done.clone();
+        Callback::from(move |_| on_done.emit(None))
+    };
+
+    html! {
+        <div class="max-w-xl mx-auto bg-white shadow rounded-lg p-6 space-y-4">
+            <h2 class="text-lg font-semibold text-gray-900">{"게스트 데이터를 계정으로 옮기는 중"}</h2>
+            {if let Some(err) = &*error {
+                html! {
+                    <>
+                        <div class="rounded-md bg-red-50 p-4 text-sm text-red-800">
+                            {format!("옮기지 못했습니다: {}", err)}
+                        </div>
+                        <p class="text-sm text-gray-600">
+                            {"게스트 데이터는 이 브라우저에 그대로 남아 있습니다. 다시 시도하면 이미 옮긴 문제는 건너뛰고 이어서 옮깁니다."}
+                        </p>
+                        <div class="flex gap-2">
+                            <button onclick={on_retry} class="px-4 py-2 text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700">
+                                {"다시 시도"}
+                            </button>
+                            <button onclick={on_later} class="px-4 py-2 text-sm font-medium rounded-md text-gray-700 bg-white border border-gray-300 hover:bg-gray-50">
+                                {"나중에 옮기기"}
+                            </button>
+                        </div>
+                    </>
+                }
+            } else {
+                html! {
+                    <p class="text-sm text-gray-600">
+                        {"로그인 전에 이 브라우저에서 만든 자격증과 문제를 계정에 저장하고 있습니다. 잠시만 기다려주세요."}
+                    </p>
+                }
+            }}
+        </div>
+    }
+}
@@ -1,57 +1,292 @@
 use yew::prelude::*;
 use web_sys::HtmlInputElement;
 use wasm_bindgen_futures::spawn_local;
+use yew_router::prelude::*;
+use gloo::timers::callback::Interval;
+use crate::components::auth::{use_is_guest, use_session, ServerSwitcher};
+use crate::routes::take_return_to;
 use crate::services::AuthService;
 
+/// 로그인 방법
+#[derive(Clone, Copy, PartialEq)]
+enum Method {
+    /// 메일로 받은 링크를 눌러 로그인
+    MagicLink,
+    /// 메일로 받은 6자리 코드를 입력해 로그인
+    Code,
+    Password,
+}
+
+impl Method {
+    const ALL: [Method; 3] = [Method::MagicLink, Method::Code, Method::Password];
+
+    fn label(self) -> &'static str {
+        match self {
+            Method::MagicLink => "이메일 링크",
+            Method::Code => "인증 코드",
+            Method::Password => "비밀번호",
+        }
+    }
+}
+
+/// 비밀번호 로그인 화면에서 하는 일
+#[derive(Clone, Copy, PartialEq)]
+enum PasswordMode {
+    SignIn,
+    SignUp,
+    Reset,
+}
+
+const MIN_PASSWORD_LENGTH: usize = 6;
+const CODE_LENGTH: usize = 6;
+
+const INPUT_CLASS: &str = "appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm";
+const SUBMIT_CLASS: &str = "group relative w-full flex justify-center py-2 px-4 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed";
+const LINK_BUTTON_CLASS: &str = "text-blue-600 hover:text-blue-800 disabled:text-gray-400 disabled:cursor-not-allowed";
+
+fn bind(state: &UseStateHandle<String>) -> Callback<InputEvent> {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+        state.set(e.target_unchecked_into::<HtmlInputElement>().value());
+    })
+}
+
 #[function_component(Login)]
 pub fn login() -> Html {
-    let email_ref = use_node_ref();
+    let method = use_state(|| Method::MagicLink);
+    let password_mode = use_state(|| PasswordMode::SignIn);
+    let email = use_state(String::new);
+    let password = use_state(String::new);
+    let code = use_state(String::new);
     let loading = use_state(|| false);
-    let error = use_state(|| None::<String>);
-    let success = use_state(|| false);
+    // 로그인 링크 콜백에서 난 오류(만료된 링크 등)가 있으면 처음부터 보여줍니다.
+    let error = use_state(AuthService::take_callback_error);
+    // 메일을 보낸 뒤 보여줄 안내
+    let notice = use_state(|| None::<String>);
+    let code_sent = use_state(|| false);
+    let cooldown = use_state(AuthService::email_cooldown);
+    let session = use_session();
+    let is_guest = use_is_guest();
+    let navigator = use_navigator().unwrap();
 
-    let on_submit = {
-        let email_ref = email_ref.clone();
-        let loading = loading.clone();
-        let error = error.clone();
-        let success = success.clone();
+    // 로그인하면 로그인 전에 가려던 화면(없으면 홈)으로 보냅니다.
+    {
+        let navigator = navigator.clone();
+        use_effect_with(session.is_some(), move |signed_in| {
+            if *signed_in {
+                navigator.replace(&take_return_to());
+            }
+            || ()
+        });
+    }
 
-        Callback::from(move |e: SubmitEvent| {
-            e.prevent_default();
+    // 로그인하지 않고 이 브라우저에만 저장하며 시작합니다.
+    let on_start_guest = Callback::from(move |_: MouseEvent| {
+        AuthService::start_guest();
+        navigator.replace(&take_return_to());
+    });
+
+    // 재전송 대기 시간을 1초마다 갱신합니다.
+    {
+        let cooldown = cooldown.clone();
+        use_effect_with((), move |_| {
+            let interval = Interval::new(1000, move || cooldown.set(AuthService::email_cooldown()));
+            move || drop(interval)
+        });
+    }
 
-            let email = email_ref
-                .cast::<HtmlInputElement>()
-                .map(|input| input.value())
-                .unwrap_or_default();
+    let switch_method = |next: Method| {
+        let method = method.clone();
+        let password_mode = password_mode.clone();
+        let error = error.clone();
+        let notice = notice.clone();
+        let code_sent = code_sent.clone();
+        Callback::from(move |_: MouseEvent| {
+            method.set(next);
+            password_mode.set(PasswordMode::SignIn);
+            error.set(None);
+            notice.set(None);
+            code_sent.set(false);
+        })
+    };
+
+    let switch_password_mode = |next: PasswordMode| {
+        let password_mode = password_mode.clone();
+        let error = error.clone();
+        let notice = notice.clone();
+        Callback::from(move |_: MouseEvent| {
+            password_mode.set(next);
+            error.set(None);
+            notice.set(None);
+        })
+    };
 
-            if email.trim().is_empty() {
+    // 로그인 링크·코드 메일 보내기 (처음 보낼 때와 다시 보낼 때 모두)
+    let send_email = {
+        let method = method.clone();
+        let email = email.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        let notice = notice.clone();
+        let code_sent = code_sent.clone();
+        let cooldown = cooldown.clone();
+        Callback::from(move |_: ()| {
+            let address = email.trim().to_string();
+            if address.is_empty() {
                 error.set(Some("이메일을 입력해주세요.".to_string()));
                 return;
             }
-
+            let method = *method;
             let loading = loading.clone();
             let error = error.clone();
-            let success = success.clone();
-
+            let notice = notice.clone();
+            let code_sent = code_sent.clone();
+            let cooldown = cooldown.clone();
             loading.set(true);
             error.set(None);
-
             spawn_local(async move {
-                match AuthService::sign_in_with_email(&email).await {
-                    Ok(_) => {
-                        success.set(true);
-                        error.set(None);
-                    }
-                    Err(e) => {
-                        error.set(Some(e));
-                        success.set(false);
+                match AuthService::sign_in_with_email(&address).await {
+                    Ok(()) => {
+                        notice.set(Some(match method {
+                            Method::Code => format!("{}로 인증 코드를 보냈습니다. 메일에 있는 {}자리 코드를 입력해주세요.", address, CODE_LENGTH),
+                            _ => format!("{}로 로그인 링크를 보냈습니다. 이메일의 링크를 클릭하면 로그인됩니다. 메일을 다시 받았다면 가장 최근 메일의 링크만 쓸 수 있습니다.", address),
+                        }));
+                        code_sent.set(true);
                     }
+                    Err(e) => error.set(Some(e)),
                 }
+                cooldown.set(AuthService::email_cooldown());
                 loading.set(false);
             });
         })
     };
 
+    let on_submit = {
+        let method = method.clone();
+        let password_mode = password_mode.clone();
+        let email = email.clone();
+        let password = password.clone();
+        let code = code.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+        let notice = notice.clone();
+        let code_sent = code_sent.clone();
+        let cooldown = cooldown.clone();
+        let send_email = send_email.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let address = email.trim().to_string();
+            if address.is_empty() {
+                error.set(Some("이메일을 입력해주세요.".to_string()));
+                return;
+            }
+
+            match (*method, *password_mode) {
+                (Method::MagicLink, _) => send_email.emit(()),
+                (Method::Code, _) if !*code_sent => send_email.emit(()),
+                (Method::Code, _) => {
+                    let token = code.trim().to_string();
+                    if token.len() != CODE_LENGTH || !token.chars().all(|c| c.is_ascii_digit()) {
+                        error.set(Some(format!("메일로 받은 {}자리 숫자를 입력해주세요.", CODE_LENGTH)));
+                        return;
+                    }
+                    let loading = loading.clone();
+                    let error = error.clone();
+                    loading.set(true);
+                    error.set(None);
+                    spawn_local(async move {
+                        // 로그인하면 세션이 바뀌어 이 화면이 홈으로 넘어갑니다.
+                        if let Err(e) = AuthService::verify_email_code(&address, &token).await {
+                            error.set(Some(e));
+                        }
+                        loading.set(false);
+                    });
+                }
+                (Method::Password, PasswordMode::Reset) => {
+                    let loading = loading.clone();
+                    let error = error.clone();
+                    let notice = notice.clone();
+                    let cooldown = cooldown.clone();
+                    loading.set(true);
+                    error.set(None);
+                    spawn_local(async move {
+                        match AuthService::request_password_reset(&address).await {
+                            Ok(()) => notice.set(Some(format!(
+                                "{}로 비밀번호 재설정 링크를 보냈습니다. 링크를 누르면 새 비밀번호를 정할 수 있습니다.",
+                                address
+                            ))),
+                            Err(e) => error.set(Some(e)),
+                        }
+                        cooldown.set(AuthService::email_cooldown());
+                        loading.set(false);
+                    });
+                }
+                (Method::Password, mode) => {
+                    let secret = (*password).clone();
+                    if secret.is_empty() {
+                        error.set(Some("비밀번호를 입력해주세요.".to_string()));
+                        return;
+                    }
+                    if mode == PasswordMode::SignUp && secret.chars().count() < MIN_PASSWORD_LENGTH {
+                        error.set(Some(format!("비밀번호는 {}자 이상이어야 합니다.", MIN_PASSWORD_LENGTH)));
+                        return;
+                    }
+                    let loading = loading.clone();
+                    let error = error.clone();
+                    let notice = notice.clone();
+                    let cooldown = cooldown.clone();
+                    loading.set(true);
+                    error.set(None);
+                    spawn_local(async move {
+                        let result = if mode == PasswordMode::SignUp {
+                            AuthService::sign_up_with_password(&address, &secret).await
+                        } else {
+                            AuthService::sign_in_with_password(&address, &secret).await.map(|_| true)
+                        };
+                        match result {
+                            Ok(true) => {}
+                            Ok(false) => notice.set(Some(format!(
+                                "{}로 가입 확인 메일을 보냈습니다. 메일의 링크를 누르면 가입이 끝나고 로그인됩니다.",
+                                address
+                            ))),
+                            Err(e) => error.set(Some(e)),
+                        }
+                        cooldown.set(AuthService::email_cooldown());
+                        loading.set(false);
+                    });
+                }
+            }
+        })
+    };
+
+    if session.is_some() {
+        return html! {};
+    }
+
+    let submit_label = match (*method, *password_mode) {
+        _ if *loading => "처리 중...",
+        (Method::MagicLink, _) => "로그인 링크 받기",
+        (Method::Code, _) if *code_sent => "인증 코드로 로그인",
+        (Method::Code, _) => "인증 코드 받기",
+        (Method::Password, PasswordMode::SignIn) => "로그인",
+        (Method::Password, PasswordMode::SignUp) => "가입하기",
+        (Method::Password, PasswordMode::Reset) => "재설정 링크 받기",
+    };
+    // 메일을 보내는 버튼은 재전송 대기 중에 막습니다.
+    let sends_email = match *method {
+        Method::MagicLink => true,
+        Method::Code => !*code_sent,
+        Method::Password => *password_mode == PasswordMode::Reset,
+    };
+    let submit_disabled = *loading || (sends_email && *cooldown > 0);
+
+    let resend = {
+        let send_email = send_email.clone();
+        Callback::from(move |_: MouseEvent| send_email.emit(()))
+    };
+
     html! {
         <div class="min-h-screen flex items-center justify-center bg-gray-50 py-12 px-4 sm:px-6 lg:px-8">
             <div class="max-w-md w-full space-y-8">
@@ -63,87 +298,246 @@ pub fn login() -> Html {
                         {"로그인"}
                     </h2>
                     <p class="mt-2 text-center text-sm text-gray-600">
-                        {"이메일로 로그인하면 모든 기기에서 데이터를 동기화할 수 있습니다"}
+                        {"로그인하면 모든 기기에서 데이터를 동기화할 수 있습니다"}
                     </p>
                 </div>
 
-                {if *success {
-                    html! {
-                        <div class="rounded-md bg-green-50 p-4">
-                            <div class="flex">
-                                <div class="flex-shrink-0">
-                                    <svg class="h-5 w-5 text-green-400" fill="currentColor" viewBox="0 0 20 20">
-                                        <path fill-rule="evenodd" d="M10 18a8 8 0 100-16 8 8 0 000 16zm3.707-9.293a1 1 0 00-1.414-1.414L9 10.586 7.707 9.293a1 1 0 00-1.414 1.414l2 2a1 1 0 001.414 0l4-4z" clip-rule="evenodd" />
-                                    </svg>
+                <div class="flex rounded-md shadow-sm" role="tablist">
+                    {for Method::ALL.iter().enumerate().map(|(index, &item)| {
+                        let rounded = match index {
+                            0 => "rounded-l-md",
+                            i if i == Method::ALL.len() - 1 => "rounded-r-md -ml-px",
+                            _ => "-ml-px",
+                        };
+                        let colors = if item == *method {
+                            "bg-blue-600 text-white border-blue-600"
+                        } else {
+                            "bg-white text-gray-700 border-gray-300 hover:bg-gray-50"
+                        };
+                        html! {
+                            <button
+                                type="button"
+                                role="tab"
+                                aria-selected={(item == *method).to_string()}
+                                onclick={switch_method(item)}
+                                disabled={*loading}
+                                class={classes!("flex-1", "px-3", "py-2", "text-sm", "font-medium", "border", rounded, colors)}
+                            >
+                                {item.label()}
+                            </button>
+                        }
+                    })}
+                </div>
+
+                <form class="mt-8 space-y-6" onsubmit={on_submit}>
+                    <div>
+                        <label for="email" class="block text-sm font-medium text-gray-700">
+                            {"이메일"}
+                        </label>
+                        <div class="mt-1">
+                            <input
+                                id="email"
+                                name="email"
+                                type="email"
+                                autocomplete="email"
+                                required=true
+                                class={INPUT_CLASS}
+                                placeholder="your@email.com"
+                                value={(*email).clone()}
+                                oninput={bind(&email)}
+                                disabled={*loading || (*method == Method::Code && *code_sent)}
+                            />
+                        </div>
+                    </div>
+
+                    {if *method == Method::Password && *password_mode != PasswordMode::Reset {
+                        html! {
+                            <div>
+                                <label for="password" class="block text-sm font-medium text-gray-700">
+                                    {"비밀번호"}
+                                </label>
+                                <div class="mt-1">
+                                    <input
+                                        id="password"
+                                        name="password"
+                                        type="password"
+                                        autocomplete={if *password_mode == PasswordMode::SignUp { "new-password" } else { "current-password" }}
+                                        required=true
+                                        class={INPUT_CLASS}
+                                        value={(*password).clone()}
+                                        oninput={bind(&password)}
+                                        disabled={*loading}
+                                    />
                                 </div>
-                                <div class="ml-3">
-                                    <h3 class="text-sm font-medium text-green-800">
-                                        {"이메일을 확인해주세요!"}
-                                    </h3>
-                                    <div class="mt-2 text-sm text-green-700">
-                                        <p>
-                                            {"로그인 링크를 이메일로 보냈습니다. 이메일의 링크를 클릭하면 로그인됩니다."}
+                                {if *password_mode == PasswordMode::SignUp {
+                                    html! {
+                                        <p class="mt-1 text-xs text-gray-500">
+                                            {format!("{}자 이상", MIN_PASSWORD_LENGTH)}
                                         </p>
-                                    </div>
-                                </div>
+                                    }
+                                } else {
+                                    html! {}
+                                }}
                             </div>
-                        </div>
-                    }
-                } else {
-                    html! {
-                        <form class="mt-8 space-y-6" onsubmit={on_submit}>
+                        }
+                    } else {
+                        html! {}
+                    }}
+
+                    {if *method == Method::Code && *code_sent {
+                        html! {
                             <div>
-                                <label for="email" class="block text-sm font-medium text-gray-700">
-                                    {"이메일"}
+                                <label for="code" class="block text-sm font-medium text-gray-700">
+                                    {"인증 코드"}
                                 </label>
                                 <div class="mt-1">
                                     <input
-                                        ref={email_ref}
-                                        id="email"
-                                        name="email"
-                                        type="email"
-                                        autocomplete="email"
+                                        id="code"
+                                        name="code"
+                                        type="text"
+                                        inputmode="numeric"
+                                        autocomplete="one-time-code"
+                                        maxlength={CODE_LENGTH.to_string()}
                                         required=true
-                                        class="appearance-none block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm placeholder-gray-400 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm"
-                                        placeholder="your@email.com"
+                                        class={classes!(INPUT_CLASS, "tracking-widest", "text-center", "font-mono")}
+                                        placeholder="000000"
+                                        value={(*code).clone()}
+                                        oninput={bind(&code)}
                                         disabled={*loading}
                                     />
                                 </div>
                             </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
 
-                            {if let Some(err) = &*error {
-                                html! {
-                                    <div class="rounded-md bg-red-50 p-4">
-                                        <div class="text-sm text-red-800">
-                                            {err}
+                    {if let Some(message) = &*notice {
+                        html! {
+                            <div class="rounded-md bg-green-50 p-4">
+                                <div class="flex">
+                                    <div class="flex-shrink-0">
+                                        <svg class="h-5 w-5 text-green-400" fill="currentColor" viewBox="0 0 20 20">
+                                            <path fill-rule="evenodd" d="M10 18a8 8 0 100-16 8 8 0 000 16zm3.707-9.293a1 1 0 00-1.414-1.414L9 10.586 7.707 9.293a1 1 0 00-1.414 1.414l2 2a1 1 0 001.414 0l4-4z" clip-rule="evenodd" />
+                                        </svg>
+                                    </div>
+                                    <div class="ml-3">
+                                        <h3 class="text-sm font-medium text-green-800">
+                                            {"이메일을 확인해주세요!"}
+                                        </h3>
+                                        <div class="mt-2 text-sm text-green-700">
+                                            <p>{message}</p>
                                         </div>
                                     </div>
-                                }
+                                </div>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
+
+                    {if let Some(err) = &*error {
+                        html! {
+                            <div class="rounded-md bg-red-50 p-4">
+                                <div class="text-sm text-red-800">
+                                    {err}
+                                </div>
+                            </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
+
+                    <div>
+                        <button type="submit" disabled={submit_disabled} class={SUBMIT_CLASS}>
+                            {if sends_email && *cooldown > 0 && !*loading {
+                                format!("{} ({}초 후 가능)", submit_label, *cooldown)
                             } else {
-                                html! {}
+                                submit_label.to_string()
                             }}
+                        </button>
+                    </div>
 
-                            <div>
-                                <button
-                                    type="submit"
-                                    disabled={*loading}
-                                    class="group relative w-full flex justify-center py-2 px-4 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-blue-500 disabled:opacity-50 disabled:cursor-not-allowed"
-                                >
-                                    {if *loading {
-                                        "로그인 링크 전송 중..."
+                    // 코드를 보낸 뒤에는 다시 받기 버튼을 따로 보여줍니다.
+                    {if *method == Method::Code && *code_sent {
+                        html! {
+                            <div class="text-sm text-center text-gray-600">
+                                {"메일이 오지 않았나요? "}
+                                <button type="button" onclick={resend} disabled={*loading || *cooldown > 0} class={LINK_BUTTON_CLASS}>
+                                    {if *cooldown > 0 {
+                                        format!("코드 다시 받기 ({}초)", *cooldown)
                                     } else {
-                                        "이메일로 로그인"
+                                        "코드 다시 받기".to_string()
                                     }}
                                 </button>
                             </div>
+                        }
+                    } else {
+                        html! {}
+                    }}
 
-                            <div class="text-sm text-center text-gray-600">
-                                {"비밀번호 없이 이메일로 전송된 링크를 클릭하여 로그인합니다"}
-                            </div>
-                        </form>
+                    <div class="text-sm text-center text-gray-600 space-y-2">
+                        {match *method {
+                            Method::MagicLink => html! {
+                                <p>{"비밀번호 없이 이메일로 전송된 링크를 클릭하여 로그인합니다"}</p>
+                            },
+                            Method::Code => html! {
+                                <p>{"메일을 다른 기기에서 확인할 때는 메일의 인증 코드를 이 화면에 입력하세요"}</p>
+                            },
+                            Method::Password => match *password_mode {
+                                PasswordMode::SignIn => html! {
+                                    <>
+                                        <p>
+                                            <button type="button" onclick={switch_password_mode(PasswordMode::Reset)} class={LINK_BUTTON_CLASS}>
+                                                {"비밀번호를 잊으셨나요?"}
+                                            </button>
+                                        </p>
+                                        <p>
+                                            {"계정이 없으신가요? "}
+                                            <button type="button" onclick={switch_password_mode(PasswordMode::SignUp)} class={LINK_BUTTON_CLASS}>
+                                                {"가입하기"}
+                                            </button>
+                                        </p>
+                                    </>
+                                },
+                                PasswordMode::SignUp | PasswordMode::Reset => html! {
+                                    <p>
+                                        <button type="button" onclick={switch_password_mode(PasswordMode::SignIn)} class={LINK_BUTTON_CLASS}>
+                                            {"비밀번호로 로그인하기"}
+                                        </button>
+                                    </p>
+                                },
+                            },
+                        }}
+                    </div>
+                </form>
+
+                {if is_guest {
+                    html! {
+                        <div class="rounded-md bg-amber-50 p-4 text-sm text-amber-800">
+                            {"지금은 게스트 모드입니다. 로그인하면 이 브라우저에 저장된 자격증과 문제를 계정으로 옮깁니다."}
+                        </div>
+                    }
+                } else {
+                    html! {
+                        <div class="border-t border-gray-200 pt-6 text-center space-y-2">
+                            <button
+                                type="button"
+                                onclick={on_start_guest}
+                                disabled={*loading}
+                                class="w-full py-2 px-4 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                            >
+                                {"로그인 없이 시작하기"}
+                            </button>
+                            <p class="text-xs text-gray-500">
+                                {"데이터는 이 브라우저에만 저장됩니다. 나중에 로그인하면 계정으로 옮길 수 있습니다."}
+                            </p>
+                        </div>
                     }
                 }}
+
+                <ServerSwitcher />
             </div>
         </div>
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,11 @@
+pub mod context;
+pub mod guest_migration;
 pub mod login;
+pub mod reset_password;
+pub mod server_switcher;
 
-pub use login::Login;
\ No newline at end of file
+pub use context::{use_has_pending_guest_data, use_is_guest, use_session, use_user, AuthProvider};
+pub use guest_migration::GuestMigration;
+pub use login::Login;
+pub use reset_password::ResetPassword;
+pub use server_switcher::ServerSwitcher;
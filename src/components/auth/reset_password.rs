@@ -0,0 +1,113 @@
+use yew::prelude::*;
+use yew_router::prelude::*;
+use web_sys::HtmlInputElement;
+use wasm_bindgen_futures::spawn_local;
+use crate::routes::Route;
+use crate::services::AuthService;
+
+const MIN_PASSWORD_LENGTH: usize = 6;
+
+/// 새 비밀번호 설정. 비밀번호 재설정 메일의 링크로 로그인하면 이 화면으로 옵니다.
+#[function_component(ResetPassword)]
+pub fn reset_password() -> Html {
+    let navigator = use_navigator().unwrap();
+    let password_ref = use_node_ref();
+    let confirm_ref = use_node_ref();
+    let loading = use_state(|| false);
+    let error = use_state(|| None::<String>);
+
+    let on_submit = {
+        let password_ref = password_ref.clone();
+        let confirm_ref = confirm_ref.clone();
+        let loading = loading.clone();
+        let error = error.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let value = |node: &NodeRef| {
+                node.cast::<HtmlInputElement>().map(|input| input.value()).unwrap_or_default()
+            };
+            let password = value(&password_ref);
+            if password.chars().count() < MIN_PASSWORD_LENGTH {
+                error.set(Some(format!("비밀번호는 {}자 이상이어야 합니다.", MIN_PASSWORD_LENGTH)));
+                return;
+            }
+            if password != value(&confirm_ref) {
+                error.set(Some("두 비밀번호가 다릅니다.".to_string()));
+                return;
+            }
+
+            let navigator = navigator.clone();
+            let loading = loading.clone();
+            let error = error.clone();
+            loading.set(true);
+            error.set(None);
+
+            spawn_local(async move {
+                match AuthService::update_password(&password).await {
+                    Ok(()) => navigator.push(&Route::Home),
+                    Err(e) => {
+                        error.set(Some(e));
+                        loading.set(false);
+                    }
+                }
+            });
+        })
+    };
+
+    html! {
+        <div class="max-w-md mx-auto bg-white shadow rounded-lg p-6 space-y-6">
+            <div>
+                <h2 class="text-xl font-bold text-gray-900">{"새 비밀번호 설정"}</h2>
+                <p class="mt-1 text-sm text-gray-600">{"앞으로 로그인할 때 쓸 비밀번호를 입력해주세요."}</p>
+            </div>
+
+            <form class="space-y-4" onsubmit={on_submit}>
+                <div>
+                    <label for="new-password" class="block text-sm font-medium text-gray-700">{"새 비밀번호"}</label>
+                    <input
+                        ref={password_ref}
+                        id="new-password"
+                        type="password"
+                        autocomplete="new-password"
+                        required=true
+                        disabled={*loading}
+                        class="mt-1 block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm"
+                    />
+                    <p class="mt-1 text-xs text-gray-500">{format!("{}자 이상", MIN_PASSWORD_LENGTH)}</p>
+                </div>
+                <div>
+                    <label for="confirm-password" class="block text-sm font-medium text-gray-700">{"새 비밀번호 확인"}</label>
+                    <input
+                        ref={confirm_ref}
+                        id="confirm-password"
+                        type="password"
+                        autocomplete="new-password"
+                        required=true
+                        disabled={*loading}
+                        class="mt-1 block w-full px-3 py-2 border border-gray-300 rounded-md shadow-sm focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm"
+                    />
+                </div>
+
+                {if let Some(err) = &*error {
+                    html! {
+                        <div class="rounded-md bg-red-50 p-4">
+                            <div class="text-sm text-red-800">{err}</div>
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }}
+
+                <button
+                    type="submit"
+                    disabled={*loading}
+                    class="w-full flex justify-center py-2 px-4 border border-transparent text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50 disabled:cursor-not-allowed"
+                >
+                    {if *loading { "저장 중..." } else { "비밀번호 저장" }}
+                </button>
+            </form>
+        </div>
+    }
+}
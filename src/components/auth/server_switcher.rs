@@ -0,0 +1,147 @@
+// 서버 전환기 (개발용)
+//
+// 설정(`server_switcher`)이 켜져 있거나 개발 빌드일 때만 보입니다. 서버를 바꾸면 페이지를 새로 불러오고,
+// 세션은 서버마다 따로 저장되므로 바꾼 서버에서는 그 서버의 로그인 상태로 시작합니다.
+
+use crate::config::supabase::{self, Environment};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+const INPUT_CLASS: &str = "block w-full px-2 py-1 border border-gray-300 rounded text-xs focus:outline-none focus:ring-blue-500 focus:border-blue-500";
+
+fn bind(state: &UseStateHandle<String>) -> Callback<InputEvent> {
+    let state = state.clone();
+    Callback::from(move |e: InputEvent| {
+        let input: HtmlInputElement = e.target_unchecked_into();
+        state.set(input.value());
+    })
+}
+
+fn switch_to(name: &str) {
+    supabase::choose_environment(name);
+    let _ = web_sys::window().unwrap().location().reload();
+}
+
+#[function_component(ServerSwitcher)]
+pub fn server_switcher() -> Html {
+    let config = supabase::loaded();
+    let open = use_state(|| false);
+    let environments = use_state(|| config.environments.clone());
+    let name = use_state(String::new);
+    let url = use_state(String::new);
+    let anon_key = use_state(String::new);
+    let error = use_state(|| None::<String>);
+
+    if !config.server_switcher {
+        return html! {};
+    }
+
+    let toggle = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(!*open))
+    };
+
+    let on_add = {
+        let environments = environments.clone();
+        let name = name.clone();
+        let url = url.clone();
+        let anon_key = anon_key.clone();
+        let error = error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let environment = Environment {
+                name: (*name).clone(),
+                url: (*url).clone(),
+                anon_key: (*anon_key).clone(),
+            };
+            match supabase::add_custom_environment(environment) {
+                Ok(environment) => {
+                    let mut list = (*environments).clone();
+                    list.push(environment);
+                    environments.set(list);
+                    name.set(String::new());
+                    url.set(String::new());
+                    anon_key.set(String::new());
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        })
+    };
+
+    let remove = |target: String| {
+        let environments = environments.clone();
+        Callback::from(move |_| {
+            supabase::remove_custom_environment(&target);
+            environments.set(environments.iter().filter(|e| e.name != target).cloned().collect());
+        })
+    };
+
+    html! {
+        <div class="border-t border-gray-200 pt-4 text-xs text-gray-500">
+            <button type="button" onclick={toggle} class="hover:text-gray-700">
+                {format!("서버: {} {}", config.active.name, if *open { "▴" } else { "▾" })}
+            </button>
+            {if *open {
+                html! {
+                    <div class="mt-3 space-y-3 text-left">
+                        <ul class="space-y-1">
+                            {for environments.iter().map(|environment| {
+                                let is_active = environment.name == config.active.name;
+                                let target = environment.name.clone();
+                                html! {
+                                    <li class="flex items-center justify-between gap-2">
+                                        <div class="min-w-0">
+                                            <p class="font-medium text-gray-700">{&environment.name}</p>
+                                            <p class="truncate">{&environment.url}</p>
+                                        </div>
+                                        <div class="flex-shrink-0 space-x-2">
+                                            {if is_active {
+                                                html! { <span class="text-green-700">{"사용 중"}</span> }
+                                            } else {
+                                                html! {
+                                                    <button type="button" onclick={move |_| switch_to(&target)}
+                                                        class="text-blue-600 hover:text-blue-800">
+                                                        {"전환"}
+                                                    </button>
+                                                }
+                                            }}
+                                            {if !is_active && supabase::is_custom_environment(&environment.name) {
+                                                html! {
+                                                    <button type="button" onclick={remove(environment.name.clone())}
+                                                        class="text-red-600 hover:text-red-800">
+                                                        {"삭제"}
+                                                    </button>
+                                                }
+                                            } else {
+                                                html! {}
+                                            }}
+                                        </div>
+                                    </li>
+                                }
+                            })}
+                        </ul>
+                        <form onsubmit={on_add} class="space-y-2">
+                            <input type="text" placeholder="이름 (예: 로컬)" class={INPUT_CLASS}
+                                value={(*name).clone()} oninput={bind(&name)} />
+                            <input type="url" placeholder="주소 (예: http://localhost:54321)" class={INPUT_CLASS}
+                                value={(*url).clone()} oninput={bind(&url)} />
+                            <input type="text" placeholder="anon key" class={INPUT_CLASS}
+                                value={(*anon_key).clone()} oninput={bind(&anon_key)} />
+                            {if let Some(e) = &*error {
+                                html! { <p class="text-red-600">{e}</p> }
+                            } else {
+                                html! {}
+                            }}
+                            <button type="submit" class="w-full py-1 border border-gray-300 rounded text-gray-700 bg-white hover:bg-gray-50">
+                                {"서버 추가"}
+                            </button>
+                        </form>
+                    </div>
+                }
+            } else {
+                html! {}
+            }}
+        </div>
+    }
+}
@@ -1,7 +1,7 @@
 use crate::components::question::QuestionList;
 use crate::models::Certificate;
 use crate::routes::Route;
-use crate::services::CertificateService;
+use crate::services::{CertificateService, TrashService};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::window;
 use yew::prelude::*;
@@ -50,7 +50,7 @@ pub fn certificate_detail(props: &CertificateDetailProps) -> Html {
         let error = error.clone();
         Callback::from(move |_| {
             if let Some(c) = &*cert_handle {
-                let confirmation_message = format!("[{}] 자격증과 관련된 모든 문제가 삭제됩니다. 정말 삭제하시겠습니까?", c.name);
+                let confirmation_message = format!("[{}] 자격증을 휴지통으로 옮깁니다. 휴지통에서 {}일 안에 복원할 수 있습니다.", c.name, TrashService::RETENTION_DAYS);
                 if window().unwrap().confirm_with_message(&confirmation_message).unwrap_or(false) {
                     let id = c.id.clone();
                     let navigator = navigator.clone();
@@ -100,6 +100,9 @@ pub fn certificate_detail(props: &CertificateDetailProps) -> Html {
                             <Link<Route> to={Route::Quiz { certificate_id: cert.id.clone() }} classes="inline-flex justify-center py-2 px-4 border border-transparent shadow-sm text-sm font-medium rounded-md text-white bg-green-600 hover:bg-green-700">
                                 {"문제 풀기"}
                             </Link<Route>>
+                            <Link<Route> to={Route::Print { certificate_id: cert.id.clone() }} classes="inline-flex justify-center py-2 px-4 border border-gray-300 shadow-sm text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50">
+                                {"시험지 인쇄"}
+                            </Link<Route>>
                             <Link<Route> to={Route::NewQuestion} classes="inline-flex justify-center py-2 px-4 border border-transparent shadow-sm text-sm font-medium rounded-md text-white bg-blue-600 hover:bg-blue-700">
                                 {"문제 추가"}
                             </Link<Route>>
@@ -1,7 +1,7 @@
 use crate::components::CertificateForm;
 use crate::models::Certificate;
 use crate::routes::Route;
-use crate::services::CertificateService;
+use crate::services::{CertificateService, TrashService};
 use wasm_bindgen_futures::spawn_local;
 use web_sys::window;
 use yew::prelude::*;
@@ -48,7 +48,7 @@ pub fn certificate_list() -> Html {
         Callback::from(move |(id, name): (String, String)| {
             let certificates = certificates.clone();
             let error = error.clone();
-            let confirmation_message = format!("[{}] 자격증과 관련된 모든 문제가 삭제됩니다. 정말 삭제하시겠습니까?", name);
+            let confirmation_message = format!("[{}] 자격증을 휴지통으로 옮깁니다. 휴지통에서 {}일 안에 복원할 수 있습니다.", name, TrashService::RETENTION_DAYS);
 
             if window().unwrap().confirm_with_message(&confirmation_message).unwrap_or(false) {
                 spawn_local(async move {
@@ -0,0 +1,38 @@
+use crate::components::markdown::{RawHtmlContext, RawHtmlSettings};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[function_component(HtmlSettings)]
+pub fn html_settings() -> Html {
+    let context = use_context::<RawHtmlContext>().expect("RawHtmlProvider 안에서 써야 합니다");
+    let settings = context.settings;
+
+    let on_toggle = |apply: fn(&mut RawHtmlSettings, bool)| {
+        let update = context.update.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = settings;
+            apply(&mut next, input.checked());
+            update.emit(next);
+        })
+    };
+    let on_own = on_toggle(|s, checked| s.own = checked);
+    let on_imported = on_toggle(|s, checked| s.imported = checked);
+
+    html! {
+        <div>
+            <h3 class="text-lg font-medium text-gray-900">{"마크다운 HTML 설정"}</h3>
+            <p class="mt-1 text-sm text-gray-600">
+                {"문제에 직접 쓴 HTML 태그를 그대로 표시할지 출처별로 정합니다. 허용하더라도 스크립트, 이벤트 속성, 허용되지 않은 태그와 링크 주소는 항상 제거됩니다. 허용하지 않으면 HTML은 글자 그대로 보입니다."}
+            </p>
+            <label class="mt-3 flex items-center gap-2 text-sm text-gray-700">
+                <input type="checkbox" checked={settings.own} onchange={on_own} />
+                {"직접 작성한 자격증의 HTML 허용"}
+            </label>
+            <label class="mt-2 flex items-center gap-2 text-sm text-gray-700">
+                <input type="checkbox" checked={settings.imported} onchange={on_imported} />
+                {"가져온 자격증(JSON·마크다운·GIFT·Aiken 파일)의 HTML 허용"}
+            </label>
+        </div>
+    }
+}
@@ -1,50 +1,191 @@
-use crate::services::DataService;
-use gloo_file::{futures::read_as_text, Blob, File, ObjectUrl};
+use crate::components::data::{HtmlSettings, SnapshotManager, TextImport};
+use crate::services::{BackupCrypto, DataService};
+use gloo_file::{futures::{read_as_bytes, read_as_text}, Blob, File, ObjectUrl};
 use wasm_bindgen::JsCast; // JsCast 트레이트를 가져옵니다.
 use wasm_bindgen_futures::spawn_local;
 use web_sys::{HtmlElement, HtmlInputElement};
 use yew::prelude::*;
 
+/// 메모리의 데이터를 파일로 내려받게 합니다.
+pub fn download_file(bytes: &[u8], mime_type: &str, filename: &str) {
+    let blob = Blob::new_with_options(bytes, Some(mime_type));
+    let url = ObjectUrl::from(blob);
+
+    let a: HtmlElement = web_sys::window()
+        .unwrap()
+        .document()
+        .unwrap()
+        .create_element("a")
+        .unwrap()
+        .dyn_into()
+        .unwrap();
+
+    a.set_attribute("href", &url).unwrap();
+    a.set_attribute("download", filename).unwrap();
+    a.click();
+}
+
 #[function_component(DataManagement)]
 pub fn data_management() -> Html {
     let message = use_state(|| None::<String>);
+    // 형식 제약 때문에 옮기지 못한 항목 (GIFT / Aiken)
+    let format_warnings = use_state(Vec::<String>::new);
     let is_loading = use_state(|| false);
     let file_input_ref = use_node_ref();
+    // 백업 암호화
+    let encrypt_export = use_state(|| false);
+    let export_passphrase = use_state(String::new);
+    let export_passphrase_confirm = use_state(String::new);
+    let pending_encrypted = use_state(|| None::<String>);
+    let import_passphrase = use_state(String::new);
 
     let on_export = {
         let message = message.clone();
         let is_loading = is_loading.clone();
+        let encrypt_export = encrypt_export.clone();
+        let export_passphrase = export_passphrase.clone();
+        let export_passphrase_confirm = export_passphrase_confirm.clone();
         Callback::from(move |_| {
+            let passphrase = if *encrypt_export {
+                if export_passphrase.chars().count() < 8 {
+                    message.set(Some("암호는 8자 이상이어야 합니다.".to_string()));
+                    return;
+                }
+                if *export_passphrase != *export_passphrase_confirm {
+                    message.set(Some("암호 확인이 일치하지 않습니다.".to_string()));
+                    return;
+                }
+                Some((*export_passphrase).clone())
+            } else {
+                None
+            };
+
             let message = message.clone();
             let is_loading = is_loading.clone();
             is_loading.set(true);
             message.set(Some("데이터를 내보내는 중...".to_string()));
 
             spawn_local(async move {
-                match DataService::export_data().await {
+                let result = match &passphrase {
+                    Some(passphrase) => DataService::export_data_encrypted(passphrase).await,
+                    None => DataService::export_data().await,
+                };
+                match result {
                     Ok(json_str) => {
-                        let blob = Blob::new_with_options(
-                            json_str.as_bytes(),
-                            Some("application/json"),
-                        );
-                        let url = ObjectUrl::from(blob);
-
-                        let a: HtmlElement = web_sys::window()
-                            .unwrap()
-                            .document()
-                            .unwrap()
-                            .create_element("a")
-                            .unwrap()
-                            .dyn_into()
-                            .unwrap();
-                        
                         let now = chrono::Local::now();
-                        let filename = format!("quiznote_backup_{}.json", now.format("%Y%m%d_%H%M%S"));
+                        let kind = if passphrase.is_some() { "encrypted" } else { "backup" };
+                        let filename = format!("quiznote_{}_{}.json", kind, now.format("%Y%m%d_%H%M%S"));
+                        download_file(json_str.as_bytes(), "application/json", &filename);
+                        message.set(Some(format!("'{}' 파일로 내보내기 성공!", filename)));
+                    }
+                    Err(e) => message.set(Some(format!("내보내기 실패: {}", e))),
+                }
+                is_loading.set(false);
+            });
+        })
+    };
+
+    let on_export_markdown = {
+        let message = message.clone();
+        let is_loading = is_loading.clone();
+        Callback::from(move |_| {
+            let message = message.clone();
+            let is_loading = is_loading.clone();
+            is_loading.set(true);
+            message.set(Some("마크다운 파일을 만드는 중...".to_string()));
+
+            spawn_local(async move {
+                match DataService::export_markdown_zip().await {
+                    Ok(bytes) => {
+                        let now = chrono::Local::now();
+                        let filename = format!("quiznote_markdown_{}.zip", now.format("%Y%m%d_%H%M%S"));
+                        download_file(&bytes, "application/zip", &filename);
+                        message.set(Some(format!("'{}' 파일로 내보내기 성공!", filename)));
+                    }
+                    Err(e) => message.set(Some(format!("내보내기 실패: {}", e))),
+                }
+                is_loading.set(false);
+            });
+        })
+    };
+
+    let on_export_anki = {
+        let message = message.clone();
+        let is_loading = is_loading.clone();
+        Callback::from(move |_| {
+            let message = message.clone();
+            let is_loading = is_loading.clone();
+            is_loading.set(true);
+            message.set(Some("Anki 덱을 만드는 중...".to_string()));
+
+            spawn_local(async move {
+                match DataService::export_anki_package().await {
+                    Ok(bytes) => {
+                        let now = chrono::Local::now();
+                        let filename = format!("quiznote_{}.apkg", now.format("%Y%m%d_%H%M%S"));
+                        download_file(&bytes, "application/octet-stream", &filename);
+                        message.set(Some(format!("'{}' 파일로 내보내기 성공!", filename)));
+                    }
+                    Err(e) => message.set(Some(format!("내보내기 실패: {}", e))),
+                }
+                is_loading.set(false);
+            });
+        })
+    };
+
+    let on_export_qti = {
+        let message = message.clone();
+        let is_loading = is_loading.clone();
+        Callback::from(move |_| {
+            let message = message.clone();
+            let is_loading = is_loading.clone();
+            is_loading.set(true);
+            message.set(Some("QTI 패키지를 만드는 중...".to_string()));
+
+            spawn_local(async move {
+                match DataService::export_qti_package().await {
+                    Ok(bytes) => {
+                        let now = chrono::Local::now();
+                        let filename = format!("quiznote_qti_{}.zip", now.format("%Y%m%d_%H%M%S"));
+                        download_file(&bytes, "application/zip", &filename);
+                        message.set(Some(format!("'{}' 파일로 내보내기 성공!", filename)));
+                    }
+                    Err(e) => message.set(Some(format!("내보내기 실패: {}", e))),
+                }
+                is_loading.set(false);
+            });
+        })
+    };
+
+    let on_export_moodle = |aiken: bool| {
+        let message = message.clone();
+        let format_warnings = format_warnings.clone();
+        let is_loading = is_loading.clone();
+        Callback::from(move |_| {
+            let message = message.clone();
+            let format_warnings = format_warnings.clone();
+            let is_loading = is_loading.clone();
+            is_loading.set(true);
+            format_warnings.set(Vec::new());
+            message.set(Some("Moodle 형식 파일을 만드는 중...".to_string()));
 
-                        a.set_attribute("href", &url).unwrap();
-                        a.set_attribute("download", &filename).unwrap();
-                        a.click();
+            spawn_local(async move {
+                let result = if aiken {
+                    DataService::export_aiken().await
+                } else {
+                    DataService::export_gift().await
+                };
+                match result {
+                    Ok(export) => {
+                        let now = chrono::Local::now();
+                        let filename = if aiken {
+                            format!("quiznote_aiken_{}.txt", now.format("%Y%m%d_%H%M%S"))
+                        } else {
+                            format!("quiznote_{}.gift", now.format("%Y%m%d_%H%M%S"))
+                        };
+                        download_file(export.text.as_bytes(), "text/plain;charset=utf-8", &filename);
                         message.set(Some(format!("'{}' 파일로 내보내기 성공!", filename)));
+                        format_warnings.set(export.warnings);
                     }
                     Err(e) => message.set(Some(format!("내보내기 실패: {}", e))),
                 }
@@ -52,30 +193,62 @@ pub fn data_management() -> Html {
             });
         })
     };
+    let on_export_gift = on_export_moodle(false);
+    let on_export_aiken = on_export_moodle(true);
 
     let on_import_change = {
         let message = message.clone();
+        let format_warnings = format_warnings.clone();
         let is_loading = is_loading.clone();
+        let pending_encrypted = pending_encrypted.clone();
         Callback::from(move |e: Event| {
             let input: HtmlInputElement = e.target_unchecked_into();
             if let Some(files) = input.files() {
                 if let Some(file) = files.get(0) {
                     let file = File::from(file);
                     let message = message.clone();
+                    let format_warnings = format_warnings.clone();
                     let is_loading = is_loading.clone();
+                    let pending_encrypted = pending_encrypted.clone();
                     is_loading.set(true);
+                    format_warnings.set(Vec::new());
+                    pending_encrypted.set(None);
                     message.set(Some("파일을 읽고 데이터를 가져오는 중...".to_string()));
 
                     spawn_local(async move {
-                        let content = read_as_text(&file).await.unwrap_or_default();
-                        match DataService::import_data(&content).await {
-                            Ok(msg) => {
+                        let name = file.name().to_lowercase();
+                        let result = if name.ends_with(".zip") {
+                            match read_as_bytes(&file).await {
+                                Ok(bytes) => DataService::import_markdown_zip(&bytes).await.map(|msg| (msg, Vec::new())),
+                                Err(e) => Err(e.to_string()),
+                            }
+                        } else if name.ends_with(".gift") || name.ends_with(".txt") {
+                            let content = read_as_text(&file).await.unwrap_or_default();
+                            DataService::import_moodle_text(&file.name(), &content).await
+                        } else {
+                            let content = read_as_text(&file).await.unwrap_or_default();
+                            if BackupCrypto::is_encrypted(&content) {
+                                // 암호를 입력받은 뒤 on_decrypt_import에서 이어서 가져옵니다.
+                                pending_encrypted.set(Some(content));
+                                message.set(Some("암호화된 백업 파일입니다. 암호를 입력해주세요.".to_string()));
+                                is_loading.set(false);
+                                return;
+                            }
+                            DataService::import_data(&content, None).await.map(|msg| (msg, Vec::new()))
+                        };
+                        match result {
+                            Ok((msg, warnings)) if warnings.is_empty() => {
                                 message.set(Some(msg));
                                 // 3초 후 페이지 새로고침
                                 gloo::timers::callback::Timeout::new(3000, || {
                                     web_sys::window().unwrap().location().reload().unwrap();
                                 }).forget();
                             }
+                            Ok((msg, warnings)) => {
+                                // 건너뛴 항목을 확인할 수 있도록 자동 새로고침하지 않습니다.
+                                message.set(Some(msg.replace(" 페이지가 새로고침됩니다.", "")));
+                                format_warnings.set(warnings);
+                            }
                             Err(e) => message.set(Some(format!("가져오기 실패: {}", e))),
                         }
                         is_loading.set(false);
@@ -85,6 +258,72 @@ pub fn data_management() -> Html {
         })
     };
 
+    let on_decrypt_import = {
+        let message = message.clone();
+        let is_loading = is_loading.clone();
+        let pending_encrypted = pending_encrypted.clone();
+        let import_passphrase = import_passphrase.clone();
+        Callback::from(move |_| {
+            let Some(content) = (*pending_encrypted).clone() else {
+                return;
+            };
+            let passphrase = (*import_passphrase).clone();
+            let message = message.clone();
+            let is_loading = is_loading.clone();
+            let pending_encrypted = pending_encrypted.clone();
+            let import_passphrase = import_passphrase.clone();
+            is_loading.set(true);
+            message.set(Some("복호화하고 데이터를 가져오는 중...".to_string()));
+
+            spawn_local(async move {
+                match DataService::import_data(&content, Some(&passphrase)).await {
+                    Ok(msg) => {
+                        pending_encrypted.set(None);
+                        import_passphrase.set(String::new());
+                        message.set(Some(msg));
+                        // 3초 후 페이지 새로고침
+                        gloo::timers::callback::Timeout::new(3000, || {
+                            web_sys::window().unwrap().location().reload().unwrap();
+                        }).forget();
+                    }
+                    // 암호가 틀린 경우 다시 입력할 수 있도록 파일 내용은 유지합니다.
+                    Err(e) => message.set(Some(format!("가져오기 실패: {}", e))),
+                }
+                is_loading.set(false);
+            });
+        })
+    };
+
+    let on_cancel_decrypt = {
+        let pending_encrypted = pending_encrypted.clone();
+        let import_passphrase = import_passphrase.clone();
+        let message = message.clone();
+        Callback::from(move |_| {
+            pending_encrypted.set(None);
+            import_passphrase.set(String::new());
+            message.set(None);
+        })
+    };
+
+    let bind_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            state.set(input.value());
+        })
+    };
+    let on_export_passphrase = bind_input(&export_passphrase);
+    let on_export_passphrase_confirm = bind_input(&export_passphrase_confirm);
+    let on_import_passphrase = bind_input(&import_passphrase);
+
+    let on_encrypt_toggle = {
+        let encrypt_export = encrypt_export.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            encrypt_export.set(input.checked());
+        })
+    };
+
     let on_import_click = {
         let file_input_ref = file_input_ref.clone();
         Callback::from(move |_| {
@@ -105,6 +344,23 @@ pub fn data_management() -> Html {
                     <p class="mt-1 text-sm text-gray-600">
                         {"모든 자격증과 문제 데이터를 JSON 파일로 백업합니다."}
                     </p>
+                    <label class="mt-3 flex items-center gap-2 text-sm text-gray-700">
+                        <input type="checkbox" checked={*encrypt_export} onchange={on_encrypt_toggle} />
+                        {"JSON 백업을 암호로 보호"}
+                    </label>
+                    if *encrypt_export {
+                        <div class="mt-2 flex flex-wrap gap-2">
+                            <input type="password" placeholder="암호 (8자 이상)" autocomplete="new-password"
+                                value={(*export_passphrase).clone()} oninput={on_export_passphrase}
+                                class="border border-gray-300 rounded-md px-3 py-2 text-sm" />
+                            <input type="password" placeholder="암호 확인" autocomplete="new-password"
+                                value={(*export_passphrase_confirm).clone()} oninput={on_export_passphrase_confirm}
+                                class="border border-gray-300 rounded-md px-3 py-2 text-sm" />
+                        </div>
+                        <p class="mt-1 text-xs text-gray-500">
+                            {"암호를 잊으면 백업을 복원할 수 없습니다. 암호는 어디에도 저장되지 않습니다."}
+                        </p>
+                    }
                     <button
                         onclick={on_export}
                         disabled={*is_loading}
@@ -112,6 +368,44 @@ pub fn data_management() -> Html {
                     >
                         {"내보내기"}
                     </button>
+                    <button
+                        onclick={on_export_markdown}
+                        disabled={*is_loading}
+                        class="mt-3 ml-2 inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                    >
+                        {"마크다운(zip)으로 내보내기"}
+                    </button>
+                    <button
+                        onclick={on_export_anki}
+                        disabled={*is_loading}
+                        class="mt-3 ml-2 inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                    >
+                        {"Anki 덱(.apkg)으로 내보내기"}
+                    </button>
+                    <button
+                        onclick={on_export_gift}
+                        disabled={*is_loading}
+                        class="mt-3 ml-2 inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                    >
+                        {"Moodle GIFT로 내보내기"}
+                    </button>
+                    <button
+                        onclick={on_export_aiken}
+                        disabled={*is_loading}
+                        class="mt-3 ml-2 inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                    >
+                        {"Moodle Aiken으로 내보내기"}
+                    </button>
+                    <button
+                        onclick={on_export_qti}
+                        disabled={*is_loading}
+                        class="mt-3 ml-2 inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                    >
+                        {"QTI 2.1(zip)로 내보내기"}
+                    </button>
+                    <p class="mt-2 text-xs text-gray-500">
+                        {"마크다운 내보내기는 문제 한 개당 .md 파일 한 개를 만들어 git 저장소에서 버전 관리하기 좋습니다. Anki 덱은 자격증별 하위 덱으로 만들어집니다. Aiken은 정답이 하나인 문제만, 해설 없이 내보냅니다. QTI 2.1 패키지는 대부분의 LMS에서 문항 은행으로 가져올 수 있습니다."}
+                    </p>
                 </div>
 
                 <div class="border-t border-gray-200"></div>
@@ -119,9 +413,9 @@ pub fn data_management() -> Html {
                 <div>
                     <h3 class="text-lg font-medium text-gray-900">{"데이터 가져오기"}</h3>
                     <p class="mt-1 text-sm text-gray-600">
-                        {"백업한 JSON 파일을 불러와 데이터를 복원합니다. 기존 데이터에 추가됩니다."}
+                        {"백업한 JSON 파일이나 마크다운 zip 파일, Moodle GIFT(.gift)·Aiken(.txt) 파일을 불러옵니다. 기존 데이터에 추가됩니다."}
                     </p>
-                    <input type="file" ref={file_input_ref} onchange={on_import_change} accept=".json" class="hidden" />
+                    <input type="file" ref={file_input_ref} onchange={on_import_change} accept=".json,.zip,.gift,.txt" class="hidden" />
                     <button
                         onclick={on_import_click}
                         disabled={*is_loading}
@@ -129,8 +423,37 @@ pub fn data_management() -> Html {
                     >
                         {"파일 선택 및 가져오기"}
                     </button>
+                    if pending_encrypted.is_some() {
+                        <div class="mt-3 flex flex-wrap items-center gap-2">
+                            <input type="password" placeholder="백업 암호" autocomplete="current-password"
+                                value={(*import_passphrase).clone()} oninput={on_import_passphrase}
+                                class="border border-gray-300 rounded-md px-3 py-2 text-sm" />
+                            <button
+                                onclick={on_decrypt_import}
+                                disabled={*is_loading || import_passphrase.is_empty()}
+                                class="inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md shadow-sm text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50"
+                            >
+                                {"복호화 후 가져오기"}
+                            </button>
+                            <button onclick={on_cancel_decrypt} disabled={*is_loading} class="text-sm text-gray-500 hover:text-gray-700">
+                                {"취소"}
+                            </button>
+                        </div>
+                    }
                 </div>
 
+                <div class="border-t border-gray-200"></div>
+
+                <TextImport />
+
+                <div class="border-t border-gray-200"></div>
+
+                <SnapshotManager />
+
+                <div class="border-t border-gray-200"></div>
+
+                <HtmlSettings />
+
                  {if let Some(msg) = &*message {
                     html! {
                         <div class="mt-4 p-4 bg-gray-50 rounded-lg text-center">
@@ -147,6 +470,17 @@ pub fn data_management() -> Html {
                 } else {
                     html! {}
                 }}
+
+                if !format_warnings.is_empty() {
+                    <div class="p-4 bg-yellow-50 border border-yellow-200 rounded-lg">
+                        <p class="text-sm font-medium text-yellow-800">
+                            {format!("변환하지 못한 항목 {}개", format_warnings.len())}
+                        </p>
+                        <ul class="mt-2 list-disc list-inside text-sm text-yellow-700 space-y-1">
+                            {for format_warnings.iter().map(|w| html! { <li>{w}</li> })}
+                        </ul>
+                    </div>
+                }
             </div>
         </div>
     }
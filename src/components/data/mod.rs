@@ -1,2 +1,9 @@
+pub mod html_settings;
 pub mod management;
-pub use management::DataManagement;
\ No newline at end of file
+pub mod snapshots;
+pub mod text_import;
+
+pub use html_settings::HtmlSettings;
+pub use management::DataManagement;
+pub use snapshots::SnapshotManager;
+pub use text_import::TextImport;
\ No newline at end of file
@@ -0,0 +1,267 @@
+use crate::models::{Snapshot, SnapshotMeta};
+use crate::services::snapshot_service::{CertificateDiff, DiffStatus};
+use crate::services::{DataService, SnapshotService};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+
+/// 선택한 스냅샷과 현재 데이터의 비교 결과
+#[derive(Clone, PartialEq)]
+struct Comparison {
+    snapshot: Snapshot,
+    diffs: Vec<CertificateDiff>,
+}
+
+#[function_component(SnapshotManager)]
+pub fn snapshot_manager() -> Html {
+    let snapshots = use_state(Vec::<SnapshotMeta>::new);
+    let comparison = use_state(|| None::<Comparison>);
+    let message = use_state(|| None::<String>);
+    let is_busy = use_state(|| false);
+    let reload = use_state(|| 0u32);
+
+    {
+        let snapshots = snapshots.clone();
+        let message = message.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                match SnapshotService::list().await {
+                    Ok(list) => snapshots.set(list),
+                    Err(e) => message.set(Some(format!("스냅샷 목록을 불러오지 못했습니다: {}", e))),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_take = {
+        let message = message.clone();
+        let is_busy = is_busy.clone();
+        let reload = reload.clone();
+        Callback::from(move |_| {
+            let message = message.clone();
+            let is_busy = is_busy.clone();
+            let reload = reload.clone();
+            is_busy.set(true);
+            spawn_local(async move {
+                match SnapshotService::take("수동 스냅샷").await {
+                    Ok(meta) => message.set(Some(format!(
+                        "스냅샷을 만들었습니다. (자격증 {}개, 문제 {}개)",
+                        meta.certificate_count, meta.question_count
+                    ))),
+                    Err(e) => message.set(Some(format!("스냅샷 생성 실패: {}", e))),
+                }
+                reload.set(*reload + 1);
+                is_busy.set(false);
+            });
+        })
+    };
+
+    let on_compare = {
+        let comparison = comparison.clone();
+        let message = message.clone();
+        let is_busy = is_busy.clone();
+        Callback::from(move |id: String| {
+            let comparison = comparison.clone();
+            let message = message.clone();
+            let is_busy = is_busy.clone();
+            is_busy.set(true);
+            message.set(None);
+            spawn_local(async move {
+                let result = async {
+                    let snapshot = SnapshotService::load(&id).await?;
+                    let current = DataService::collect_export().await?;
+                    let diffs = SnapshotService::diff(&snapshot, &current);
+                    Ok::<_, String>(Comparison { snapshot, diffs })
+                };
+                match result.await {
+                    Ok(result) => comparison.set(Some(result)),
+                    Err(e) => message.set(Some(format!("비교 실패: {}", e))),
+                }
+                is_busy.set(false);
+            });
+        })
+    };
+
+    let on_delete = {
+        let comparison = comparison.clone();
+        let message = message.clone();
+        let reload = reload.clone();
+        Callback::from(move |id: String| {
+            let comparison = comparison.clone();
+            let message = message.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                if let Err(e) = SnapshotService::delete(&id).await {
+                    message.set(Some(format!("스냅샷 삭제 실패: {}", e)));
+                }
+                if comparison.as_ref().is_some_and(|c| c.snapshot.id == id) {
+                    comparison.set(None);
+                }
+                reload.set(*reload + 1);
+            });
+        })
+    };
+
+    // certificate_id가 None이면 전체 복원
+    let on_restore = {
+        let comparison = comparison.clone();
+        let message = message.clone();
+        let is_busy = is_busy.clone();
+        let reload = reload.clone();
+        Callback::from(move |certificate_id: Option<String>| {
+            let Some(current) = (*comparison).clone() else {
+                return;
+            };
+            let confirmation = match &certificate_id {
+                Some(_) => "이 자격증을 스냅샷 시점으로 되돌립니다. 이후에 추가·수정한 문제는 사라집니다. 계속하시겠습니까?",
+                None => "모든 데이터를 스냅샷 시점으로 되돌립니다. 스냅샷 이후에 만든 자격증과 문제는 휴지통으로 옮겨집니다. 계속하시겠습니까?\n(복원 직전 상태도 스냅샷으로 남습니다.)",
+            };
+            if !window().unwrap().confirm_with_message(confirmation).unwrap_or(false) {
+                return;
+            }
+
+            let comparison = comparison.clone();
+            let message = message.clone();
+            let is_busy = is_busy.clone();
+            let reload = reload.clone();
+            is_busy.set(true);
+            message.set(Some("복원하는 중...".to_string()));
+            spawn_local(async move {
+                let result = match &certificate_id {
+                    Some(id) => SnapshotService::restore_certificate(&current.snapshot, id).await,
+                    None => SnapshotService::restore_all(&current.snapshot).await,
+                };
+                match result {
+                    Ok(msg) => {
+                        message.set(Some(msg));
+                        comparison.set(None);
+                    }
+                    Err(e) => message.set(Some(format!("복원 실패: {}", e))),
+                }
+                reload.set(*reload + 1);
+                is_busy.set(false);
+            });
+        })
+    };
+
+    let status_badge = |status: DiffStatus| {
+        let (label, class) = match status {
+            DiffStatus::Unchanged => ("변경 없음", "bg-gray-100 text-gray-600"),
+            DiffStatus::Changed => ("변경됨", "bg-yellow-100 text-yellow-800"),
+            DiffStatus::Removed => ("삭제됨", "bg-red-100 text-red-800"),
+            DiffStatus::Added => ("새로 생김", "bg-green-100 text-green-800"),
+        };
+        html! { <span class={classes!("px-2", "py-0.5", "rounded", "text-xs", class)}>{label}</span> }
+    };
+
+    html! {
+        <div>
+            <div class="flex items-center justify-between">
+                <h3 class="text-lg font-medium text-gray-900">{"로컬 스냅샷"}</h3>
+                <button
+                    onclick={on_take}
+                    disabled={*is_busy}
+                    class="inline-flex items-center px-3 py-1.5 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                >
+                    {"지금 스냅샷 만들기"}
+                </button>
+            </div>
+            <p class="mt-1 text-sm text-gray-600">
+                {"영구 삭제나 복원 직전, 그리고 6시간마다 이 브라우저에 데이터 사본을 저장합니다. 최근 30개까지 보관됩니다."}
+            </p>
+
+            if let Some(msg) = &*message {
+                <p class="mt-2 text-sm text-gray-700">{msg}</p>
+            }
+
+            if snapshots.is_empty() {
+                <p class="mt-3 text-sm text-gray-500">{"아직 저장된 스냅샷이 없습니다."}</p>
+            } else {
+                <ul class="mt-3 divide-y divide-gray-200 border border-gray-200 rounded-md">
+                    {for snapshots.iter().map(|meta| {
+                        let id = meta.id.clone();
+                        let on_compare = on_compare.clone();
+                        let on_delete = on_delete.clone();
+                        let delete_id = id.clone();
+                        let is_selected = comparison.as_ref().is_some_and(|c| c.snapshot.id == meta.id);
+                        html! {
+                            <li class={classes!("flex", "items-center", "justify-between", "px-4", "py-2", "text-sm", is_selected.then_some("bg-blue-50"))}>
+                                <div>
+                                    <span class="font-medium text-gray-900">
+                                        {meta.created_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string()}
+                                    </span>
+                                    <span class="ml-2 text-gray-500">{&meta.reason}</span>
+                                    <span class="ml-2 text-gray-400">
+                                        {format!("자격증 {}개 · 문제 {}개", meta.certificate_count, meta.question_count)}
+                                    </span>
+                                </div>
+                                <div class="space-x-3">
+                                    <button onclick={move |_| on_compare.emit(id.clone())} disabled={*is_busy}
+                                        class="text-blue-600 hover:text-blue-900 disabled:opacity-50">
+                                        {"비교"}
+                                    </button>
+                                    <button onclick={move |_| on_delete.emit(delete_id.clone())} disabled={*is_busy}
+                                        class="text-gray-500 hover:text-red-600 disabled:opacity-50">
+                                        {"삭제"}
+                                    </button>
+                                </div>
+                            </li>
+                        }
+                    })}
+                </ul>
+            }
+
+            if let Some(current) = &*comparison {
+                <div class="mt-4 border border-gray-200 rounded-md p-4">
+                    <div class="flex items-center justify-between mb-3">
+                        <h4 class="text-sm font-medium text-gray-900">{"현재 데이터와 비교"}</h4>
+                        <button
+                            onclick={{ let on_restore = on_restore.clone(); move |_| on_restore.emit(None) }}
+                            disabled={*is_busy}
+                            class="inline-flex items-center px-3 py-1.5 border border-transparent text-sm font-medium rounded-md text-white bg-red-600 hover:bg-red-700 disabled:opacity-50"
+                        >
+                            {"이 시점으로 전체 복원"}
+                        </button>
+                    </div>
+                    <table class="min-w-full text-sm">
+                        <thead>
+                            <tr class="text-left text-gray-500">
+                                <th class="py-1">{"자격증"}</th>
+                                <th class="py-1">{"상태"}</th>
+                                <th class="py-1">{"이후 추가"}</th>
+                                <th class="py-1">{"이후 삭제"}</th>
+                                <th class="py-1">{"이후 수정"}</th>
+                                <th></th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            {for current.diffs.iter().map(|diff| {
+                                let on_restore = on_restore.clone();
+                                let id = diff.certificate_id.clone();
+                                let restorable = matches!(diff.status, DiffStatus::Changed | DiffStatus::Removed);
+                                html! {
+                                    <tr class="border-t border-gray-100">
+                                        <td class="py-1 text-gray-900">{&diff.name}</td>
+                                        <td class="py-1">{status_badge(diff.status)}</td>
+                                        <td class="py-1">{diff.questions_added}</td>
+                                        <td class="py-1">{diff.questions_removed}</td>
+                                        <td class="py-1">{diff.questions_changed}</td>
+                                        <td class="py-1 text-right">
+                                            if restorable {
+                                                <button onclick={move |_| on_restore.emit(Some(id.clone()))} disabled={*is_busy}
+                                                    class="text-blue-600 hover:text-blue-900 disabled:opacity-50">
+                                                    {"이 자격증만 복원"}
+                                                </button>
+                                            }
+                                        </td>
+                                    </tr>
+                                }
+                            })}
+                        </tbody>
+                    </table>
+                </div>
+            }
+        </div>
+    }
+}
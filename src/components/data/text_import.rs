@@ -0,0 +1,334 @@
+use crate::models::{Certificate, QuestionOption};
+use crate::services::exam_text_parser::ParsedQuestion;
+use crate::services::{CertificateService, ExamTextParser, QuestionService};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use yew::prelude::*;
+
+/// 기출문제 텍스트를 붙여넣어 여러 문제를 한 번에 등록하는 화면.
+/// 파싱 결과를 미리보기에서 고친 뒤 저장합니다.
+#[function_component(TextImport)]
+pub fn text_import() -> Html {
+    let certificates = use_state(Vec::<Certificate>::new);
+    let selected_certificate = use_state(String::new);
+    let raw_text = use_state(String::new);
+    let answer_key = use_state(String::new);
+    let parsed = use_state(Vec::<ParsedQuestion>::new);
+    let warnings = use_state(Vec::<String>::new);
+    let message = use_state(|| None::<String>);
+    let is_saving = use_state(|| false);
+
+    {
+        let certificates = certificates.clone();
+        let message = message.clone();
+        use_effect_with((), move |_| {
+            spawn_local(async move {
+                match CertificateService::get_all().await {
+                    Ok(certs) => certificates.set(certs),
+                    Err(e) => message.set(Some(e)),
+                }
+            });
+            || ()
+        });
+    }
+
+    let on_certificate_change = {
+        let selected_certificate = selected_certificate.clone();
+        Callback::from(move |e: Event| {
+            let value = e.target_dyn_into::<HtmlSelectElement>().unwrap().value();
+            selected_certificate.set(value);
+        })
+    };
+
+    let on_text_change = {
+        let raw_text = raw_text.clone();
+        Callback::from(move |e: Event| {
+            let target: HtmlTextAreaElement = e.target_unchecked_into();
+            raw_text.set(target.value());
+        })
+    };
+
+    let on_answer_key_change = {
+        let answer_key = answer_key.clone();
+        Callback::from(move |e: Event| {
+            let target: HtmlTextAreaElement = e.target_unchecked_into();
+            answer_key.set(target.value());
+        })
+    };
+
+    let on_parse = {
+        let raw_text = raw_text.clone();
+        let answer_key = answer_key.clone();
+        let parsed = parsed.clone();
+        let warnings = warnings.clone();
+        let message = message.clone();
+        Callback::from(move |_| {
+            let exam = ExamTextParser::parse(&raw_text, &answer_key);
+            message.set(Some(format!("{}개의 문제를 찾았습니다.", exam.questions.len())));
+            parsed.set(exam.questions);
+            warnings.set(exam.warnings);
+        })
+    };
+
+    let on_stem_change = {
+        let parsed = parsed.clone();
+        Callback::from(move |(idx, value): (usize, String)| {
+            let mut items = (*parsed).clone();
+            if let Some(item) = items.get_mut(idx) {
+                item.question.content = value;
+            }
+            parsed.set(items);
+        })
+    };
+
+    let on_explanation_change = {
+        let parsed = parsed.clone();
+        Callback::from(move |(idx, value): (usize, String)| {
+            let mut items = (*parsed).clone();
+            if let Some(item) = items.get_mut(idx) {
+                item.question.explanation = value;
+            }
+            parsed.set(items);
+        })
+    };
+
+    let on_option_change = {
+        let parsed = parsed.clone();
+        Callback::from(move |(idx, opt_idx, value): (usize, usize, String)| {
+            let mut items = (*parsed).clone();
+            if let Some(option) = items.get_mut(idx).and_then(|i| i.question.options.get_mut(opt_idx)) {
+                option.content = value;
+            }
+            parsed.set(items);
+        })
+    };
+
+    let on_correct_change = {
+        let parsed = parsed.clone();
+        Callback::from(move |(idx, opt_idx): (usize, usize)| {
+            let mut items = (*parsed).clone();
+            if let Some(item) = items.get_mut(idx) {
+                for (i, option) in item.question.options.iter_mut().enumerate() {
+                    option.is_correct = i == opt_idx;
+                }
+            }
+            parsed.set(items);
+        })
+    };
+
+    let on_add_option = {
+        let parsed = parsed.clone();
+        Callback::from(move |idx: usize| {
+            let mut items = (*parsed).clone();
+            if let Some(item) = items.get_mut(idx) {
+                item.question.options.push(QuestionOption::new(String::new(), false));
+            }
+            parsed.set(items);
+        })
+    };
+
+    let on_remove_question = {
+        let parsed = parsed.clone();
+        Callback::from(move |idx: usize| {
+            let mut items = (*parsed).clone();
+            if idx < items.len() {
+                items.remove(idx);
+            }
+            parsed.set(items);
+        })
+    };
+
+    let on_save = {
+        let selected_certificate = selected_certificate.clone();
+        let parsed = parsed.clone();
+        let warnings = warnings.clone();
+        let message = message.clone();
+        let is_saving = is_saving.clone();
+        Callback::from(move |_| {
+            if selected_certificate.is_empty() {
+                message.set(Some("자격증을 선택해주세요.".to_string()));
+                return;
+            }
+
+            let certificate_id = (*selected_certificate).clone();
+            let items = (*parsed).clone();
+            let parsed = parsed.clone();
+            let warnings = warnings.clone();
+            let message = message.clone();
+            let is_saving = is_saving.clone();
+            is_saving.set(true);
+
+            spawn_local(async move {
+                let mut created = 0;
+                let mut failed = Vec::new();
+
+                // 실패한 문제는 미리보기에 남겨 수정 후 다시 저장할 수 있게 합니다.
+                for mut item in items {
+                    item.question.certificate_id = certificate_id.clone();
+                    item.question.options.retain(|o| !o.content.trim().is_empty());
+                    match QuestionService::create(item.question.clone()).await {
+                        Ok(_) => created += 1,
+                        Err(e) => failed.push((item, e)),
+                    }
+                }
+
+                warnings.set(failed.iter().map(|(item, e)| format!("{}번: {}", item.number, e)).collect());
+                message.set(Some(if failed.is_empty() {
+                    format!("{}개의 문제를 저장했습니다.", created)
+                } else {
+                    format!("{}개 저장, {}개 실패했습니다. 실패한 문제를 수정 후 다시 저장해주세요.", created, failed.len())
+                }));
+                parsed.set(failed.into_iter().map(|(item, _)| item).collect());
+                is_saving.set(false);
+            });
+        })
+    };
+
+    html! {
+        <div class="space-y-4">
+            <div>
+                <h3 class="text-lg font-medium text-gray-900">{"기출문제 텍스트 가져오기"}</h3>
+                <p class="mt-1 text-sm text-gray-600">
+                    {"'1. 문제' 다음 줄에 ①②③④ 보기가 오는 형식의 텍스트를 붙여넣고, 정답표(예: 1-③ 2-①)를 입력하세요."}
+                </p>
+            </div>
+
+            <select
+                class="block w-full pl-3 pr-10 py-2 text-base border-gray-300 focus:outline-none focus:ring-blue-500 focus:border-blue-500 sm:text-sm rounded-md border"
+                onchange={on_certificate_change}
+                disabled={*is_saving}
+            >
+                <option value="" selected={selected_certificate.is_empty()}>{"저장할 자격증을 선택하세요"}</option>
+                { for certificates.iter().map(|cert| {
+                    html! { <option value={cert.id.clone()} selected={*selected_certificate == cert.id}>{&cert.name}</option> }
+                })}
+            </select>
+
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+                <textarea
+                    value={(*raw_text).clone()}
+                    onchange={on_text_change}
+                    rows="10"
+                    class="md:col-span-2 block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm p-2 border font-mono"
+                    placeholder={"1. 다음 중 ...\n① 보기1\n② 보기2\n③ 보기3\n④ 보기4"}
+                    disabled={*is_saving}
+                />
+                <textarea
+                    value={(*answer_key).clone()}
+                    onchange={on_answer_key_change}
+                    rows="10"
+                    class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm p-2 border font-mono"
+                    placeholder="정답표: 1-③ 2-① 3-④"
+                    disabled={*is_saving}
+                />
+            </div>
+
+            <button
+                onclick={on_parse}
+                disabled={*is_saving}
+                class="inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+            >
+                {"미리보기"}
+            </button>
+
+            if let Some(msg) = &*message {
+                <p class="text-sm text-gray-700">{msg}</p>
+            }
+
+            if !warnings.is_empty() {
+                <ul class="text-sm text-yellow-800 bg-yellow-50 rounded-md p-3 list-disc list-inside">
+                    { for warnings.iter().map(|w| html! { <li>{w}</li> }) }
+                </ul>
+            }
+
+            if !parsed.is_empty() {
+                <div class="space-y-4">
+                    {for parsed.iter().enumerate().map(|(idx, item)| {
+                        let on_stem_change = on_stem_change.clone();
+                        let on_explanation_change = on_explanation_change.clone();
+                        let on_add_option = on_add_option.clone();
+                        let on_remove_question = on_remove_question.clone();
+
+                        html! {
+                            <div key={item.question.id.clone()} class="p-4 border rounded-lg bg-gray-50 space-y-2">
+                                <div class="flex justify-between items-center">
+                                    <span class="text-sm font-medium text-gray-700">{format!("{}번", item.number)}</span>
+                                    <button
+                                        type="button"
+                                        onclick={move |_| on_remove_question.emit(idx)}
+                                        class="text-red-600 hover:text-red-900 text-sm"
+                                        disabled={*is_saving}
+                                    >
+                                        {"제외"}
+                                    </button>
+                                </div>
+                                <textarea
+                                    value={item.question.content.clone()}
+                                    onchange={move |e: Event| {
+                                        let target: HtmlTextAreaElement = e.target_unchecked_into();
+                                        on_stem_change.emit((idx, target.value()));
+                                    }}
+                                    rows="2"
+                                    class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm p-2 border"
+                                    disabled={*is_saving}
+                                />
+                                {for item.question.options.iter().enumerate().map(|(opt_idx, option)| {
+                                    let on_option_change = on_option_change.clone();
+                                    let on_correct_change = on_correct_change.clone();
+                                    html! {
+                                        <div key={option.id.clone()} class="flex items-center space-x-2">
+                                            <input
+                                                type="radio"
+                                                name={format!("correct_{}", item.question.id)}
+                                                checked={option.is_correct}
+                                                onchange={move |_| on_correct_change.emit((idx, opt_idx))}
+                                                class="h-4 w-4 text-blue-600 border-gray-300 focus:ring-blue-500"
+                                                disabled={*is_saving}
+                                            />
+                                            <input
+                                                type="text"
+                                                value={option.content.clone()}
+                                                onchange={move |e: Event| {
+                                                    let target: HtmlInputElement = e.target_unchecked_into();
+                                                    on_option_change.emit((idx, opt_idx, target.value()));
+                                                }}
+                                                class="flex-1 rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm p-1 border"
+                                                disabled={*is_saving}
+                                            />
+                                        </div>
+                                    }
+                                })}
+                                <button
+                                    type="button"
+                                    onclick={move |_| on_add_option.emit(idx)}
+                                    class="text-sm text-blue-600 hover:text-blue-900"
+                                    disabled={*is_saving}
+                                >
+                                    {"+ 보기 추가"}
+                                </button>
+                                <textarea
+                                    value={item.question.explanation.clone()}
+                                    onchange={move |e: Event| {
+                                        let target: HtmlTextAreaElement = e.target_unchecked_into();
+                                        on_explanation_change.emit((idx, target.value()));
+                                    }}
+                                    rows="2"
+                                    placeholder="해설 (선택)"
+                                    class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm p-2 border"
+                                    disabled={*is_saving}
+                                />
+                            </div>
+                        }
+                    })}
+                    <button
+                        onclick={on_save}
+                        disabled={*is_saving}
+                        class="inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md shadow-sm text-white bg-blue-600 hover:bg-blue-700 disabled:opacity-50"
+                    >
+                        { if *is_saving { "저장 중...".to_string() } else { format!("{}개 문제 저장", parsed.len()) } }
+                    </button>
+                </div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,274 @@
+// 문제·해설용 마크다운 확장
+//
+// CommonMark에 없는 아래 문법을 pulldown-cmark 이벤트 단계에서 HTML로 바꿉니다.
+//
+// > [!TIP] 제목(생략 가능)          콜아웃 상자 (NOTE, TIP, IMPORTANT, WARNING, CAUTION)
+// > 내용
+//
+// > [!HINT] 힌트 보기               접힌 힌트 (<details>). 다른 종류도 `[!TIP]-`처럼 `-`를 붙이면
+// > 내용                            접히고, `+`를 붙이면 펼친 채로 접을 수 있게 됩니다.
+//
+// ==형광펜==                         <mark>
+// ① ~ ⑩                           원문자 번호 (보기 번호와 같은 모양으로 표시). `(1)`처럼 괄호로 적은
+//                                   번호는 목록·수식에 흔히 쓰이므로 그대로 둡니다.
+//
+// 색과 모양은 index.html(앱 화면), 시험지 CSS, Anki 카드 CSS에서 각각 정합니다.
+
+use pulldown_cmark::{CowStr, Event, Tag};
+
+pub const CALLOUT_CLASS: &str = "callout";
+pub const CALLOUT_TITLE_CLASS: &str = "callout-title";
+pub const CIRCLED_NUMBER_CLASS: &str = "circled-number";
+
+/// (표기, CSS 클래스, 기본 제목, 기본으로 접는지)
+const CALLOUT_KINDS: [(&str, &str, &str, bool); 6] = [
+    ("NOTE", "callout-note", "참고", false),
+    ("TIP", "callout-tip", "팁", false),
+    ("IMPORTANT", "callout-important", "중요", false),
+    ("WARNING", "callout-warning", "주의", false),
+    ("CAUTION", "callout-caution", "경고", false),
+    ("HINT", "callout-hint", "힌트", true),
+];
+
+/// 콜아웃 종류별 CSS 클래스 (HTML 정리기 허용 목록용)
+pub fn callout_kind_classes() -> impl Iterator<Item = &'static str> {
+    CALLOUT_KINDS.iter().map(|(_, class, _, _)| *class)
+}
+
+pub fn apply(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    inline_marks(callouts(events))
+}
+
+struct Marker {
+    class: &'static str,
+    default_title: &'static str,
+    folded: Option<bool>,
+    /// 표시 뒤 같은 줄에 남은 글자 (직접 적은 제목)
+    rest: String,
+}
+
+/// `[!KIND]`, `[!KIND]-`, `[!KIND]+` 표시를 읽습니다. 종류는 대소문자를 가리지 않습니다.
+fn parse_marker(text: &str) -> Option<Marker> {
+    let inner = text.strip_prefix("[!")?;
+    let (kind, after) = inner.split_once(']')?;
+    let (_, class, default_title, folded_by_default) =
+        CALLOUT_KINDS.iter().find(|(name, ..)| name.eq_ignore_ascii_case(kind))?;
+    let (folded, rest) = match after.chars().next() {
+        Some('-') => (Some(true), &after[1..]),
+        Some('+') => (Some(false), &after[1..]),
+        _ => (folded_by_default.then_some(true), after),
+    };
+    // `[!TIP]제목`처럼 붙여 쓴 것은 표시로 보지 않습니다.
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    Some(Marker { class, default_title, folded, rest: rest.trim().to_string() })
+}
+
+fn is_line_end(event: &Event) -> bool {
+    matches!(event, Event::SoftBreak | Event::HardBreak | Event::End(Tag::Paragraph))
+}
+
+/// 첫 줄이 `[!KIND]`로 시작하는 인용문을 콜아웃 상자로 바꿉니다.
+fn callouts(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut output = Vec::with_capacity(events.len());
+    // 열려 있는 인용문마다 닫는 태그 (콜아웃이 아니면 None)
+    let mut quotes: Vec<Option<&'static str>> = Vec::new();
+    let mut i = 0;
+    while i < events.len() {
+        match &events[i] {
+            Event::Start(Tag::BlockQuote) => {
+                match callout_at(&events, i) {
+                    Some((marker, title_end)) => {
+                        let (open, close, title_tag) = match marker.folded {
+                            Some(folded) => (
+                                format!(
+                                    "<details class=\"{} {}\"{}>",
+                                    CALLOUT_CLASS,
+                                    marker.class,
+                                    if folded { "" } else { " open" }
+                                ),
+                                "</details>",
+                                "summary",
+                            ),
+                            None => (format!("<div class=\"{} {}\">", CALLOUT_CLASS, marker.class), "</div>", "p"),
+                        };
+                        quotes.push(Some(close));
+                        output.push(Event::Html(format!("{}<{} class=\"{}\">", open, title_tag, CALLOUT_TITLE_CLASS).into()));
+
+                        // 제목: 표시 뒤의 글자와 같은 줄의 나머지 인라인 요소
+                        let mut title_start = i + 2;
+                        while matches!(events.get(title_start), Some(Event::Text(_))) {
+                            title_start += 1;
+                        }
+                        let has_inline_title = title_start < title_end;
+                        if !marker.rest.is_empty() {
+                            output.push(Event::Text(marker.rest.into()));
+                        } else if !has_inline_title {
+                            output.push(Event::Text(marker.default_title.into()));
+                        }
+                        output.extend(events[title_start..title_end].iter().cloned());
+                        output.push(Event::Html(format!("</{}>", title_tag).into()));
+
+                        // 첫 줄만 있던 문단은 통째로 건너뛰고, 줄이 더 있으면 문단을 다시 엽니다.
+                        i = match events[title_end] {
+                            Event::End(Tag::Paragraph) => title_end + 1,
+                            _ => {
+                                output.push(Event::Start(Tag::Paragraph));
+                                title_end + 1
+                            }
+                        };
+                        continue;
+                    }
+                    None => {
+                        quotes.push(None);
+                        output.push(events[i].clone());
+                    }
+                }
+            }
+            Event::End(Tag::BlockQuote) => match quotes.pop().flatten() {
+                Some(close) => output.push(Event::Html(close.into())),
+                None => output.push(events[i].clone()),
+            },
+            event => output.push(event.clone()),
+        }
+        i += 1;
+    }
+    output
+}
+
+/// `events[start]`의 인용문이 콜아웃이면 표시와 첫 줄 끝 위치를 돌려줍니다.
+fn callout_at<'a>(events: &[Event<'a>], start: usize) -> Option<(Marker, usize)> {
+    if !matches!(events.get(start + 1), Some(Event::Start(Tag::Paragraph))) {
+        return None;
+    }
+    // 파서가 `[`, `]`에서 글자를 나누므로 이어지는 Text를 합쳐서 봅니다.
+    let mut text = String::new();
+    let mut end = start + 2;
+    while let Some(Event::Text(part)) = events.get(end) {
+        text.push_str(part);
+        end += 1;
+    }
+    let marker = parse_marker(&text)?;
+    let line_end = (end..events.len()).find(|&j| is_line_end(&events[j]))?;
+    Some((marker, line_end))
+}
+
+/// `==` 후보 하나 (출력 위치, 열 수 있는지, 닫을 수 있는지)
+struct Delimiter {
+    index: usize,
+    can_open: bool,
+    can_close: bool,
+}
+
+/// 글자 안의 `==형광펜==`과 원문자 번호를 바꿉니다. 코드와 이미지 대체 텍스트는 건드리지 않습니다.
+fn inline_marks(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut output: Vec<Event> = Vec::with_capacity(events.len());
+    let mut delimiters: Vec<Delimiter> = Vec::new();
+    let mut code_depth = 0usize;
+    let mut image_depth = 0usize;
+
+    for event in events {
+        match event {
+            Event::Text(text) if code_depth == 0 && image_depth == 0 => {
+                split_text(&text, &mut output, &mut delimiters);
+            }
+            event => {
+                match &event {
+                    Event::Start(Tag::CodeBlock(_)) => code_depth += 1,
+                    Event::End(Tag::CodeBlock(_)) => code_depth = code_depth.saturating_sub(1),
+                    Event::Start(Tag::Image(..)) => image_depth += 1,
+                    Event::End(Tag::Image(..)) => image_depth = image_depth.saturating_sub(1),
+                    _ => {}
+                }
+                // 형광펜은 한 블록(문단, 제목, 표 칸 등) 안에서만 짝을 짓습니다.
+                if !is_inline(&event) {
+                    pair_delimiters(&mut output, &mut delimiters);
+                }
+                output.push(event);
+            }
+        }
+    }
+    pair_delimiters(&mut output, &mut delimiters);
+    output
+}
+
+fn is_inline(event: &Event) -> bool {
+    match event {
+        Event::Start(tag) | Event::End(tag) => matches!(
+            tag,
+            Tag::Emphasis | Tag::Strong | Tag::Strikethrough | Tag::Link(..) | Tag::Image(..)
+        ),
+        Event::Html(html) => !BLOCK_HTML_PREFIXES.iter().any(|prefix| html.trim_start().starts_with(prefix)),
+        _ => true,
+    }
+}
+
+/// 블록 경계로 보는 HTML (콜아웃 상자, 강조된 코드 블록, 직접 쓴 블록 태그)
+const BLOCK_HTML_PREFIXES: [&str; 10] =
+    ["<div", "</div", "<details", "</details", "<summary", "</summary", "<p", "</p", "<pre", "</pre"];
+
+/// 글자를 일반 글자, `==` 후보, 원문자 번호로 나눠 `output`에 넣습니다.
+fn split_text<'a>(text: &str, output: &mut Vec<Event<'a>>, delimiters: &mut Vec<Delimiter>) {
+    let mut plain = String::new();
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with("==") && !rest.starts_with("===") && previous != Some('=') {
+            let next = rest[2..].chars().next();
+            flush(&mut plain, output);
+            delimiters.push(Delimiter {
+                index: output.len(),
+                // `x==y` 같은 비교식은 형광펜으로 보지 않습니다.
+                can_open: next.is_none_or(|c| !c.is_whitespace())
+                    && !previous.is_some_and(|c| c.is_ascii_alphanumeric()),
+                can_close: previous.is_none_or(|c| !c.is_whitespace())
+                    && !next.is_some_and(|c| c.is_ascii_alphanumeric()),
+            });
+            output.push(Event::Text("==".into()));
+            previous = Some('=');
+            rest = &rest[2..];
+            continue;
+        }
+        if is_circled_number(c) {
+            flush(&mut plain, output);
+            output.push(Event::Html(
+                format!("<span class=\"{}\">{}</span>", CIRCLED_NUMBER_CLASS, c).into(),
+            ));
+            previous = Some(c);
+            rest = &rest[c.len_utf8()..];
+            continue;
+        }
+        plain.push(c);
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    flush(&mut plain, output);
+}
+
+fn flush<'a>(plain: &mut String, output: &mut Vec<Event<'a>>) {
+    if !plain.is_empty() {
+        output.push(Event::Text(CowStr::from(std::mem::take(plain))));
+    }
+}
+
+/// 보기 번호와 같은 모양으로 표시할 원문자(①~⑩)인지
+fn is_circled_number(c: char) -> bool {
+    ('\u{2460}'..='\u{2469}').contains(&c)
+}
+
+/// 블록이 끝날 때 `==` 후보를 앞에서부터 짝지어 `<mark>`로 바꾸고, 남은 것은 글자로 둡니다.
+fn pair_delimiters(output: &mut [Event], delimiters: &mut Vec<Delimiter>) {
+    let mut open: Option<usize> = None;
+    for delimiter in delimiters.drain(..) {
+        match open {
+            Some(start) if delimiter.can_close && delimiter.index > start + 1 => {
+                output[start] = Event::Html("<mark>".into());
+                output[delimiter.index] = Event::Html("</mark>".into());
+                open = None;
+            }
+            _ if delimiter.can_open => open = Some(delimiter.index),
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,394 @@
+// 코드 블록 구문 강조
+//
+// 자격증 문제에 자주 나오는 언어(C, Java, Python, SQL, 셸)만 가벼운 토큰 규칙으로 색을 입힙니다.
+// 펜스 정보 문자열의 첫 단어가 언어이고, `{2,4-5}`처럼 줄 번호를 적으면 그 줄을 표시합니다.
+//
+// ```sql {2}
+// SELECT name
+//   FROM certificates
+// ```
+//
+// 줄 번호는 CSS 카운터로 붙이므로(index.html, 시험지 CSS) HTML에는 줄마다 span만 있습니다.
+// 색은 index.html(앱 화면), 시험지 CSS, Anki 카드 CSS에서 각각 정합니다.
+
+use pulldown_cmark::{CodeBlockKind, Event, Tag};
+use std::ops::Range;
+
+/// 토큰 종류별 CSS 클래스. HTML 정리기 허용 목록과 스타일시트가 이 이름을 씁니다.
+pub const TOKEN_CLASSES: [&str; 8] = [
+    "hl-keyword",
+    "hl-type",
+    "hl-literal",
+    "hl-string",
+    "hl-number",
+    "hl-comment",
+    "hl-function",
+    "hl-meta",
+];
+pub const CODE_CLASS: &str = "hl-code";
+pub const LINE_CLASS: &str = "hl-line";
+pub const MARKED_LINE_CLASS: &str = "hl-marked";
+
+struct Language {
+    keywords: &'static [&'static str],
+    types: &'static [&'static str],
+    literals: &'static [&'static str],
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Python의 `"""` 여러 줄 문자열
+    triple_quotes: bool,
+    /// SQL처럼 키워드 대소문자를 구분하지 않는 언어
+    case_insensitive: bool,
+    /// 줄 맨 앞의 이 문자로 시작하는 줄 전체를 전처리기로 표시 (C의 `#include`)
+    line_meta: Option<char>,
+    /// 이 문자로 시작하는 단어를 메타로 표시 (Python 데코레이터 `@`, 셸 변수 `$`)
+    word_meta: Option<char>,
+    /// 대문자로 시작하는 식별자를 타입으로 봅니다. (Java 클래스 이름)
+    capitalized_types: bool,
+}
+
+const C: Language = Language {
+    keywords: &[
+        "break", "case", "const", "continue", "default", "do", "else", "enum", "extern", "for", "goto", "if",
+        "inline", "register", "restrict", "return", "sizeof", "static", "struct", "switch", "typedef", "union",
+        "volatile", "while", "class", "namespace", "template", "typename", "public", "private", "protected",
+        "virtual", "new", "delete", "using", "try", "catch", "throw",
+    ],
+    types: &[
+        "void", "char", "short", "int", "long", "float", "double", "signed", "unsigned", "bool", "size_t",
+        "FILE", "auto",
+    ],
+    literals: &["NULL", "true", "false", "nullptr"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    case_insensitive: false,
+    line_meta: Some('#'),
+    word_meta: None,
+    capitalized_types: false,
+};
+
+const JAVA: Language = Language {
+    keywords: &[
+        "abstract", "assert", "break", "case", "catch", "class", "continue", "default", "do", "else", "enum",
+        "extends", "final", "finally", "for", "if", "implements", "import", "instanceof", "interface", "native",
+        "new", "package", "private", "protected", "public", "return", "static", "super", "switch",
+        "synchronized", "this", "throw", "throws", "transient", "try", "volatile", "while", "var", "record",
+    ],
+    types: &["boolean", "byte", "char", "double", "float", "int", "long", "short", "void"],
+    literals: &["true", "false", "null"],
+    line_comments: &["//"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    case_insensitive: false,
+    line_meta: None,
+    word_meta: Some('@'),
+    capitalized_types: true,
+};
+
+const PYTHON: Language = Language {
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else",
+        "except", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "nonlocal", "not",
+        "or", "pass", "raise", "return", "try", "while", "with", "yield", "match", "case",
+    ],
+    types: &[
+        "int", "float", "str", "bool", "list", "dict", "set", "tuple", "bytes", "object", "type", "self", "cls",
+    ],
+    literals: &["True", "False", "None"],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    triple_quotes: true,
+    case_insensitive: false,
+    line_meta: None,
+    word_meta: Some('@'),
+    capitalized_types: false,
+};
+
+const SQL: Language = Language {
+    keywords: &[
+        "select", "from", "where", "and", "or", "not", "insert", "into", "values", "update", "set", "delete",
+        "create", "table", "drop", "alter", "add", "column", "primary", "key", "foreign", "references", "index",
+        "view", "join", "inner", "left", "right", "full", "outer", "cross", "natural", "on", "using", "as",
+        "group", "by", "order", "having", "distinct", "union", "all", "except", "intersect", "minus", "limit",
+        "offset", "fetch", "first", "rows", "only", "case", "when", "then", "else", "end", "is", "in",
+        "between", "like", "exists", "any", "some", "default", "constraint", "unique", "check", "cascade",
+        "grant", "revoke", "commit", "rollback", "savepoint", "begin", "transaction", "with", "recursive",
+        "asc", "desc", "truncate", "rename", "to", "over", "partition",
+    ],
+    types: &[
+        "int", "integer", "smallint", "bigint", "varchar", "varchar2", "char", "text", "date", "time",
+        "timestamp", "numeric", "number", "decimal", "float", "real", "boolean", "blob", "clob",
+    ],
+    literals: &["null", "true", "false"],
+    line_comments: &["--"],
+    block_comment: Some(("/*", "*/")),
+    quotes: &['\''],
+    triple_quotes: false,
+    case_insensitive: true,
+    line_meta: None,
+    word_meta: None,
+    capitalized_types: false,
+};
+
+const SHELL: Language = Language {
+    keywords: &[
+        "if", "then", "else", "elif", "fi", "for", "while", "until", "do", "done", "case", "esac", "in",
+        "function", "return", "exit", "export", "local", "readonly", "unset", "shift", "source",
+    ],
+    types: &[
+        "echo", "printf", "read", "cd", "pwd", "ls", "cat", "grep", "sed", "awk", "find", "chmod", "chown",
+        "mkdir", "rm", "cp", "mv", "kill", "ps", "sudo", "test",
+    ],
+    literals: &["true", "false"],
+    line_comments: &["#"],
+    block_comment: None,
+    quotes: &['"', '\''],
+    triple_quotes: false,
+    case_insensitive: false,
+    line_meta: None,
+    word_meta: Some('$'),
+    capitalized_types: false,
+};
+
+impl Language {
+    fn find(name: &str) -> Option<&'static Language> {
+        match name.to_ascii_lowercase().as_str() {
+            "c" | "h" | "cpp" | "c++" | "cc" => Some(&C),
+            "java" => Some(&JAVA),
+            "python" | "py" | "python3" => Some(&PYTHON),
+            "sql" | "mysql" | "postgresql" | "postgres" | "plsql" | "sqlite" | "oracle" => Some(&SQL),
+            "sh" | "bash" | "shell" | "zsh" => Some(&SHELL),
+            _ => None,
+        }
+    }
+
+    fn classify(&self, word: &str) -> Option<&'static str> {
+        let matches = |list: &[&str]| {
+            if self.case_insensitive {
+                list.iter().any(|w| w.eq_ignore_ascii_case(word))
+            } else {
+                list.contains(&word)
+            }
+        };
+        if matches(self.keywords) {
+            Some("hl-keyword")
+        } else if matches(self.types) {
+            Some("hl-type")
+        } else if matches(self.literals) {
+            Some("hl-literal")
+        } else if self.capitalized_types && word.starts_with(|c: char| c.is_ascii_uppercase()) {
+            Some("hl-type")
+        } else {
+            None
+        }
+    }
+}
+
+/// 펜스 정보 문자열 (`python {1,3-4}`)
+struct FenceInfo {
+    language: String,
+    marked: Vec<(usize, usize)>,
+}
+
+impl FenceInfo {
+    fn parse(info: &str) -> Self {
+        let (head, marked) = match (info.find('{'), info.rfind('}')) {
+            (Some(start), Some(end)) if start < end => (&info[..start], Self::parse_ranges(&info[start + 1..end])),
+            _ => (info, Vec::new()),
+        };
+        let language = head.split_whitespace().next().unwrap_or_default().to_string();
+        Self { language, marked }
+    }
+
+    fn parse_ranges(list: &str) -> Vec<(usize, usize)> {
+        list.split(',')
+            .filter_map(|part| {
+                let part = part.trim();
+                match part.split_once('-') {
+                    Some((from, to)) => Some((from.trim().parse().ok()?, to.trim().parse().ok()?)),
+                    None => part.parse().ok().map(|line| (line, line)),
+                }
+            })
+            .collect()
+    }
+
+    fn is_marked(&self, line: usize) -> bool {
+        self.marked.iter().any(|(from, to)| (*from..=*to).contains(&line))
+    }
+}
+
+/// 펜스 코드 블록을 강조된 HTML 이벤트 하나로 바꿉니다. 들여쓰기 코드 블록은 그대로 둡니다.
+pub fn highlight_code_blocks<'a>(events: impl Iterator<Item = Event<'a>>) -> Vec<Event<'a>> {
+    let mut output = Vec::new();
+    let mut fence: Option<(FenceInfo, String)> = None;
+    for event in events {
+        match (&mut fence, event) {
+            (None, Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info)))) => {
+                fence = Some((FenceInfo::parse(&info), String::new()));
+            }
+            (Some((_, code)), Event::Text(text)) => code.push_str(&text),
+            (Some(_), Event::End(Tag::CodeBlock(_))) => {
+                if let Some((info, code)) = fence.take() {
+                    output.push(Event::Html(render_block(&info, &code).into()));
+                }
+            }
+            (_, event) => output.push(event),
+        }
+    }
+    output
+}
+
+fn render_block(info: &FenceInfo, code: &str) -> String {
+    let tokens = match Language::find(&info.language) {
+        Some(language) => tokenize(language, code),
+        None => vec![(None, code)],
+    };
+
+    let mut html = format!("<pre class=\"{}\"><code>", CODE_CLASS);
+    let mut line = 1;
+    let mut line_open = false;
+    for (class, text) in tokens {
+        for (index, part) in text.split('\n').enumerate() {
+            if index > 0 {
+                if !line_open {
+                    open_line(&mut html, info, line);
+                }
+                // 줄 끝의 줄바꿈은 줄 span 안에 두어 복사할 때 그대로 남게 합니다.
+                html.push_str("\n</span>");
+                line_open = false;
+                line += 1;
+            }
+            if part.is_empty() {
+                continue;
+            }
+            if !line_open {
+                open_line(&mut html, info, line);
+                line_open = true;
+            }
+            match class {
+                Some(class) => html.push_str(&format!("<span class=\"{}\">{}</span>", class, escape(part))),
+                None => html.push_str(&escape(part)),
+            }
+        }
+    }
+    if line_open {
+        html.push_str("</span>");
+    }
+    html.push_str("</code></pre>\n");
+    html
+}
+
+fn open_line(html: &mut String, info: &FenceInfo, line: usize) {
+    if info.is_marked(line) {
+        html.push_str(&format!("<span class=\"{} {}\">", LINE_CLASS, MARKED_LINE_CLASS));
+    } else {
+        html.push_str(&format!("<span class=\"{}\">", LINE_CLASS));
+    }
+}
+
+/// 코드를 (클래스, 조각) 목록으로 나눕니다. 클래스가 없는 조각은 그대로 출력합니다.
+fn tokenize<'c>(language: &Language, code: &'c str) -> Vec<(Option<&'static str>, &'c str)> {
+    let mut tokens: Vec<(Option<&'static str>, Range<usize>)> = Vec::new();
+    let mut push = |class: Option<&'static str>, range: Range<usize>| match tokens.last_mut() {
+        // 클래스 없는 조각은 이어 붙여서 span 수를 줄입니다.
+        Some((None, last)) if class.is_none() && last.end == range.start => last.end = range.end,
+        _ if range.is_empty() => {}
+        _ => tokens.push((class, range)),
+    };
+
+    let mut i = 0;
+    let mut line_start = true;
+    while i < code.len() {
+        let rest = &code[i..];
+        let c = rest.chars().next().unwrap_or_default();
+        let line_end = rest.find('\n').map_or(code.len(), |n| i + n);
+
+        if language.line_comments.iter().any(|prefix| rest.starts_with(prefix)) {
+            push(Some("hl-comment"), i..line_end);
+            i = line_end;
+            continue;
+        }
+        if let Some((open, close)) = language.block_comment.filter(|(open, _)| rest.starts_with(open)) {
+            let end = rest[open.len()..].find(close).map_or(code.len(), |n| i + open.len() + n + close.len());
+            push(Some("hl-comment"), i..end);
+            i = end;
+            continue;
+        }
+        if line_start && language.line_meta == Some(c) {
+            push(Some("hl-meta"), i..line_end);
+            i = line_end;
+            continue;
+        }
+        line_start = c == '\n' || (line_start && c.is_whitespace());
+
+        if language.quotes.contains(&c) {
+            let end = i + string_len(rest, c, language.triple_quotes);
+            push(Some("hl-string"), i..end);
+            i = end;
+            continue;
+        }
+        if c.is_ascii_digit() {
+            let len = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '.' || ch == '_'))
+                .unwrap_or(rest.len());
+            push(Some("hl-number"), i..i + len);
+            i += len;
+            continue;
+        }
+        if language.word_meta == Some(c) {
+            let word = rest[1..].find(|ch: char| !is_word_char(ch)).map_or(rest.len(), |n| n + 1);
+            // `${NAME}` 형태의 셸 변수
+            let len = if word == 1 && rest[1..].starts_with('{') {
+                rest.find('}').map_or(1, |n| n + 1)
+            } else {
+                word
+            };
+            push((len > 1).then_some("hl-meta"), i..i + len);
+            i += len;
+            continue;
+        }
+        if c.is_alphabetic() || c == '_' {
+            let len = rest.find(|ch: char| !is_word_char(ch)).unwrap_or(rest.len());
+            let class = language.classify(&rest[..len]).or_else(|| {
+                rest[len..].trim_start_matches([' ', '\t']).starts_with('(').then_some("hl-function")
+            });
+            push(class, i..i + len);
+            i += len;
+            continue;
+        }
+        push(None, i..i + c.len_utf8());
+        i += c.len_utf8();
+    }
+    tokens.into_iter().map(|(class, range)| (class, &code[range])).collect()
+}
+
+/// 여는 따옴표부터 닫는 따옴표까지의 바이트 길이. Python의 삼중 따옴표는 여러 줄에 걸칩니다.
+fn string_len(rest: &str, quote: char, triple_quotes: bool) -> usize {
+    let triple: String = std::iter::repeat_n(quote, 3).collect();
+    if triple_quotes && rest.starts_with(&triple) {
+        return rest[3..].find(&triple).map_or(rest.len(), |n| n + 6);
+    }
+    let mut escaped = false;
+    for (offset, ch) in rest.char_indices().skip(1) {
+        match ch {
+            '\n' => return offset,
+            '\\' if !escaped => escaped = true,
+            ch if ch == quote && !escaped => return offset + 1,
+            _ => escaped = false,
+        }
+    }
+    rest.len()
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
@@ -1,11 +1,11 @@
 use yew::prelude::*;
 use yew_router::prelude::*;
 use crate::routes::Route;
-use crate::services::AuthService;
+use crate::components::auth::use_user;
 
 #[function_component(Home)]
 pub fn home() -> Html {
-    let user = AuthService::get_current_user();
+    let user = use_user();
 
     html! {
         <div class="px-4 py-5 sm:p-6">
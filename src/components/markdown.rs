@@ -1,39 +1,296 @@
+use ammonia::Builder;
+use gloo::storage::{LocalStorage, Storage};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{html, Event, Options, Parser};
+use serde::{Deserialize, Serialize};
+use lru::LruCache;
+use std::borrow::Cow;
+use std::cell::{Cell, RefCell};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::num::NonZeroUsize;
+use std::rc::Rc;
 use yew::prelude::*;
-use web_sys::Element;
-use pulldown_cmark::{Parser, html, Options};
+
+use crate::components::{exam_syntax, highlight};
+use crate::components::math::MathFormulas;
+use crate::models::Certificate;
+
+/// 마크다운 원문의 출처. 출처마다 원시 HTML을 허용할지 따로 정합니다.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ContentSource {
+    /// 사용자가 직접 작성한 문제
+    #[default]
+    Own,
+    /// 파일 가져오기나 공유로 들어온 문제
+    Imported,
+}
+
+impl ContentSource {
+    pub fn of(certificate: &Certificate) -> Self {
+        if certificate.imported {
+            Self::Imported
+        } else {
+            Self::Own
+        }
+    }
+}
+
+/// 출처별 원시 HTML 허용 설정. 허용해도 아래 허용 목록 밖의 태그·속성·URL은 모두 제거됩니다.
+/// 허용하지 않으면 원시 HTML은 글자 그대로 보입니다.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RawHtmlSettings {
+    pub own: bool,
+    pub imported: bool,
+}
+
+impl Default for RawHtmlSettings {
+    fn default() -> Self {
+        Self { own: true, imported: false }
+    }
+}
+
+thread_local! {
+    /// LocalStorage에서 한 번 읽어 둔 설정. 렌더링할 때마다 읽고 파싱하지 않도록 둡니다.
+    static CURRENT_RAW_HTML_SETTINGS: Cell<Option<RawHtmlSettings>> = const { Cell::new(None) };
+}
+
+impl RawHtmlSettings {
+    const STORAGE_KEY: &'static str = "quiznote-raw-html";
+
+    pub fn load() -> Self {
+        CURRENT_RAW_HTML_SETTINGS.with(|current| {
+            current.get().unwrap_or_else(|| {
+                let settings = LocalStorage::get(Self::STORAGE_KEY).unwrap_or_default();
+                current.set(Some(settings));
+                settings
+            })
+        })
+    }
+
+    pub fn save(&self) {
+        let _ = LocalStorage::set(Self::STORAGE_KEY, self);
+        CURRENT_RAW_HTML_SETTINGS.with(|current| current.set(Some(*self)));
+    }
+
+    pub fn allows(&self, source: ContentSource) -> bool {
+        match source {
+            ContentSource::Own => self.own,
+            ContentSource::Imported => self.imported,
+        }
+    }
+}
+
+/// 원시 HTML 설정 컨텍스트. 설정을 바꾸면 화면에 떠 있는 `Markdown`이 바로 다시 그려집니다.
+#[derive(Clone, PartialEq)]
+pub struct RawHtmlContext {
+    pub settings: RawHtmlSettings,
+    /// 설정을 저장하고 컨텍스트를 바꿉니다.
+    pub update: Callback<RawHtmlSettings>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct RawHtmlProviderProps {
+    pub children: Children,
+}
+
+#[function_component(RawHtmlProvider)]
+pub fn raw_html_provider(props: &RawHtmlProviderProps) -> Html {
+    let settings = use_state_eq(RawHtmlSettings::load);
+    let update = {
+        let settings = settings.clone();
+        Callback::from(move |next: RawHtmlSettings| {
+            next.save();
+            settings.set(next);
+        })
+    };
+    let context = RawHtmlContext { settings: *settings, update };
+
+    html! {
+        <ContextProvider<RawHtmlContext> {context}>
+            {props.children.clone()}
+        </ContextProvider<RawHtmlContext>>
+    }
+}
+
+/// 각주 앵커가 앱의 다른 요소 ID와 겹치지 않도록 붙이는 접두어
+const FOOTNOTE_ID_PREFIX: &str = "fn-";
+
+/// pulldown-cmark 출력과 문제 작성에 흔히 쓰는 태그만 남기는 HTML 정리기
+static SANITIZER: Lazy<Builder<'static>> = Lazy::new(|| {
+    let mut builder = Builder::empty();
+    builder
+        .tags(HashSet::from([
+            "p", "br", "hr", "h1", "h2", "h3", "h4", "h5", "h6", "strong", "b", "em", "i", "u", "del", "s",
+            "sub", "sup", "mark", "small", "kbd", "code", "pre", "blockquote", "ul", "ol", "li", "dl", "dt",
+            "dd", "table", "thead", "tbody", "tr", "th", "td", "a", "img", "input", "div", "span", "details",
+            "summary",
+        ]))
+        .tag_attributes(HashMap::from([
+            ("a", HashSet::from(["href", "title"])),
+            ("img", HashSet::from(["src", "alt", "title", "width", "height"])),
+            ("ol", HashSet::from(["start"])),
+            ("th", HashSet::from(["style"])),
+            ("td", HashSet::from(["style"])),
+            ("input", HashSet::from(["checked", "disabled"])),
+            ("div", HashSet::from(["id"])),
+            ("details", HashSet::from(["open"])),
+        ]))
+        // 작업 목록(- [ ])의 체크박스만 허용합니다.
+        .set_tag_attribute_values(HashMap::from([("input", HashMap::from([("type", "checkbox")]))]))
+        .allowed_classes(HashMap::from([
+            ("sup", HashSet::from(["footnote-reference", "footnote-definition-label"])),
+            (
+                "div",
+                ["footnote-definition", exam_syntax::CALLOUT_CLASS]
+                    .into_iter()
+                    .chain(exam_syntax::callout_kind_classes())
+                    .collect(),
+            ),
+            (
+                "details",
+                std::iter::once(exam_syntax::CALLOUT_CLASS)
+                    .chain(exam_syntax::callout_kind_classes())
+                    .collect(),
+            ),
+            ("p", HashSet::from([exam_syntax::CALLOUT_TITLE_CLASS])),
+            ("summary", HashSet::from([exam_syntax::CALLOUT_TITLE_CLASS])),
+            ("pre", HashSet::from([highlight::CODE_CLASS])),
+            (
+                "span",
+                highlight::TOKEN_CLASSES
+                    .into_iter()
+                    .chain([highlight::LINE_CLASS, highlight::MARKED_LINE_CLASS, exam_syntax::CIRCLED_NUMBER_CLASS])
+                    .collect(),
+            ),
+        ]))
+        .generic_attributes(HashSet::new())
+        .filter_style_properties(HashSet::from(["text-align"]))
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .link_rel(Some("noopener noreferrer nofollow"))
+        .id_prefix(Some(FOOTNOTE_ID_PREFIX))
+        .attribute_filter(|element, attribute, value| {
+            // 각주 ID에 접두어가 붙으므로 각주로 가는 링크도 맞춰 줍니다.
+            if element == "a" && attribute == "href" {
+                if let Some(anchor) = value.strip_prefix('#') {
+                    return Some(Cow::Owned(format!("#{}{}", FOOTNOTE_ID_PREFIX, anchor)));
+                }
+            }
+            Some(Cow::Borrowed(value))
+        });
+    builder
+});
+
+/// 렌더링 결과 캐시 크기. 퀴즈 한 화면(문제·보기·해설)과 문제 목록 미리보기를 넉넉히 담습니다.
+const RENDER_CACHE_SIZE: usize = 512;
+
+struct CachedRender {
+    /// 해시가 겹쳤을 때 다른 내용을 돌려주지 않도록 원문도 함께 둡니다.
+    content: String,
+    html: Rc<str>,
+}
+
+thread_local! {
+    /// 모든 Markdown 컴포넌트가 함께 쓰는 LRU 캐시 (키: 원문 + 원시 HTML 허용 여부의 해시)
+    static RENDER_CACHE: RefCell<LruCache<u64, CachedRender>> =
+        RefCell::new(LruCache::new(NonZeroUsize::new(RENDER_CACHE_SIZE).unwrap()));
+}
 
 #[derive(Properties, PartialEq)]
 pub struct MarkdownProps {
     pub content: String,
+    #[prop_or_default]
+    pub source: ContentSource,
 }
 
-#[function_component(Markdown)]
-pub fn markdown(props: &MarkdownProps) -> Html {
-    // 1. div 요소 생성
-    let div = web_sys::window()
-        .unwrap()
-        .document()
-        .unwrap()
-        .create_element("div")
-        .unwrap();
-
-    // 2. 마크다운 옵션 설정
+/// 앱 전체에서 공통으로 사용하는 마크다운 옵션
+pub fn markdown_options() -> Options {
     let mut options = Options::empty();
     options.insert(Options::ENABLE_TABLES);
     options.insert(Options::ENABLE_FOOTNOTES);
     options.insert(Options::ENABLE_STRIKETHROUGH);
     options.insert(Options::ENABLE_TASKLISTS);
+    options
+}
+
+/// 마크다운을 정리된 HTML 문자열로 변환합니다. (내보내기 등 DOM 밖에서도 사용)
+/// 원시 HTML 허용 여부는 출처별 설정을 따릅니다. 같은 내용은 캐시에서 꺼내 씁니다.
+pub fn render_html(content: &str, source: ContentSource) -> String {
+    render_cached(content, RawHtmlSettings::load().allows(source))
+}
+
+fn render_cached(content: &str, allow_raw_html: bool) -> String {
+    let key = cache_key(content, allow_raw_html);
+
+    let cached = RENDER_CACHE.with(|cache| {
+        cache
+            .borrow_mut()
+            .get(&key)
+            .filter(|entry| entry.content == content)
+            .map(|entry| entry.html.clone())
+    });
+    if let Some(html) = cached {
+        return html.to_string();
+    }
 
-    // 3. HTML 변환
-    let parser = Parser::new_ext(&props.content, options);
+    let html: Rc<str> = render_html_with(content, allow_raw_html).into();
+    RENDER_CACHE.with(|cache| {
+        cache.borrow_mut().put(key, CachedRender { content: content.to_string(), html: html.clone() });
+    });
+    html.to_string()
+}
+
+fn cache_key(content: &str, allow_raw_html: bool) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    allow_raw_html.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// `allow_raw_html`이 false이면 원시 HTML을 글자로 바꿔 보여줍니다.
+/// 어느 쪽이든 결과는 허용 목록으로 한 번 더 정리하고, 수식 MathML은 정리한 뒤에 넣습니다.
+/// 콜아웃·형광펜·원문자 번호 같은 확장 문법은 `exam_syntax`가 처리합니다.
+pub fn render_html_with(content: &str, allow_raw_html: bool) -> String {
+    let (content, math) = MathFormulas::extract(content);
+    let events = math
+        .restore_non_text(Parser::new_ext(&content, markdown_options()))
+        .map(|event| match event {
+            Event::Html(raw) if !allow_raw_html => Event::Text(raw),
+            event => event,
+        });
+    let events = exam_syntax::apply(highlight::highlight_code_blocks(events));
     let mut html_output = String::new();
-    html::push_html(&mut html_output, parser);
+    html::push_html(&mut html_output, events.into_iter());
+    math.render_into(&SANITIZER.clean(&html_output).to_string())
+}
+
+#[function_component(Markdown)]
+pub fn markdown(props: &MarkdownProps) -> Html {
+    let settings = use_context::<RawHtmlContext>().map_or_else(RawHtmlSettings::load, |context| context.settings);
+    let allow_raw_html = settings.allows(props.source);
+
+    // 내용과 원시 HTML 허용 여부가 바뀌지 않았으면 다시 파싱하지 않고 같은 요소를 그대로 씁니다.
+    let node = use_memo((props.content.clone(), allow_raw_html), |(content, allow_raw_html)| {
+        // 1. div 요소 생성
+        let div = web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("div")
+            .unwrap();
 
-    // 4. HTML 주입 및 Tailwind Typography 클래스 적용
-    div.set_inner_html(&html_output);
-    // 'prose' 클래스가 마크다운 스타일을 자동으로 적용해줍니다.
-    let _ = div.set_class_name("prose prose-sm max-w-none break-words");
+        // 2. HTML 변환 (허용 목록 밖의 태그·속성은 여기서 제거됩니다)
+        let html_output = render_cached(content, *allow_raw_html);
 
-    // 5. VRef로 반환
-    Html::VRef(div.into())
-}
\ No newline at end of file
+        // 3. HTML 주입 및 Tailwind Typography 클래스 적용
+        div.set_inner_html(&html_output);
+        // 'prose' 클래스가 마크다운 스타일을 자동으로 적용해줍니다.
+        div.set_class_name("prose prose-sm max-w-none break-words");
+
+        web_sys::Node::from(div)
+    });
+
+    // 4. VRef로 반환
+    Html::VRef((*node).clone())
+}
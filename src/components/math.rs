@@ -0,0 +1,256 @@
+// LaTeX 수식 렌더링 (`$...$`, `$$...$$`)
+//
+// 마크다운 파서는 수식 안의 `_`, `*`를 강조로 해석해 버리므로, 파싱 전에 수식을 자리표시자로
+// 바꿔 두었다가 HTML 정리까지 끝난 뒤 MathML로 바꿔 넣습니다. 변환은 math-core(순수 Rust)가
+// 하므로 런타임에 CDN이나 JS가 필요 없습니다.
+
+use math_core::{LatexToMathML, MathCoreConfig, MathDisplay};
+use once_cell::sync::Lazy;
+use pulldown_cmark::{Event, Parser, Tag};
+use std::ops::Range;
+
+use crate::components::markdown::markdown_options;
+
+/// 자리표시자 앞뒤에 붙이는 사용자 영역 문자. 사이에 수식 번호가 들어갑니다.
+const OPEN: char = '\u{E000}';
+const CLOSE: char = '\u{E001}';
+
+static CONVERTER: Lazy<LatexToMathML> = Lazy::new(|| {
+    LatexToMathML::new(MathCoreConfig::default()).expect("기본 설정에는 사용자 매크로가 없습니다")
+});
+
+struct Formula {
+    /// 구분자(`$`, `$$`)를 포함한 원문
+    source: String,
+    latex: String,
+    display: MathDisplay,
+}
+
+/// 본문에서 꺼낸 수식 목록
+pub struct MathFormulas {
+    formulas: Vec<Formula>,
+}
+
+impl MathFormulas {
+    /// 코드 블록과 인라인 코드 밖의 수식을 자리표시자로 바꾼 본문과 수식 목록을 돌려줍니다.
+    /// `\$`는 수식 구분자로 보지 않습니다.
+    pub fn extract(content: &str) -> (String, Self) {
+        let content: String = content.chars().filter(|c| *c != OPEN && *c != CLOSE).collect();
+        let code = Self::code_ranges(&content);
+        let in_code = |at: usize| code.iter().find(|r| r.contains(&at)).map(|r| r.end);
+
+        let bytes = content.as_bytes();
+        let mut formulas = Vec::new();
+        let mut output = String::with_capacity(content.len());
+        let mut copied = 0;
+        let mut i = 0;
+        while i < bytes.len() {
+            if let Some(end) = in_code(i) {
+                i = end;
+                continue;
+            }
+            match bytes[i] {
+                b'\\' => i += 2,
+                b'$' => {
+                    let found = if bytes.get(i + 1) == Some(&b'$') {
+                        Self::find_block_end(bytes, i + 2, &in_code)
+                            .map(|end| (end + 2, &content[i + 2..end], MathDisplay::Block))
+                    } else {
+                        Self::find_inline_end(bytes, i + 1, &in_code)
+                            .map(|end| (end + 1, &content[i + 1..end], MathDisplay::Inline))
+                    };
+                    match found {
+                        Some((end, latex, display)) if !latex.trim().is_empty() => {
+                            output.push_str(&content[copied..i]);
+                            output.push(OPEN);
+                            output.push_str(&formulas.len().to_string());
+                            output.push(CLOSE);
+                            formulas.push(Formula {
+                                source: content[i..end].to_string(),
+                                latex: latex.trim().to_string(),
+                                display,
+                            });
+                            copied = end;
+                            i = end;
+                        }
+                        // `$$`가 짝이 없으면 두 글자 모두 그대로 둡니다.
+                        _ if bytes.get(i + 1) == Some(&b'$') => i += 2,
+                        _ => i += 1,
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+        output.push_str(&content[copied..]);
+        (output, Self { formulas })
+    }
+
+    /// 자리표시자를 수식 원문으로 되돌립니다. 속성값이나 이미지 대체 텍스트처럼
+    /// MathML을 넣을 수 없는 곳에 씁니다.
+    pub fn to_source(&self, text: &str) -> String {
+        self.replace(text, |formula| formula.source.clone())
+    }
+
+    /// HTML 본문의 자리표시자를 MathML로 바꿉니다. 잘못된 수식은 그 자리에 오류를 표시합니다.
+    pub fn render_into(&self, html: &str) -> String {
+        self.replace(html, Self::render)
+    }
+
+    /// 링크 주소·제목, 이미지 대체 텍스트, 각주 이름, 원시 HTML 안의 자리표시자를 원문으로 되돌립니다.
+    /// 그러면 남은 자리표시자는 모두 HTML 본문 텍스트에 있게 됩니다.
+    pub fn restore_non_text<'a, 's>(
+        &'s self,
+        events: impl Iterator<Item = Event<'a>> + 's,
+    ) -> impl Iterator<Item = Event<'a>> + 's {
+        let mut image_depth = 0usize;
+        events.map(move |event| match event {
+            Event::Start(Tag::Image(kind, url, title)) => {
+                image_depth += 1;
+                Event::Start(Tag::Image(kind, self.to_source(&url).into(), self.to_source(&title).into()))
+            }
+            Event::End(Tag::Image(kind, url, title)) => {
+                image_depth = image_depth.saturating_sub(1);
+                Event::End(Tag::Image(kind, url, title))
+            }
+            Event::Start(Tag::Link(kind, url, title)) => {
+                Event::Start(Tag::Link(kind, self.to_source(&url).into(), self.to_source(&title).into()))
+            }
+            // 각주 이름은 href와 id에 들어갑니다. 같은 수식이라도 자리표시자 번호가 다르므로
+            // 원문으로 되돌려야 참조와 정의가 같은 이름이 됩니다.
+            Event::FootnoteReference(label) => Event::FootnoteReference(self.to_source(&label).into()),
+            Event::Start(Tag::FootnoteDefinition(label)) => {
+                Event::Start(Tag::FootnoteDefinition(self.to_source(&label).into()))
+            }
+            Event::End(Tag::FootnoteDefinition(label)) => {
+                Event::End(Tag::FootnoteDefinition(self.to_source(&label).into()))
+            }
+            Event::Text(text) if image_depth > 0 => Event::Text(self.to_source(&text).into()),
+            Event::Html(html) => Event::Html(self.to_source(&html).into()),
+            event => event,
+        })
+    }
+
+    fn render(formula: &Formula) -> String {
+        match CONVERTER.convert_with_local_state(&formula.latex, formula.display) {
+            Ok(result) => result.mathml,
+            Err(error) => {
+                let (tag, class) = match formula.display {
+                    MathDisplay::Block => ("div", "my-2"),
+                    MathDisplay::Inline => ("span", "inline-block"),
+                };
+                let mut message = String::new();
+                error.to_message(&mut message, &formula.latex);
+                format!(
+                    "<{tag} class=\"{class} rounded border border-red-300 bg-red-50 px-1 text-red-700\" title=\"{title}\">\
+                     <code>{latex}</code> <small>수식 오류: {message}</small></{tag}>",
+                    title = escape(&formula.source),
+                    latex = escape(&formula.latex),
+                    message = escape(&message),
+                )
+            }
+        }
+    }
+
+    fn replace(&self, text: &str, mut render: impl FnMut(&Formula) -> String) -> String {
+        if !text.contains(OPEN) {
+            return text.to_string();
+        }
+        let mut output = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find(OPEN) {
+            output.push_str(&rest[..start]);
+            let after = &rest[start + OPEN.len_utf8()..];
+            let formula = after
+                .find(CLOSE)
+                .and_then(|end| Some((end, self.formulas.get(after[..end].parse::<usize>().ok()?)?)));
+            match formula {
+                Some((end, formula)) => {
+                    output.push_str(&render(formula));
+                    rest = &after[end + CLOSE.len_utf8()..];
+                }
+                None => rest = after,
+            }
+        }
+        output.push_str(rest);
+        output
+    }
+
+    /// 마크다운 파서로 코드 블록과 인라인 코드의 범위를 찾습니다.
+    fn code_ranges(content: &str) -> Vec<Range<usize>> {
+        let mut ranges = Vec::new();
+        for (event, range) in Parser::new_ext(content, markdown_options()).into_offset_iter() {
+            match event {
+                Event::Start(Tag::CodeBlock(_)) | Event::Code(_) => ranges.push(range),
+                _ => {}
+            }
+        }
+        ranges
+    }
+
+    /// `$$` 뒤에서 닫는 `$$`를 찾습니다. 여러 줄에 걸칠 수 있습니다.
+    fn find_block_end(bytes: &[u8], from: usize, in_code: &impl Fn(usize) -> Option<usize>) -> Option<usize> {
+        let mut j = from;
+        while j + 1 < bytes.len() {
+            if in_code(j).is_some() {
+                return None;
+            }
+            match bytes[j] {
+                b'\\' => j += 2,
+                b'$' if bytes[j + 1] == b'$' => return Some(j),
+                _ => j += 1,
+            }
+        }
+        None
+    }
+
+    /// `$` 뒤에서 닫는 `$`를 찾습니다. 금액 표기(`$5와 $10`)와 구분하기 위해 여는 `$` 뒤와
+    /// 닫는 `$` 앞은 공백이 아니어야 하고, 닫는 `$` 바로 뒤에 숫자가 오면 안 됩니다.
+    /// 다음 `$`가 닫을 수 없는 자리이거나 빈 줄을 만나면 수식이 아닌 것으로 봅니다.
+    fn find_inline_end(bytes: &[u8], from: usize, in_code: &impl Fn(usize) -> Option<usize>) -> Option<usize> {
+        if bytes.get(from).is_none_or(|b| b.is_ascii_whitespace()) {
+            return None;
+        }
+        let mut j = from;
+        while j < bytes.len() {
+            if in_code(j).is_some() {
+                return None;
+            }
+            match bytes[j] {
+                b'\\' => j += 2,
+                b'\n' if bytes[j + 1..].iter().take_while(|b| **b != b'\n').all(|b| b.is_ascii_whitespace()) => {
+                    return None;
+                }
+                // 닫을 수 없는 `$`를 만나면 여기서 포기하고, 그 `$`부터 다시 찾게 합니다.
+                b'$' => {
+                    let closes = !bytes[j - 1].is_ascii_whitespace()
+                        && !bytes.get(j + 1).is_some_and(|b| b.is_ascii_digit());
+                    return closes.then_some(j);
+                }
+                _ => j += 1,
+            }
+        }
+        None
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::components::markdown::render_html_with;
+
+    #[test]
+    fn footnote_labels_keep_formula_source() {
+        let html = render_html_with("본문[^$x$]\n\n[^$x$]: 각주 $y$", false);
+
+        assert!(html.contains(r##"href="#fn-$x$""##), "{html}");
+        assert!(html.contains(r#"id="fn-$x$""#), "{html}");
+        // 각주 본문의 수식은 그대로 MathML로 바뀝니다.
+        assert_eq!(html.matches("<math").count(), 1, "{html}");
+    }
+}
@@ -1,14 +1,23 @@
+pub mod account;
 pub mod home;
 pub mod certificate;
 pub mod question;
 pub mod quiz;
 pub mod auth;
 pub mod data;
+pub mod print;
+pub mod trash;
+pub mod exam_syntax;
+pub mod highlight;
 pub mod markdown;
+pub mod math;
 
+pub use account::AccountPage;
 pub use home::Home;
 pub use certificate::{CertificateList, CertificateForm, CertificateDetail};
 pub use question::QuestionForm;
 pub use quiz::QuizPage;
 pub use data::DataManagement;
+pub use print::ExamSheetPage;
+pub use trash::TrashPage;
 pub use markdown::Markdown;
\ No newline at end of file
@@ -0,0 +1,225 @@
+use crate::components::data::management::download_file;
+use crate::models::{Certificate, Question};
+use crate::routes::Route;
+use crate::services::exam_sheet::{ExamSections, EXAM_SHEET_CSS};
+use crate::services::{CertificateService, ExamSheet, ImageBundle, QuestionService};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+use yew_router::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct ExamSheetPageProps {
+    pub certificate_id: String,
+}
+
+fn random_seed() -> u64 {
+    // 손으로 옮겨 적기 쉬운 길이로 만듭니다.
+    rand::random::<u32>() as u64 % 1_000_000
+}
+
+#[function_component(ExamSheetPage)]
+pub fn exam_sheet_page(props: &ExamSheetPageProps) -> Html {
+    let certificate = use_state(|| None::<Certificate>);
+    let questions = use_state(Vec::<Question>::new);
+    let error = use_state(|| None::<String>);
+    let is_loading = use_state(|| true);
+
+    let is_random = use_state(|| false);
+    let count = use_state(|| 20usize);
+    let seed = use_state(random_seed);
+    let sections = use_state(ExamSections::default);
+
+    {
+        let certificate = certificate.clone();
+        let questions = questions.clone();
+        let error = error.clone();
+        let is_loading = is_loading.clone();
+
+        use_effect_with(props.certificate_id.clone(), move |id| {
+            let id = id.clone();
+            spawn_local(async move {
+                is_loading.set(true);
+                match CertificateService::get_by_id(&id).await {
+                    Ok(cert) => certificate.set(Some(cert)),
+                    Err(e) => error.set(Some(e)),
+                }
+                match QuestionService::get_by_certificate(&id).await {
+                    Ok(list) => questions.set(list),
+                    Err(e) => error.set(Some(e)),
+                }
+                is_loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let sheet = (*certificate).clone().map(|cert| {
+        let random = if *is_random { Some((*count, *seed)) } else { None };
+        ExamSheet::new(cert, (*questions).clone(), random)
+    });
+
+    let on_random_toggle = {
+        let is_random = is_random.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            is_random.set(input.checked());
+        })
+    };
+
+    let on_count_change = {
+        let count = count.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().trim().parse::<usize>() {
+                count.set(value.max(1));
+            }
+        })
+    };
+
+    let on_seed_change = {
+        let seed = seed.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            if let Ok(value) = input.value().trim().parse::<u64>() {
+                seed.set(value);
+            }
+        })
+    };
+
+    let on_new_seed = {
+        let seed = seed.clone();
+        Callback::from(move |_| seed.set(random_seed()))
+    };
+
+    let section_toggle = |update: fn(&mut ExamSections, bool)| {
+        let sections = sections.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let mut next = *sections;
+            update(&mut next, input.checked());
+            sections.set(next);
+        })
+    };
+    let on_questions_toggle = section_toggle(|s, v| s.questions = v);
+    let on_answer_key_toggle = section_toggle(|s, v| s.answer_key = v);
+    let on_explanations_toggle = section_toggle(|s, v| s.explanations = v);
+
+    let on_print = Callback::from(|_| {
+        let _ = web_sys::window().unwrap().print();
+    });
+
+    let on_download = {
+        let certificate = certificate.clone();
+        let questions = questions.clone();
+        let is_random = is_random.clone();
+        let count = count.clone();
+        let seed = seed.clone();
+        let sections = sections.clone();
+        Callback::from(move |_| {
+            if let Some(cert) = (*certificate).clone() {
+                let random = if *is_random { Some((*count, *seed)) } else { None };
+                let sheet = ExamSheet::new(cert, (*questions).clone(), random);
+                let suffix = match sheet.seed {
+                    Some(seed) => format!("_{}문항_시드{}", sheet.questions.len(), seed),
+                    None => String::new(),
+                };
+                let filename = format!("{}_시험지{}.html", sheet.certificate.name, suffix);
+                let sections = *sections;
+                // 내려받은 파일 하나로도 보이도록 첨부 이미지는 data URI로 넣습니다.
+                spawn_local(async move {
+                    let images = ImageBundle::collect(&sheet.questions).await;
+                    let document = images.embed(&sheet.render_document(sections));
+                    download_file(document.as_bytes(), "text/html;charset=utf-8", &filename);
+                });
+            }
+        })
+    };
+
+    let body = sheet.as_ref().map(|sheet| {
+        let div = web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .create_element("div")
+            .unwrap();
+        div.set_inner_html(&sheet.render_body(*sections));
+        Html::VRef(div.into())
+    });
+
+    html! {
+        <div class="px-4 py-5 sm:p-6">
+            <style>{EXAM_SHEET_CSS}</style>
+            if *is_loading {
+                <div class="text-center py-12 text-gray-500">{"문제를 불러오는 중..."}</div>
+            } else if let Some(err) = &*error {
+                <div class="text-red-600 text-center py-12">{format!("오류: {}", err)}</div>
+            } else if let Some(body) = body {
+                <>
+                    <div class="no-print bg-white shadow rounded-lg p-4 mb-6 space-y-4">
+                        <div class="flex items-center justify-between">
+                            <h2 class="text-xl font-bold text-gray-900">{"시험지 인쇄"}</h2>
+                            <Link<Route> to={Route::CertificateDetail { id: props.certificate_id.clone() }} classes="text-sm text-blue-600 hover:text-blue-900">
+                                {"← 자격증으로 돌아가기"}
+                            </Link<Route>>
+                        </div>
+
+                        <div class="flex flex-wrap items-center gap-4 text-sm text-gray-700">
+                            <label class="inline-flex items-center gap-2">
+                                <input type="checkbox" checked={*is_random} onchange={on_random_toggle} />
+                                {"무작위 추출"}
+                            </label>
+                            <label class="inline-flex items-center gap-2">
+                                {"문항 수"}
+                                <input type="number" min="1" value={count.to_string()} disabled={!*is_random} onchange={on_count_change}
+                                    class="w-20 border border-gray-300 rounded-md px-2 py-1 disabled:bg-gray-100" />
+                            </label>
+                            <label class="inline-flex items-center gap-2">
+                                {"시드"}
+                                <input type="number" min="0" value={seed.to_string()} disabled={!*is_random} onchange={on_seed_change}
+                                    class="w-28 border border-gray-300 rounded-md px-2 py-1 disabled:bg-gray-100" />
+                            </label>
+                            <button onclick={on_new_seed} disabled={!*is_random}
+                                class="px-3 py-1 border border-gray-300 rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50">
+                                {"다시 섞기"}
+                            </button>
+                        </div>
+
+                        <div class="flex flex-wrap items-center gap-4 text-sm text-gray-700">
+                            <label class="inline-flex items-center gap-2">
+                                <input type="checkbox" checked={sections.questions} onchange={on_questions_toggle} />
+                                {"시험지"}
+                            </label>
+                            <label class="inline-flex items-center gap-2">
+                                <input type="checkbox" checked={sections.answer_key} onchange={on_answer_key_toggle} />
+                                {"정답표"}
+                            </label>
+                            <label class="inline-flex items-center gap-2">
+                                <input type="checkbox" checked={sections.explanations} onchange={on_explanations_toggle} />
+                                {"해설집"}
+                            </label>
+                        </div>
+
+                        <div class="flex items-center gap-2">
+                            <button onclick={on_print}
+                                class="inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md shadow-sm text-white bg-blue-600 hover:bg-blue-700">
+                                {"인쇄"}
+                            </button>
+                            <button onclick={on_download}
+                                class="inline-flex items-center px-4 py-2 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50">
+                                {"HTML 내려받기"}
+                            </button>
+                            <span class="text-xs text-gray-500">
+                                {"PDF가 필요하면 인쇄 창에서 'PDF로 저장'을 선택하세요. 같은 시드로 다시 만들면 같은 시험지가 나옵니다."}
+                            </span>
+                        </div>
+                    </div>
+
+                    <div class="shadow">{body}</div>
+                </>
+            } else {
+                <div class="text-center py-12 text-gray-500">{"자격증을 찾을 수 없습니다."}</div>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,3 @@
+pub mod exam_sheet;
+
+pub use exam_sheet::ExamSheetPage;
\ No newline at end of file
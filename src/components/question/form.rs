@@ -1,12 +1,53 @@
 use crate::models::{Certificate, Question, QuestionOption};
 use crate::routes::Route;
-use crate::services::{CertificateService, QuestionService};
+use crate::services::{CertificateService, ImageService, QuestionService};
+use crate::components::markdown::ContentSource;
 use crate::components::Markdown; // [중요] Markdown 컴포넌트 사용
-use web_sys::{HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
+use gloo_file::File;
+use wasm_bindgen::JsCast;
+use web_sys::{ClipboardEvent, FileList, HtmlInputElement, HtmlSelectElement, HtmlTextAreaElement};
 use yew::prelude::*;
 use yew_router::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 
+/// 이미지를 넣을 입력란
+#[derive(Clone, Copy, PartialEq)]
+enum ImageTarget {
+    Question,
+    Explanation,
+}
+
+/// 붙여넣거나 끌어다 놓은 항목 중 올릴 수 있는 이미지 파일만 고릅니다.
+fn image_files(list: Option<FileList>) -> Vec<File> {
+    let Some(list) = list else {
+        return Vec::new();
+    };
+    (0..list.length())
+        .filter_map(|i| list.get(i))
+        .filter(|file| ImageService::is_image_type(&file.type_()))
+        .map(File::from)
+        .collect()
+}
+
+/// 커서 위치(없으면 끝)에 `text`를 넣은 새 값을 돌려줍니다.
+fn insert_at_cursor(textarea: &HtmlTextAreaElement, text: &str) -> String {
+    let value = textarea.value();
+    // selectionStart는 UTF-16 단위이므로 바이트 위치로 바꿉니다.
+    let cursor = textarea.selection_start().ok().flatten().map(|units| {
+        let mut seen = 0;
+        value
+            .char_indices()
+            .find(|(_, c)| {
+                let found = seen >= units as usize;
+                seen += c.len_utf16();
+                found
+            })
+            .map_or(value.len(), |(index, _)| index)
+    });
+    let at = cursor.unwrap_or(value.len());
+    format!("{}{}{}", &value[..at], text, &value[at..])
+}
+
 #[derive(Properties, PartialEq)]
 pub struct QuestionFormProps {
     #[prop_or_default]
@@ -33,6 +74,10 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
     });
     let error = use_state(|| None::<String>);
     let is_loading = use_state(|| true);
+    let is_uploading = use_state(|| false);
+    let question_ref = use_node_ref();
+    let explanation_ref = use_node_ref();
+    let image_input_ref = use_node_ref();
 
     {
         let certificates = certificates.clone();
@@ -149,6 +194,126 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
         })
     };
 
+    // 이미지를 올린 뒤 대상 입력란의 커서 위치에 마크다운 참조를 넣습니다.
+    let on_image_files = {
+        let question_content = question_content.clone();
+        let explanation = explanation.clone();
+        let error = error.clone();
+        let is_uploading = is_uploading.clone();
+        let question_ref = question_ref.clone();
+        let explanation_ref = explanation_ref.clone();
+        Callback::from(move |(target, files): (ImageTarget, Vec<File>)| {
+            if files.is_empty() {
+                return;
+            }
+            let question_content = question_content.clone();
+            let explanation = explanation.clone();
+            let error = error.clone();
+            let is_uploading = is_uploading.clone();
+            let (field, textarea) = match target {
+                ImageTarget::Question => (question_content, question_ref.cast::<HtmlTextAreaElement>()),
+                ImageTarget::Explanation => (explanation, explanation_ref.cast::<HtmlTextAreaElement>()),
+            };
+            is_uploading.set(true);
+            error.set(None);
+            spawn_local(async move {
+                let mut references = Vec::new();
+                let mut failures = Vec::new();
+                for file in &files {
+                    match ImageService::upload(file).await {
+                        Ok(reference) => references.push(reference),
+                        Err(e) => failures.push(e),
+                    }
+                }
+                if !references.is_empty() {
+                    let text = references.join("\n");
+                    let value = match &textarea {
+                        Some(textarea) => insert_at_cursor(textarea, &text),
+                        None if field.is_empty() => text,
+                        None => format!("{}\n{}", *field, text),
+                    };
+                    field.set(value);
+                }
+                if !failures.is_empty() {
+                    error.set(Some(failures.join("\n")));
+                }
+                is_uploading.set(false);
+            });
+        })
+    };
+
+    let on_image_paste = |target: ImageTarget| {
+        let on_image_files = on_image_files.clone();
+        Callback::from(move |e: Event| {
+            let files = image_files(
+                e.dyn_ref::<ClipboardEvent>()
+                    .and_then(|e| e.clipboard_data())
+                    .and_then(|data| data.files()),
+            );
+            // 이미지가 없으면 평소처럼 글자를 붙여넣습니다.
+            if !files.is_empty() {
+                e.prevent_default();
+                on_image_files.emit((target, files));
+            }
+        })
+    };
+
+    let on_image_drop = |target: ImageTarget| {
+        let on_image_files = on_image_files.clone();
+        Callback::from(move |e: DragEvent| {
+            let files = image_files(e.data_transfer().and_then(|data| data.files()));
+            if !files.is_empty() {
+                e.prevent_default();
+                on_image_files.emit((target, files));
+            }
+        })
+    };
+
+    // 기본 동작(파일 열기)을 막아야 drop 이벤트가 옵니다.
+    let on_image_dragover = Callback::from(|e: DragEvent| e.prevent_default());
+
+    let on_image_pick = {
+        let image_input_ref = image_input_ref.clone();
+        Callback::from(move |_| {
+            if let Some(input) = image_input_ref.cast::<HtmlInputElement>() {
+                input.click();
+            }
+        })
+    };
+
+    let on_image_input_change = {
+        let on_image_files = on_image_files.clone();
+        Callback::from(move |e: Event| {
+            let input: HtmlInputElement = e.target_unchecked_into();
+            let files = image_files(input.files());
+            // 같은 파일을 다시 고를 수 있도록 비웁니다.
+            input.set_value("");
+            on_image_files.emit((ImageTarget::Question, files));
+        })
+    };
+
+    // 첨부 이미지 제거: 모든 입력란에서 참조를 지웁니다. 파일은 나중에 정리됩니다.
+    let on_image_remove = {
+        let question_content = question_content.clone();
+        let explanation = explanation.clone();
+        let options = options.clone();
+        Callback::from(move |url: String| {
+            question_content.set(ImageService::remove_references(&question_content, &url));
+            explanation.set(ImageService::remove_references(&explanation, &url));
+            let opts = options
+                .iter()
+                .map(|(content, is_correct, expl)| {
+                    (
+                        ImageService::remove_references(content, &url),
+                        *is_correct,
+                        ImageService::remove_references(expl, &url),
+                    )
+                })
+                .collect();
+            options.set(opts);
+        })
+    };
+
     let on_submit = {
         let navigator = navigator.clone();
         let selected_certificate = selected_certificate.clone();
@@ -227,6 +392,23 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
         })
     };
 
+    // 미리보기도 실제 화면과 같은 원시 HTML 규칙을 따릅니다.
+    let source = certificates
+        .iter()
+        .find(|c| c.id == *selected_certificate)
+        .map(ContentSource::of)
+        .unwrap_or_default();
+
+    let mut image_urls: Vec<String> = Vec::new();
+    let texts = [question_content.as_str(), explanation.as_str()]
+        .into_iter()
+        .chain(options.iter().flat_map(|(content, _, expl)| [content.as_str(), expl.as_str()]));
+    for url in texts.flat_map(ImageService::image_urls) {
+        if !image_urls.contains(&url) {
+            image_urls.push(url);
+        }
+    }
+
     html! {
         <div class="max-w-4xl mx-auto px-4 py-5 sm:p-6">
             <h2 class="text-2xl font-bold text-gray-900 mb-6">
@@ -256,20 +438,68 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
                     </label>
                     <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
                         <textarea
+                            ref={question_ref}
                             value={(*question_content).clone()}
                             onchange={on_question_change}
+                            onpaste={on_image_paste(ImageTarget::Question)}
+                            ondrop={on_image_drop(ImageTarget::Question)}
+                            ondragover={on_image_dragover.clone()}
                             rows="5"
                             class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm p-2 border"
-                            placeholder="문제를 입력하세요. (예: **굵게**, `코드`)"
+                            placeholder="문제를 입력하세요. (예: **굵게**, `코드`, $x^2$)"
                             disabled={*is_loading}
                         />
                         <div class="border rounded-md p-3 bg-gray-50 overflow-y-auto h-full min-h-[130px]">
                             <p class="text-xs text-gray-400 mb-2 font-bold">{"미리보기"}</p>
                             <div class="prose prose-sm max-w-none">
-                                <Markdown content={(*question_content).clone()} />
+                                <Markdown content={(*question_content).clone()} source={source} />
                             </div>
                         </div>
                     </div>
+                    <div class="mt-2 flex items-center space-x-3">
+                        <input
+                            type="file"
+                            ref={image_input_ref}
+                            onchange={on_image_input_change}
+                            accept="image/png,image/jpeg,image/gif,image/webp"
+                            multiple=true
+                            class="hidden"
+                        />
+                        <button
+                            type="button"
+                            onclick={on_image_pick}
+                            class="inline-flex items-center px-3 py-1.5 border border-gray-300 text-sm font-medium rounded-md text-gray-700 bg-white hover:bg-gray-50 disabled:opacity-50"
+                            disabled={*is_loading || *is_uploading}
+                        >
+                            {"이미지 첨부"}
+                        </button>
+                        <span class="text-xs text-gray-500">
+                            { if *is_uploading { "이미지를 올리는 중..." } else { "문제·해설 입력란에 이미지를 붙여넣거나 끌어다 놓을 수도 있습니다. (PNG, JPEG, GIF, WebP · 5MB 이하)" } }
+                        </span>
+                    </div>
+                    if !image_urls.is_empty() {
+                        <div class="mt-3 flex flex-wrap gap-3">
+                            {for image_urls.iter().map(|url| {
+                                let on_image_remove = on_image_remove.clone();
+                                let remove_url = url.clone();
+                                html! {
+                                    <div key={url.clone()} class="w-24 text-center">
+                                        <a href={url.clone()} target="_blank" rel="noopener noreferrer">
+                                            <img src={url.clone()} alt="첨부 이미지" class="h-24 w-24 object-cover rounded border border-gray-200 bg-gray-50" />
+                                        </a>
+                                        <button
+                                            type="button"
+                                            onclick={move |_| on_image_remove.emit(remove_url.clone())}
+                                            class="mt-1 text-xs text-red-600 hover:text-red-900 disabled:opacity-50"
+                                            disabled={*is_loading}
+                                        >
+                                            {"제거"}
+                                        </button>
+                                    </div>
+                                }
+                            })}
+                        </div>
+                    }
                 </div>
 
                 // [수정됨] 보기 입력 부분 (미리보기 포함)
@@ -324,7 +554,7 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
                                                 if !content_value.is_empty() {
                                                     <div class="mt-1 p-2 bg-gray-50 rounded border border-gray-100">
                                                         <p class="text-[10px] text-gray-400 mb-1">{"미리보기"}</p>
-                                                        <div class="prose prose-sm text-sm"><Markdown content={content_value.clone()} /></div>
+                                                        <div class="prose prose-sm text-sm"><Markdown content={content_value.clone()} source={source} /></div>
                                                     </div>
                                                 }
                                             </div>
@@ -343,7 +573,7 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
                                                 if !expl_value.is_empty() {
                                                     <div class="mt-1 p-2 bg-gray-50 rounded border border-gray-100">
                                                         <p class="text-[10px] text-gray-400 mb-1">{"해설 미리보기"}</p>
-                                                        <div class="prose prose-sm text-sm text-gray-600"><Markdown content={expl_value.clone()} /></div>
+                                                        <div class="prose prose-sm text-sm text-gray-600"><Markdown content={expl_value.clone()} source={source} /></div>
                                                     </div>
                                                 }
                                             </div>
@@ -376,18 +606,25 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
                     </label>
                     <div class="space-y-2">
                         <textarea
+                            ref={explanation_ref}
                             value={(*explanation).clone()}
                             onchange={on_explanation_change}
+                            onpaste={on_image_paste(ImageTarget::Explanation)}
+                            ondrop={on_image_drop(ImageTarget::Explanation)}
+                            ondragover={on_image_dragover}
                             rows="3"
                             class="block w-full rounded-md border-gray-300 shadow-sm focus:border-blue-500 focus:ring-blue-500 sm:text-sm p-2 border"
                             placeholder="문제에 대한 전체 해설을 입력하세요"
                             disabled={*is_loading}
                         />
+                        <p class="text-xs text-gray-500">
+                            {"> [!TIP] · > [!WARNING] 콜아웃, > [!HINT] 접는 힌트, ==형광펜==, ①~⑩ 원문자 번호를 쓸 수 있습니다."}
+                        </p>
                         if !explanation.is_empty() {
                             <div class="border rounded-md p-3 bg-gray-50">
                                 <p class="text-xs text-gray-400 mb-2 font-bold">{"해설 미리보기"}</p>
                                 <div class="prose prose-sm max-w-none text-gray-700">
-                                    <Markdown content={(*explanation).clone()} />
+                                    <Markdown content={(*explanation).clone()} source={source} />
                                 </div>
                             </div>
                         }
@@ -409,7 +646,7 @@ pub fn question_form(props: &QuestionFormProps) -> Html {
                     <button
                         type="submit"
                         class="px-4 py-2 text-sm font-medium text-white bg-blue-600 rounded-md hover:bg-blue-700 disabled:opacity-50"
-                        disabled={*is_loading}
+                        disabled={*is_loading || *is_uploading}
                     >
                         { if *is_loading { "저장 중..." } else { "문제 저장" } }
                     </button>
@@ -3,7 +3,7 @@ use yew_router::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 use crate::models::Question;
 use crate::routes::Route;
-use crate::services::QuestionService;
+use crate::services::{QuestionService, TrashService};
 use web_sys::{window, HtmlInputElement}; // HtmlInputElement 추가
 
 #[derive(Properties, PartialEq)]
@@ -34,7 +34,7 @@ pub fn question_list(props: &QuestionListProps) -> Html {
                 error.set(None);
                 match QuestionService::get_by_certificate(&certificate_id).await {
                     Ok(mut quests) => {
-                        quests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                        quests.sort_by_key(|q| std::cmp::Reverse(q.created_at));
                         questions.set(quests);
                     },
                     Err(e) => error.set(Some(e)),
@@ -51,7 +51,7 @@ pub fn question_list(props: &QuestionListProps) -> Html {
         Callback::from(move |id: String| {
             let questions = questions.clone();
             let error = error.clone();
-            if window().unwrap().confirm_with_message("정말로 이 문제를 삭제하시겠습니까?").unwrap_or(false) {
+            if window().unwrap().confirm_with_message(&format!("이 문제를 휴지통으로 옮깁니다. 휴지통에서 {}일 안에 복원할 수 있습니다.", TrashService::RETENTION_DAYS)).unwrap_or(false) {
                 spawn_local(async move {
                     if let Err(e) = QuestionService::delete(&id).await {
                         error.set(Some(e));
@@ -134,11 +134,11 @@ pub fn question_list(props: &QuestionListProps) -> Html {
                     html! {
                         <>
                             <div class="space-y-4">
-                                {for current_view_questions.enumerate().map(|(idx_on_page, question)| {
+                                {for current_view_questions.map(|question| {
                                     let question_id = question.id.clone();
                                     let on_delete = on_delete.clone();
-                                    // 전체 목록에서의 실제 인덱스 (검색 결과 기준 아님, 현재 페이지 기준 번호 표시)
-                                    let display_number = total_items - (start_index + idx_on_page) + 1; // 역순 번호 표시 예시 (선택 사항)
+                                    // 번호를 표시하려면 `enumerate()`로 현재 페이지 안의 순서(idx_on_page)를 받아 씁니다.
+                                    // let display_number = total_items - (start_index + idx_on_page) + 1; // 역순 번호 표시 예시 (선택 사항)
                                     // 또는 기존 방식대로:
                                     // let display_number = start_index + idx_on_page + 1;
 
@@ -1,6 +1,7 @@
 use crate::models::{Certificate, Question, QuestionOption};
 use crate::routes::Route;
 use crate::services::{CertificateService, QuestionService};
+use crate::components::markdown::ContentSource;
 use crate::components::Markdown; // [추가됨]
 use chrono::Utc;
 use rand::seq::SliceRandom;
@@ -147,6 +148,7 @@ pub fn quiz_page(props: &QuizPageProps) -> Html {
         QuizState::InProgress { current_index, .. } => questions.get(*current_index).cloned(),
         _ => None,
     };
+    let source = certificate.as_ref().map(ContentSource::of).unwrap_or_default();
 
     html! {
         <div class="max-w-4xl mx-auto px-4 py-5 sm:p-6">
@@ -183,7 +185,7 @@ pub fn quiz_page(props: &QuizPageProps) -> Html {
                                 <div class="bg-white shadow rounded-lg p-6 mb-6">
                                     // [수정됨] 문제 내용 마크다운 적용
                                     <h3 class="text-lg font-medium text-gray-900 mb-4 prose max-w-none">
-                                        <Markdown content={question.content.clone()} />
+                                        <Markdown content={question.content.clone()} source={source} />
                                     </h3>
 
                                     <div class="space-y-3">
@@ -218,13 +220,13 @@ pub fn quiz_page(props: &QuizPageProps) -> Html {
                                                         <div class="flex-1">
                                                             // [수정됨] 보기 내용 마크다운 적용
                                                             <div class="prose prose-sm max-w-none">
-                                                                <Markdown content={option.content.clone()} />
+                                                                <Markdown content={option.content.clone()} source={source} />
                                                             </div>
                                                             {if (is_solved || is_tried_incorrect) && !option.explanation.is_empty() {
                                                                 html! {
                                                                     // [수정됨] 보기 해설 마크다운 적용
                                                                     <div class="mt-2 text-sm text-gray-600 prose prose-sm max-w-none bg-white p-2 rounded border border-gray-100">
-                                                                        <Markdown content={option.explanation.clone()} />
+                                                                        <Markdown content={option.explanation.clone()} source={source} />
                                                                     </div>
                                                                 }
                                                             } else {
@@ -250,7 +252,7 @@ pub fn quiz_page(props: &QuizPageProps) -> Html {
                                                 <h4 class="font-medium text-gray-900 mb-2">{"전체 해설"}</h4>
                                                 // [수정됨] 전체 해설 마크다운 적용
                                                 <div class="text-gray-700 prose prose-sm max-w-none">
-                                                    <Markdown content={question.explanation.clone()} />
+                                                    <Markdown content={question.explanation.clone()} source={source} />
                                                 </div>
                                             </div>
                                         }
@@ -285,7 +287,7 @@ pub fn quiz_page(props: &QuizPageProps) -> Html {
                         <div class="mb-6">
                             <p class="text-4xl font-bold text-blue-600 mb-2">{format!("{}/{}", correct_answers, total_questions)}</p>
                             <p class="text-gray-600">
-                                {if total_questions > 0 { format!("첫 시도 정답률: {}%", correct_answers * 100 / total_questions) } else { "정답률: 0%".to_string() }}
+                                {if let Some(rate) = (correct_answers * 100).checked_div(total_questions) { format!("첫 시도 정답률: {}%", rate) } else { "정답률: 0%".to_string() }}
                             </p>
                         </div>
                         <div class="space-y-3">
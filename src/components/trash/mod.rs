@@ -0,0 +1,3 @@
+pub mod trash_page;
+
+pub use trash_page::TrashPage;
\ No newline at end of file
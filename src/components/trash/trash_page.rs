@@ -0,0 +1,199 @@
+use crate::services::trash_service::TrashContents;
+use crate::services::{CertificateService, QuestionService, TrashService};
+use wasm_bindgen_futures::spawn_local;
+use web_sys::window;
+use yew::prelude::*;
+
+/// 휴지통 항목 종류
+#[derive(Clone, Copy, PartialEq)]
+enum TrashKind {
+    Certificate,
+    Question,
+}
+
+fn preview(content: &str) -> String {
+    let line = content.lines().find(|l| !l.trim().is_empty()).unwrap_or_default();
+    let mut text: String = line.chars().take(80).collect();
+    if line.chars().count() > 80 {
+        text.push('…');
+    }
+    text
+}
+
+#[function_component(TrashPage)]
+pub fn trash_page() -> Html {
+    let contents = use_state(TrashContents::default);
+    let error = use_state(|| None::<String>);
+    let is_loading = use_state(|| true);
+    let reload = use_state(|| 0u32);
+
+    {
+        let contents = contents.clone();
+        let error = error.clone();
+        let is_loading = is_loading.clone();
+        use_effect_with(*reload, move |_| {
+            spawn_local(async move {
+                is_loading.set(true);
+                // 보관 기간이 지난 항목은 목록을 보여주기 전에 정리합니다.
+                if let Err(e) = TrashService::purge_expired().await {
+                    web_sys::console::warn_1(&e.into());
+                }
+                match TrashService::list().await {
+                    Ok(list) => contents.set(list),
+                    Err(e) => error.set(Some(e)),
+                }
+                is_loading.set(false);
+            });
+            || ()
+        });
+    }
+
+    let on_restore = {
+        let error = error.clone();
+        let reload = reload.clone();
+        Callback::from(move |(kind, id): (TrashKind, String)| {
+            let error = error.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                let result = match kind {
+                    TrashKind::Certificate => CertificateService::restore(&id).await,
+                    TrashKind::Question => QuestionService::restore(&id).await,
+                };
+                if let Err(e) = result {
+                    error.set(Some(e));
+                }
+                reload.set(*reload + 1);
+            });
+        })
+    };
+
+    let on_purge = {
+        let error = error.clone();
+        let reload = reload.clone();
+        Callback::from(move |(kind, id): (TrashKind, String)| {
+            let confirmation = match kind {
+                TrashKind::Certificate => "이 자격증과 모든 문제를 영구 삭제합니다. 되돌릴 수 없습니다. 계속하시겠습니까?",
+                TrashKind::Question => "이 문제를 영구 삭제합니다. 되돌릴 수 없습니다. 계속하시겠습니까?",
+            };
+            if !window().unwrap().confirm_with_message(confirmation).unwrap_or(false) {
+                return;
+            }
+            let error = error.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                let result = match kind {
+                    TrashKind::Certificate => CertificateService::purge(&id).await,
+                    TrashKind::Question => QuestionService::purge(&id).await,
+                };
+                if let Err(e) = result {
+                    error.set(Some(e));
+                }
+                reload.set(*reload + 1);
+            });
+        })
+    };
+
+    let on_empty = {
+        let error = error.clone();
+        let reload = reload.clone();
+        Callback::from(move |_| {
+            if !window().unwrap().confirm_with_message("휴지통의 모든 항목을 영구 삭제합니다. 계속하시겠습니까?").unwrap_or(false) {
+                return;
+            }
+            let error = error.clone();
+            let reload = reload.clone();
+            spawn_local(async move {
+                if let Err(e) = TrashService::empty().await {
+                    error.set(Some(e));
+                }
+                reload.set(*reload + 1);
+            });
+        })
+    };
+
+    let actions = |kind: TrashKind, id: String| {
+        let on_restore = on_restore.clone();
+        let on_purge = on_purge.clone();
+        let purge_id = id.clone();
+        html! {
+            <div class="flex-shrink-0 space-x-3 text-sm">
+                <button onclick={move |_| on_restore.emit((kind, id.clone()))} class="text-blue-600 hover:text-blue-900">
+                    {"복원"}
+                </button>
+                <button onclick={move |_| on_purge.emit((kind, purge_id.clone()))} class="text-red-600 hover:text-red-900">
+                    {"영구 삭제"}
+                </button>
+            </div>
+        }
+    };
+
+    let is_empty = contents.certificates.is_empty() && contents.questions.is_empty();
+
+    html! {
+        <div class="px-4 py-5 sm:p-6">
+            <div class="flex items-center justify-between mb-6">
+                <h2 class="text-2xl font-bold text-gray-900">{"휴지통"}</h2>
+                if !is_empty {
+                    <button
+                        onclick={on_empty}
+                        class="inline-flex items-center px-4 py-2 border border-transparent text-sm font-medium rounded-md shadow-sm text-white bg-red-600 hover:bg-red-700"
+                    >
+                        {"휴지통 비우기"}
+                    </button>
+                }
+            </div>
+            <p class="mb-4 text-sm text-gray-600">
+                {format!("삭제한 자격증과 문제는 {}일 동안 보관된 뒤 자동으로 영구 삭제됩니다.", TrashService::RETENTION_DAYS)}
+            </p>
+
+            if let Some(err) = &*error {
+                <div class="mb-4 text-red-600">{format!("오류: {}", err)}</div>
+            }
+
+            if *is_loading {
+                <div class="text-center py-12 text-gray-500">{"휴지통을 불러오는 중..."}</div>
+            } else if is_empty {
+                <div class="text-center py-12 text-gray-500">{"휴지통이 비어 있습니다."}</div>
+            } else {
+                <div class="space-y-6">
+                    if !contents.certificates.is_empty() {
+                        <div class="bg-white shadow rounded-lg">
+                            <h3 class="px-4 py-3 border-b border-gray-200 text-sm font-medium text-gray-900">{"자격증"}</h3>
+                            <ul class="divide-y divide-gray-200">
+                                {for contents.certificates.iter().map(|cert| html! {
+                                    <li class="flex items-center justify-between px-4 py-3">
+                                        <div>
+                                            <p class="text-sm font-medium text-gray-900">{&cert.name}</p>
+                                            <p class="text-xs text-gray-500">
+                                                {format!("문제 {}개 · {}일 후 영구 삭제", cert.question_count, TrashService::days_left(cert.deleted_at))}
+                                            </p>
+                                        </div>
+                                        {actions(TrashKind::Certificate, cert.id.clone())}
+                                    </li>
+                                })}
+                            </ul>
+                        </div>
+                    }
+                    if !contents.questions.is_empty() {
+                        <div class="bg-white shadow rounded-lg">
+                            <h3 class="px-4 py-3 border-b border-gray-200 text-sm font-medium text-gray-900">{"문제"}</h3>
+                            <ul class="divide-y divide-gray-200">
+                                {for contents.questions.iter().map(|(question, certificate_name)| html! {
+                                    <li class="flex items-center justify-between px-4 py-3">
+                                        <div class="min-w-0 mr-4">
+                                            <p class="text-sm text-gray-900 truncate">{preview(&question.content)}</p>
+                                            <p class="text-xs text-gray-500">
+                                                {format!("{} · {}일 후 영구 삭제", certificate_name, TrashService::days_left(question.deleted_at))}
+                                            </p>
+                                        </div>
+                                        {actions(TrashKind::Question, question.id.clone())}
+                                    </li>
+                                })}
+                            </ul>
+                        </div>
+                    }
+                </div>
+            }
+        </div>
+    }
+}
@@ -1,3 +1,3 @@
 pub mod supabase;
 
-pub use supabase::SUPABASE_CONFIG;
\ No newline at end of file
+pub use supabase::{scoped_key, SUPABASE_CONFIG};
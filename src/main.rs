@@ -6,136 +6,218 @@ mod models;
 mod routes;
 mod services;
 
+use components::auth::{use_has_pending_guest_data, use_is_guest, use_user, AuthProvider, GuestMigration};
+use components::markdown::RawHtmlProvider;
 use routes::{switch, Route};
-use services::AuthService;
+use services::{AuthService, SchemaService, SnapshotService, TrashService};
 use wasm_bindgen_futures::spawn_local;
-use web_sys::window;
 use yew::prelude::*;
 use yew_router::prelude::*;
 
 #[function_component(App)]
 fn app() -> Html {
-    let is_checking_auth = use_state(|| true);
-    let force_render = use_force_update();
+    html! {
+        <AuthProvider>
+            <RawHtmlProvider>
+                <HashRouter>
+                    <Layout />
+                </HashRouter>
+            </RawHtmlProvider>
+        </AuthProvider>
+    }
+}
 
-    // 인증 콜백 및 세션 갱신 처리
-    {
-        let is_checking_auth = is_checking_auth.clone();
-        let force_render = force_render.clone();
+#[function_component(Layout)]
+fn layout() -> Html {
+    let current_user = use_user();
+    let is_guest = use_is_guest();
+    let has_pending_guest_data = use_has_pending_guest_data();
+    let is_authenticated = current_user.is_some() || is_guest;
 
-        use_effect_with((), move |_| {
-            spawn_local(async move {
-                // Magic Link 콜백 처리
-                match AuthService::handle_auth_callback().await {
-                    Ok(processed) => {
-                        if processed {
-                            web_sys::console::log_1(&"Auth callback successfully processed.".into());
-                        }
-                    }
-                    Err(e) => {
-                        web_sys::console::error_1(&format!("Auth callback error: {}", e).into());
-                    }
-                }
+    // 게스트로 쓰다가 로그인했으면 먼저 게스트 데이터를 계정으로 옮깁니다.
+    let migration_finished = use_state(|| false);
+    let migration_message = use_state(|| None::<String>);
+    let is_migrating = current_user.is_some() && !*migration_finished && has_pending_guest_data;
+    let on_migration_done = {
+        let migration_finished = migration_finished.clone();
+        let migration_message = migration_message.clone();
+        Callback::from(move |message: Option<String>| {
+            migration_message.set(message);
+            migration_finished.set(true);
+        })
+    };
 
-                // ===== 추가된 세션 갱신 로직 시작 =====
-                // 세션이 존재하고 Access Token이 만료되었다면
-                if AuthService::get_session().is_some() && AuthService::is_token_expired() {
-                    web_sys::console::log_1(&"Access token expired, attempting to refresh...".into());
-                    // Refresh Token으로 새로운 Access Token을 요청합니다.
-                    if let Err(e) = AuthService::refresh_token().await {
-                        web_sys::console::error_1(&format!("Failed to refresh token: {}", e).into());
-                        // 갱신 실패 시 로그아웃 처리 (오래된 세션 삭제)
-                        let _ = AuthService::sign_out().await;
-                    } else {
-                        web_sys::console::log_1(&"Token refreshed successfully.".into());
+    // 자동 스냅샷과 휴지통 정리: 로그인(또는 게스트 모드)해 있는 동안 바로 한 번, 이후 30분마다 확인합니다.
+    // (실제 저장 주기와 보관 기간은 각 서비스가 판단)
+    let data_owner = current_user.as_ref().map(|u| u.id.clone()).filter(|_| !is_migrating);
+    use_effect_with((data_owner, is_guest), |(data_owner, is_guest)| {
+        let interval = (data_owner.is_some() || *is_guest).then(|| {
+            let check = || {
+                spawn_local(async {
+                    if let Err(e) = SnapshotService::take_scheduled().await {
+                        web_sys::console::warn_1(&format!("자동 스냅샷을 만들지 못했습니다: {}", e).into());
                     }
-                }
-                // ===== 추가된 세션 갱신 로직 끝 =====
-
-                is_checking_auth.set(false);
-                // 상태 변경을 UI에 즉시 반영하기 위해 강제 리렌더링
-                force_render.force_update();
-            });
-            || ()
+                    if let Err(e) = TrashService::purge_expired().await {
+                        web_sys::console::warn_1(&format!("휴지통을 비우지 못했습니다: {}", e).into());
+                    }
+                });
+            };
+            check();
+            gloo::timers::callback::Interval::new(30 * 60 * 1000, check)
         });
-    }
+        move || drop(interval)
+    });
 
-    if *is_checking_auth {
-        return html! {
-            <div class="min-h-screen flex items-center justify-center">
-                <p class="text-gray-500">{"세션 확인 중..."}</p>
-            </div>
-        };
-    }
-
-    let is_authenticated = AuthService::is_authenticated();
-    let current_user = AuthService::get_current_user();
+    // 서버 전환기로 다른 서버를 쓰고 있으면 헷갈리지 않게 이름을 보여줍니다.
+    let config = config::supabase::loaded();
+    let server_name = (config.active != config.environments[0]).then(|| config.active.name.clone());
 
     html! {
-        <HashRouter>
-            <div class="min-h-screen bg-gray-50">
-                {if is_authenticated {
-                    html! {
-                        <>
-                            <nav class="bg-white shadow-sm">
-                                <div class="max-w-7xl mx-auto px-4 sm:px-6 lg:px-8">
-                                    <div class="flex justify-between h-16">
-                                        <div class="flex">
-                                            <div class="flex-shrink-0 flex items-center">
-                                                <Link<Route> to={Route::Home}>
-                                                    <h1 class="text-xl font-bold text-gray-800">{"QuizNote"}</h1>
-                                                </Link<Route>>
-                                            </div>
-                                            <div class="hidden sm:ml-6 sm:flex sm:space-x-8">
-                                                <Link<Route> to={Route::Certificates}
-                                                    classes="border-transparent text-gray-500 hover:border-gray-300 hover:text-gray-700 inline-flex items-center px-1 pt-1 border-b-2 text-sm font-medium">
-                                                    {"자격증 관리"}
-                                                </Link<Route>>
-                                                <Link<Route> to={Route::NewQuestion}
-                                                    classes="border-transparent text-gray-500 hover:border-gray-300 hover:text-gray-700 inline-flex items-center px-1 pt-1 border-b-2 text-sm font-medium">
-                                                    {"문제 작성"}
-                                                </Link<Route>>
-                                                <Link<Route> to={Route::Data}
-                                                    classes="border-transparent text-gray-500 hover:border-gray-300 hover:text-gray-700 inline-flex items-center px-1 pt-1 border-b-2 text-sm font-medium">
-                                                {"데이터 관리"}
+        <div class="min-h-screen bg-gray-50">
+            {if is_authenticated {
+                html! {
+                    <>
+                        <nav class="bg-white shadow-sm">
+                            <div class="max-w-7xl mx-auto px-4 sm:px-6 lg:px-8">
+                                <div class="flex justify-between h-16">
+                                    <div class="flex">
+                                        <div class="flex-shrink-0 flex items-center">
+                                            <Link<Route> to={Route::Home}>
+                                                <h1 class="text-xl font-bold text-gray-800">{"QuizNote"}</h1>
                                             </Link<Route>>
-                                            </div>
+                                            {if let Some(name) = &server_name {
+                                                html! {
+                                                    <span class="ml-2 text-xs font-medium text-purple-800 bg-purple-100 rounded px-2 py-1"
+                                                        title="기본 서버가 아닌 서버에 연결되어 있습니다">
+                                                        {name}
+                                                    </span>
+                                                }
+                                            } else {
+                                                html! {}
+                                            }}
                                         </div>
-                                        <div class="flex items-center">
-                                            <span class="text-sm text-gray-500 mr-4">
-                                                {current_user.map(|u| u.email).unwrap_or_default()}
-                                            </span>
-                                            <button
-                                                onclick={|_| {
-                                                    spawn_local(async {
-                                                        let _ = AuthService::sign_out().await;
-                                                    });
-                                                }}
-                                                class="text-sm text-gray-500 hover:text-gray-700"
-                                            >
-                                                {"로그아웃"}
-                                            </button>
+                                        <div class="hidden sm:ml-6 sm:flex sm:space-x-8">
+                                            <Link<Route> to={Route::Certificates}
+                                                classes="border-transparent text-gray-500 hover:border-gray-300 hover:text-gray-700 inline-flex items-center px-1 pt-1 border-b-2 text-sm font-medium">
+                                                {"자격증 관리"}
+                                            </Link<Route>>
+                                            <Link<Route> to={Route::NewQuestion}
+                                                classes="border-transparent text-gray-500 hover:border-gray-300 hover:text-gray-700 inline-flex items-center px-1 pt-1 border-b-2 text-sm font-medium">
+                                                {"문제 작성"}
+                                            </Link<Route>>
+                                            <Link<Route> to={Route::Data}
+                                                classes="border-transparent text-gray-500 hover:border-gray-300 hover:text-gray-700 inline-flex items-center px-1 pt-1 border-b-2 text-sm font-medium">
+                                            {"데이터 관리"}
+                                        </Link<Route>>
+                                            <Link<Route> to={Route::Trash}
+                                                classes="border-transparent text-gray-500 hover:border-gray-300 hover:text-gray-700 inline-flex items-center px-1 pt-1 border-b-2 text-sm font-medium">
+                                                {"휴지통"}
+                                            </Link<Route>>
                                         </div>
                                     </div>
+                                    {if let Some(user) = &current_user {
+                                        html! {
+                                            <div class="flex items-center">
+                                                <Link<Route> to={Route::Account} classes="text-sm text-gray-500 hover:text-gray-700 mr-4">
+                                                    {user.email.clone()}
+                                                </Link<Route>>
+                                                <button
+                                                    onclick={|_| {
+                                                        spawn_local(async {
+                                                            let _ = AuthService::sign_out().await;
+                                                        });
+                                                    }}
+                                                    class="text-sm text-gray-500 hover:text-gray-700"
+                                                >
+                                                    {"로그아웃"}
+                                                </button>
+                                            </div>
+                                        }
+                                    } else {
+                                        html! {
+                                            <div class="flex items-center">
+                                                <span class="text-xs font-medium text-amber-800 bg-amber-100 rounded px-2 py-1 mr-4"
+                                                    title="데이터가 이 브라우저에만 저장됩니다">
+                                                    {"게스트 모드"}
+                                                </span>
+                                                <Link<Route> to={Route::Login} classes="text-sm text-blue-600 hover:text-blue-800">
+                                                    {"로그인하고 동기화"}
+                                                </Link<Route>>
+                                            </div>
+                                        }
+                                    }}
                                 </div>
-                            </nav>
+                            </div>
+                        </nav>
 
-                            <main class="max-w-7xl mx-auto py-6 sm:px-6 lg:px-8">
-                                <Switch<Route> render={switch} />
-                            </main>
-                        </>
-                    }
-                } else {
-                    html! {
-                        <Switch<Route> render={switch} />
-                    }
-                }}
+                        <main class="max-w-7xl mx-auto py-6 sm:px-6 lg:px-8">
+                            {if is_migrating {
+                                html! { <GuestMigration on_done={on_migration_done} /> }
+                            } else {
+                                html! {
+                                    <>
+                                        {if let Some(message) = &*migration_message {
+                                            let migration_message = migration_message.clone();
+                                            html! {
+                                                <div class="mb-4 rounded-md bg-green-50 p-4 flex justify-between items-start text-sm text-green-800">
+                                                    <p>{message}</p>
+                                                    <button onclick={move |_| migration_message.set(None)} class="ml-4 text-green-700 hover:text-green-900">
+                                                        {"닫기"}
+                                                    </button>
+                                                </div>
+                                            }
+                                        } else {
+                                            html! {}
+                                        }}
+                                        <Switch<Route> render={switch} />
+                                    </>
+                                }
+                            }}
+                        </main>
+                    </>
+                }
+            } else {
+                html! {
+                    <Switch<Route> render={switch} />
+                }
+            }}
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+struct ConfigErrorProps {
+    message: String,
+}
+
+/// 서버 설정이나 서버 스키마가 잘못되어 앱을 시작할 수 없을 때
+#[function_component(ConfigError)]
+fn config_error(props: &ConfigErrorProps) -> Html {
+    html! {
+        <div class="min-h-screen flex items-center justify-center bg-gray-50 px-4">
+            <div class="max-w-md w-full rounded-md bg-red-50 p-6 text-sm text-red-800 space-y-2">
+                <h2 class="text-lg font-bold">{"서버 설정 오류"}</h2>
+                <p>{&props.message}</p>
             </div>
-        </HashRouter>
+        </div>
     }
 }
 
 fn main() {
     wasm_logger::init(wasm_logger::Config::default());
-    yew::Renderer::<App>::new().render();
+    // 서버 주소가 정해지고 서버 스키마가 앱과 맞는지 확인한 뒤에 앱을 그립니다.
+    spawn_local(async {
+        let ready = match config::supabase::load().await {
+            Ok(()) => SchemaService::check().await,
+            Err(message) => Err(message),
+        };
+        match ready {
+            Ok(()) => {
+                yew::Renderer::<App>::new().render();
+            }
+            Err(message) => {
+                yew::Renderer::<ConfigError>::with_props(ConfigErrorProps { message }).render();
+            }
+        }
+    });
 }
\ No newline at end of file
@@ -29,6 +29,9 @@ pub struct Question {
     pub attempt_count: u32,
     #[serde(skip_serializing, default)]
     pub correct_count: u32,
+    /// 휴지통으로 옮긴 시각. None이면 사용 중인 문제입니다.
+    #[serde(skip_serializing, default)]
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -57,6 +60,31 @@ pub struct Certificate {
     pub question_count: u32,
     #[serde(skip_serializing, default)]
     pub created_at: DateTime<Utc>,
+    /// 휴지통으로 옮긴 시각. None이면 사용 중인 자격증입니다.
+    #[serde(skip_serializing, default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    /// 파일 가져오기로 만든 자격증. 마크다운의 원시 HTML 처리 기준이 달라집니다.
+    #[serde(default)]
+    pub imported: bool,
+}
+
+/// 로컬(IndexedDB)에 저장한 스냅샷의 요약 정보. 목록은 이것만 읽어서 보여줍니다.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotMeta {
+    pub id: String,
+    pub user_id: String,
+    pub created_at: DateTime<Utc>,
+    pub reason: String,
+    pub certificate_count: usize,
+    pub question_count: usize,
+    /// 내용이 바뀌지 않았으면 자동 스냅샷을 건너뛰기 위한 해시
+    pub fingerprint: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub certificates: Vec<ExportedCertificate>,
 }
 
 // impl 블록들은 그대로 유지합니다.
@@ -72,6 +100,7 @@ impl Question {
             last_attempt: None,
             attempt_count: 0,
             correct_count: 0,
+            deleted_at: None,
         }
     }
 }
@@ -97,6 +126,8 @@ impl Certificate {
             description,
             question_count: 0,
             created_at: Utc::now(),
+            deleted_at: None,
+            imported: false,
         }
     }
 }
\ No newline at end of file
@@ -1,6 +1,6 @@
-use crate::components::auth::Login;
-use crate::components::{CertificateDetail, CertificateList, Home, QuestionForm, QuizPage, DataManagement};
-use crate::services::AuthService;
+use crate::components::auth::{use_is_guest, use_session, Login, ResetPassword};
+use crate::components::{AccountPage, CertificateDetail, CertificateList, Home, QuestionForm, QuizPage, DataManagement, ExamSheetPage, TrashPage};
+use gloo::storage::{LocalStorage, Storage};
 use yew::prelude::*;
 use yew_router::prelude::*;
 
@@ -20,22 +20,79 @@ pub enum Route {
     EditQuestion { id: String },
     #[at("/quiz/:certificate_id")]
     Quiz { certificate_id: String },
+    #[at("/print/:certificate_id")]
+    Print { certificate_id: String },
+    #[at("/trash")]
+    Trash,
     #[at("/data")] // 추가
     Data,
+    #[at("/reset-password")]
+    ResetPassword,
+    #[at("/account")]
+    Account,
     #[not_found]
     #[at("/404")]
     NotFound,
 }
 
-// 제네릭 T를 제거하고 Redirect<Route>로 명시적으로 변경
-fn render_protected_route(component: Html) -> Html {
-    if AuthService::is_authenticated() {
-        component
+/// 로그인 뒤 돌아갈 화면의 경로. 로그인 링크는 새 탭에서 열리는 경우가 많아 LocalStorage에 둡니다.
+const RETURN_TO_KEY: &str = "quiz_note_return_to";
+
+impl Route {
+    /// 로그인한 뒤 돌아가도 되는 화면인지
+    fn is_return_target(&self) -> bool {
+        !matches!(self, Route::Login | Route::ResetPassword | Route::NotFound)
+    }
+}
+
+fn remember_return_to(route: &Route) {
+    if route.is_return_target() {
+        let _ = LocalStorage::set(RETURN_TO_KEY, route.to_path());
+    }
+}
+
+/// 로그인 전에 가려던 화면을 한 번만 꺼냅니다. 없거나 알 수 없는 경로이면 홈
+pub fn take_return_to() -> Route {
+    let path: Option<String> = LocalStorage::get(RETURN_TO_KEY).ok();
+    LocalStorage::delete(RETURN_TO_KEY);
+    path.and_then(|path| Route::recognize(&path))
+        .filter(Route::is_return_target)
+        .unwrap_or(Route::Home)
+}
+
+#[derive(Properties, PartialEq)]
+struct RequireAuthProps {
+    children: Children,
+}
+
+/// 로그인하지 않았고 게스트 모드도 아니면 로그인 화면으로 보냅니다. 로그아웃하면 그 자리에서 바로 로그인 화면으로 바뀝니다.
+/// 로그인하지 않은 채 들어온 화면(공유받은 링크 등)은 기억해 두었다가 로그인한 뒤 그리로 보냅니다.
+#[function_component(RequireAuth)]
+fn require_auth(props: &RequireAuthProps) -> Html {
+    // 게스트 모드도 로그인한 것처럼 모든 화면을 씁니다.
+    let session = use_session();
+    let is_guest = use_is_guest();
+    let signed_in = session.is_some() || is_guest;
+    let route = use_route::<Route>();
+    // 보던 중에 로그아웃한 화면은 다음에 로그인할 사람에게 보여주지 않습니다.
+    let signed_in_on_mount = use_state(|| signed_in);
+
+    if signed_in {
+        html! { <>{props.children.clone()}</> }
     } else {
+        if !*signed_in_on_mount {
+            if let Some(route) = route {
+                remember_return_to(&route);
+            }
+        }
         html! { <Redirect<Route> to={Route::Login} /> }
     }
 }
 
+fn render_protected_route(component: Html) -> Html {
+    html! { <RequireAuth>{component}</RequireAuth> }
+}
+
 pub fn switch(routes: Route) -> Html {
     match routes {
         Route::Login => html! { <Login /> },
@@ -52,7 +109,13 @@ pub fn switch(routes: Route) -> Html {
         Route::Quiz { certificate_id } => {
             render_protected_route(html! { <QuizPage {certificate_id} /> })
         }
+        Route::Print { certificate_id } => {
+            render_protected_route(html! { <ExamSheetPage {certificate_id} /> })
+        }
+        Route::Trash => render_protected_route(html! { <TrashPage /> }),
         Route::Data => render_protected_route(html! { <DataManagement /> }), // 추가
+        Route::ResetPassword => render_protected_route(html! { <ResetPassword /> }),
+        Route::Account => render_protected_route(html! { <AccountPage /> }),
         Route::NotFound => html! {
             <div class="text-center py-12">
                 <h1 class="text-2xl font-bold text-gray-900">{"404 - 페이지를 찾을 수 없습니다"}</h1>
@@ -0,0 +1,273 @@
+// 계정 데이터 내보내기와 계정 삭제
+//
+// 내보내기는 계정에 딸린 모든 것(휴지통 포함 자격증과 문제, 풀이 기록, 통계, 이 브라우저의 설정)을
+// 버전이 붙은 JSON 한 파일로 만듭니다. 이 파일은 데이터 관리 화면에서 그대로 다시 가져올 수 있고,
+// 가져올 때 휴지통에 있던 것은 빼고 풀이 기록은 문제에 다시 붙여 통계를 되살립니다.
+// 계정 삭제는 서버에 `delete_account` 함수가 있는지 먼저 확인하고, 첨부 이미지를 지우고 모든 기기에서
+// 로그아웃한 뒤 그 함수로 모든 행과 인증 사용자를 지웁니다.
+
+use crate::components::markdown::RawHtmlSettings;
+use crate::config::SUPABASE_CONFIG;
+use crate::models::{Certificate, ExportedCertificate, Question, QuestionOption};
+use crate::services::auth::User;
+use crate::services::{AuthService, DataStore, ImageService, SnapshotService, SupabaseClient};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+
+/// 계정 내보내기 파일의 `format` 값
+const EXPORT_FORMAT: &str = "quiznote-account";
+/// 내보내기 형식 버전. 필드의 의미가 바뀌면 올리고 `read_export`에서 이전 버전을 변환합니다.
+const EXPORT_VERSION: u32 = 1;
+/// 서버에 계정 삭제 함수를 만드는 마이그레이션
+const DELETE_ACCOUNT_MIGRATION: &str = "20261019000500_delete_account.sql";
+
+#[derive(Serialize)]
+struct AccountExport {
+    format: &'static str,
+    version: u32,
+    exported_at: DateTime<Utc>,
+    user: User,
+    settings: AccountSettings,
+    stats: AccountStats,
+    /// 휴지통에 있는 것을 포함한 모든 자격증과 문제
+    certificates: Vec<CertificateRecord>,
+    /// 한 번이라도 푼 문제의 풀이 기록
+    attempts: Vec<AttemptRecord>,
+}
+
+/// 이 브라우저에 저장된 설정
+#[derive(Serialize)]
+struct AccountSettings {
+    raw_html: RawHtmlSettings,
+}
+
+#[derive(Serialize)]
+struct AccountStats {
+    certificate_count: usize,
+    question_count: usize,
+    /// 휴지통에 있는 자격증과 문제 수
+    trashed_certificate_count: usize,
+    trashed_question_count: usize,
+    attempt_count: u32,
+    correct_count: u32,
+}
+
+/// `Certificate`는 DB가 관리하는 필드를 직렬화하지 않으므로 내보내기용으로 모두 적습니다.
+/// 필드 이름이 같아 `ExportedCertificate`로 다시 읽을 수 있습니다.
+#[derive(Serialize)]
+struct CertificateRecord {
+    id: String,
+    name: String,
+    description: String,
+    imported: bool,
+    question_count: u32,
+    created_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+    questions: Vec<QuestionRecord>,
+}
+
+#[derive(Serialize)]
+struct QuestionRecord {
+    id: String,
+    certificate_id: String,
+    content: String,
+    explanation: String,
+    question_options: Vec<QuestionOption>,
+    created_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AttemptRecord {
+    question_id: String,
+    certificate_id: String,
+    attempt_count: u32,
+    correct_count: u32,
+    last_attempt: Option<DateTime<Utc>>,
+}
+
+/// 가져올 때 읽는 부분
+#[derive(Deserialize)]
+struct AccountImport {
+    format: String,
+    version: u32,
+    certificates: Vec<ExportedCertificate>,
+    #[serde(default)]
+    attempts: Vec<AttemptRecord>,
+}
+
+impl CertificateRecord {
+    fn new(certificate: Certificate, questions: Vec<QuestionRecord>) -> Self {
+        Self {
+            id: certificate.id,
+            name: certificate.name,
+            description: certificate.description,
+            imported: certificate.imported,
+            question_count: certificate.question_count,
+            created_at: certificate.created_at,
+            deleted_at: certificate.deleted_at,
+            questions,
+        }
+    }
+}
+
+impl From<Question> for QuestionRecord {
+    fn from(question: Question) -> Self {
+        Self {
+            id: question.id,
+            certificate_id: question.certificate_id,
+            content: question.content,
+            explanation: question.explanation,
+            question_options: question.options,
+            created_at: question.created_at,
+            deleted_at: question.deleted_at,
+        }
+    }
+}
+
+pub struct AccountService;
+
+impl AccountService {
+    /// 계정의 모든 데이터를 JSON 문자열로 내보냅니다.
+    pub async fn export() -> Result<String, String> {
+        let user = AuthService::get_current_user().ok_or_else(|| "로그인이 필요합니다".to_string())?;
+        let client = DataStore::current();
+        let mut certificates = client.get_all_certificates().await?;
+        certificates.extend(client.get_deleted_certificates().await?);
+        certificates.sort_by_key(|c| c.created_at);
+        let mut questions = client.get_all_questions().await?;
+        questions.sort_by_key(|q| q.created_at);
+
+        let stats = AccountStats {
+            certificate_count: certificates.iter().filter(|c| c.deleted_at.is_none()).count(),
+            question_count: questions.iter().filter(|q| q.deleted_at.is_none()).count(),
+            trashed_certificate_count: certificates.iter().filter(|c| c.deleted_at.is_some()).count(),
+            trashed_question_count: questions.iter().filter(|q| q.deleted_at.is_some()).count(),
+            attempt_count: questions.iter().map(|q| q.attempt_count).sum(),
+            correct_count: questions.iter().map(|q| q.correct_count).sum(),
+        };
+        let attempts = questions
+            .iter()
+            .filter(|q| q.attempt_count > 0)
+            .map(|q| AttemptRecord {
+                question_id: q.id.clone(),
+                certificate_id: q.certificate_id.clone(),
+                attempt_count: q.attempt_count,
+                correct_count: q.correct_count,
+                last_attempt: q.last_attempt,
+            })
+            .collect();
+        let certificates = certificates
+            .into_iter()
+            .map(|certificate| {
+                let own = questions
+                    .iter()
+                    .filter(|q| q.certificate_id == certificate.id)
+                    .cloned()
+                    .map(QuestionRecord::from)
+                    .collect();
+                CertificateRecord::new(certificate, own)
+            })
+            .collect();
+
+        let export = AccountExport {
+            format: EXPORT_FORMAT,
+            version: EXPORT_VERSION,
+            exported_at: Utc::now(),
+            user,
+            settings: AccountSettings { raw_html: RawHtmlSettings::load() },
+            stats,
+            certificates,
+            attempts,
+        };
+        serde_json::to_string_pretty(&export).map_err(|e| e.to_string())
+    }
+
+    /// 계정 내보내기 파일이면 휴지통에 있던 것을 뺀 자격증과 문제를 돌려줍니다.
+    /// 문제에는 `attempts`의 풀이 기록을 채워 두므로 가져오면서 통계도 옮길 수 있습니다.
+    /// 다른 형식(자격증 배열 백업 등)이면 `Ok(None)`.
+    pub fn read_export(json_str: &str) -> Result<Option<Vec<ExportedCertificate>>, String> {
+        if !json_str.trim_start().starts_with('{') {
+            return Ok(None);
+        }
+        let export: AccountImport = serde_json::from_str(json_str).map_err(|e| e.to_string())?;
+        if export.format != EXPORT_FORMAT {
+            return Err(format!("알 수 없는 파일 형식입니다: {}", export.format));
+        }
+        if export.version > EXPORT_VERSION {
+            return Err("더 새로운 버전의 앱에서 내보낸 파일입니다. 앱을 새로고침한 뒤 다시 시도해주세요.".to_string());
+        }
+        let attempts: HashMap<String, AttemptRecord> =
+            export.attempts.into_iter().map(|a| (a.question_id.clone(), a)).collect();
+        let certificates = export
+            .certificates
+            .into_iter()
+            .filter(|c| c.certificate.deleted_at.is_none())
+            .map(|mut c| {
+                c.questions.retain(|q| q.deleted_at.is_none());
+                for question in &mut c.questions {
+                    if let Some(attempt) = attempts.get(&question.id) {
+                        question.attempt_count = attempt.attempt_count;
+                        question.correct_count = attempt.correct_count;
+                        question.last_attempt = attempt.last_attempt;
+                    }
+                }
+                c
+            })
+            .collect();
+        Ok(Some(certificates))
+    }
+
+    /// 첨부 이미지, 서버의 모든 행과 인증 사용자, 이 브라우저의 스냅샷을 지우고 로그아웃합니다.
+    /// 계정을 지우기 전에 모든 기기의 세션을 끝내므로 다른 기기도 다음 토큰 갱신 때 로그아웃됩니다.
+    pub async fn delete_account() -> Result<(), String> {
+        if AuthService::get_current_user().is_none() {
+            return Err("로그인이 필요합니다".to_string());
+        }
+        // 세션을 끝낸 뒤에는 토큰을 갱신할 수 없습니다. 1분 안에 만료될 토큰이면 지금 갱신해
+        // 삭제를 마칠 때까지 쓸 수 있게 합니다. (`refresh_token`은 그보다 많이 남았으면 그대로 둡니다)
+        AuthService::refresh_token().await?;
+        // 이미지를 지우고 로그아웃한 뒤에 함수가 없다는 걸 알면 되돌릴 수 없으므로 먼저 확인합니다.
+        Self::call_delete_account(true).await?;
+        // 계정을 지운 뒤에는 이미지를 지울 권한도 없어지므로 먼저 지웁니다.
+        ImageService::delete_all()
+            .await
+            .map_err(|e| format!("첨부 이미지를 지우지 못해 계정 삭제를 멈췄습니다: {}", e))?;
+        // 계정을 지우면 로그아웃 요청을 보낼 수 없으므로 그 전에 모든 기기에서 로그아웃합니다.
+        AuthService::revoke_all_sessions()
+            .await
+            .map_err(|e| format!("다른 기기의 로그인을 끝내지 못해 계정 삭제를 멈췄습니다: {}", e))?;
+        Self::call_delete_account(false).await?;
+
+        if let Err(e) = SnapshotService::delete_all().await {
+            web_sys::console::warn_1(&format!("로컬 스냅샷을 지우지 못했습니다: {}", e).into());
+        }
+        AuthService::clear_session();
+        Ok(())
+    }
+
+    /// 서버의 `delete_account` 함수를 부릅니다. `dry_run`이면 함수가 있는지만 확인하고 아무것도 지우지 않습니다.
+    async fn call_delete_account(dry_run: bool) -> Result<(), String> {
+        let url = format!("{}/rest/v1/rpc/delete_account", SUPABASE_CONFIG.url);
+        let response = SupabaseClient::new()
+            .request_builder("POST", &url).await?
+            .header("Content-Type", "application/json")
+            .json(&json!({ "dry_run": dry_run })).map_err(|e| e.to_string())?
+            .send().await.map_err(|e| e.to_string())?;
+        if !response.ok() {
+            let status = response.status();
+            let error: serde_json::Value = response.json().await.unwrap_or_default();
+            return Err(if status == 404 || error["code"] == "PGRST202" {
+                format!(
+                    "서버에 계정 삭제 함수가 없습니다. supabase/migrations의 {}을 적용해주세요.",
+                    DELETE_ACCOUNT_MIGRATION
+                )
+            } else {
+                format!("계정 삭제 실패: {}", error["message"].as_str().unwrap_or_default())
+            });
+        }
+        Ok(())
+    }
+}
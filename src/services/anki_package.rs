@@ -0,0 +1,392 @@
+// Anki 덱 패키지(.apkg) 내보내기
+//
+// .apkg는 `collection.anki2`(SQLite, 스키마 11)와 `media`(파일 번호 → 이름 JSON)를
+// 담은 zip 파일입니다. 자격증은 "QuizNote::<자격증 이름>" 덱이 되고, 자격증 이름은
+// 태그로도 붙습니다. 첨부 이미지는 zip 안에 "0", "1", ... 이름으로 넣고 `media`에
+// 원래 파일 이름을 적으며, 카드에서는 그 파일 이름으로 가리킵니다.
+
+use crate::components::markdown::{render_html, ContentSource};
+use crate::models::{ExportedCertificate, Question};
+use crate::services::exam_sheet::ExamSheet;
+use crate::services::sqlite_writer::{SqlValue, SqliteWriter};
+use crate::services::ImageBundle;
+use serde_json::{json, Map, Value};
+use sha1::{Digest, Sha1};
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// 가져올 때마다 같은 노트 유형으로 합쳐지도록 고정된 ID를 사용합니다.
+const MODEL_ID: i64 = 1_718_000_000_000;
+const DEFAULT_DECK_ID: i64 = 1;
+const DEFAULT_CONF_ID: i64 = 1;
+const ROOT_DECK: &str = "QuizNote";
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+const SCHEMA: [(&str, &str); 5] = [
+    (
+        "col",
+        "CREATE TABLE col (id integer primary key, crt integer not null, mod integer not null, \
+         scm integer not null, ver integer not null, dty integer not null, usn integer not null, \
+         ls integer not null, conf text not null, models text not null, decks text not null, \
+         dconf text not null, tags text not null)",
+    ),
+    (
+        "notes",
+        "CREATE TABLE notes (id integer primary key, guid text not null, mid integer not null, \
+         mod integer not null, usn integer not null, tags text not null, flds text not null, \
+         sfld integer not null, csum integer not null, flags integer not null, data text not null)",
+    ),
+    (
+        "cards",
+        "CREATE TABLE cards (id integer primary key, nid integer not null, did integer not null, \
+         ord integer not null, mod integer not null, usn integer not null, type integer not null, \
+         queue integer not null, due integer not null, ivl integer not null, factor integer not null, \
+         reps integer not null, lapses integer not null, left integer not null, odue integer not null, \
+         odid integer not null, flags integer not null, data text not null)",
+    ),
+    (
+        "revlog",
+        "CREATE TABLE revlog (id integer primary key, cid integer not null, usn integer not null, \
+         ease integer not null, ivl integer not null, lastIvl integer not null, factor integer not null, \
+         time integer not null, type integer not null)",
+    ),
+    (
+        "graves",
+        "CREATE TABLE graves (usn integer not null, oid integer not null, type integer not null)",
+    ),
+];
+
+const FRONT_TEMPLATE: &str = "<div class=\"qn-question\">{{Question}}</div>\n\
+<div class=\"qn-options\">{{Options}}</div>";
+
+const BACK_TEMPLATE: &str = "<div class=\"qn-question\">{{Question}}</div>\n\
+<hr id=answer>\n\
+<div class=\"qn-options\">{{Answer}}</div>\n\
+{{#Explanation}}<div class=\"qn-explanation\">{{Explanation}}</div>{{/Explanation}}";
+
+const CARD_CSS: &str = ".card { font-family: sans-serif; font-size: 18px; text-align: left; color: #111827; background: #fff; }\n\
+.qn-options ol { list-style: none; padding-left: 0; }\n\
+.qn-options li { margin: 6px 0; padding: 6px 10px; border: 1px solid #d1d5db; border-radius: 6px; }\n\
+.qn-options li.correct { border-color: #22c55e; background: #f0fdf4; }\n\
+.qn-num { font-weight: bold; margin-right: 6px; }\n\
+.qn-option-explanation { font-size: 14px; color: #4b5563; margin-top: 4px; }\n\
+.qn-explanation { margin-top: 16px; padding: 10px; background: #f9fafb; border-radius: 6px; }\n\
+.hl-code { background: #f3f4f6; padding: 8px; border-radius: 6px; text-align: left; }\n\
+.hl-line { display: block; }\n\
+.hl-marked { background: #fef9c3; }\n\
+.hl-keyword { color: #7c3aed; }\n\
+.hl-type { color: #b45309; }\n\
+.hl-literal, .hl-number { color: #c2410c; }\n\
+.hl-string { color: #15803d; }\n\
+.hl-comment { color: #6b7280; font-style: italic; }\n\
+.hl-function { color: #1d4ed8; }\n\
+.hl-meta { color: #0e7490; }\n\
+.callout { border-left: 4px solid #3b82f6; background: #eff6ff; padding: 6px 12px; margin: 8px 0; border-radius: 4px; }\n\
+.callout-title { font-weight: bold; margin: 0 0 4px; }\n\
+.callout-tip { border-color: #22c55e; background: #f0fdf4; }\n\
+.callout-important { border-color: #8b5cf6; background: #f5f3ff; }\n\
+.callout-warning { border-color: #f59e0b; background: #fffbeb; }\n\
+.callout-caution { border-color: #ef4444; background: #fef2f2; }\n\
+.callout-hint { border-color: #6b7280; background: #f9fafb; }\n\
+details.callout summary { cursor: pointer; }\n\
+mark { background: #fef08a; }";
+
+pub struct AnkiPackage;
+
+impl AnkiPackage {
+    /// 자격증 목록을 .apkg 파일 바이트로 만듭니다.
+    pub fn build(certificates: &[ExportedCertificate], images: &ImageBundle) -> Result<Vec<u8>, String> {
+        let collection = Self::build_collection(certificates, images)?;
+
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        zip.start_file("collection.anki2", options).map_err(|e| e.to_string())?;
+        zip.write_all(&collection).map_err(|e| e.to_string())?;
+
+        let mut media = Map::new();
+        for (index, image) in images.images().iter().enumerate() {
+            zip.start_file(index.to_string(), options).map_err(|e| e.to_string())?;
+            zip.write_all(&image.bytes).map_err(|e| e.to_string())?;
+            media.insert(index.to_string(), Value::from(image.file_name.as_str()));
+        }
+        zip.start_file("media", options).map_err(|e| e.to_string())?;
+        zip.write_all(Value::Object(media).to_string().as_bytes()).map_err(|e| e.to_string())?;
+
+        zip.finish().map(|cursor| cursor.into_inner()).map_err(|e| e.to_string())
+    }
+
+    fn build_collection(certificates: &[ExportedCertificate], images: &ImageBundle) -> Result<Vec<u8>, String> {
+        let now = chrono::Utc::now();
+        let now_ms = now.timestamp_millis();
+        let now_secs = now.timestamp();
+
+        let mut db = SqliteWriter::new();
+        for (name, sql) in SCHEMA {
+            db.create_table(name, sql);
+        }
+
+        let mut decks = Map::new();
+        decks.insert(DEFAULT_DECK_ID.to_string(), Self::deck_json(DEFAULT_DECK_ID, "Default", now_secs));
+
+        // 노트와 카드 ID는 Anki 관례대로 밀리초 타임스탬프 기반으로 겹치지 않게 매깁니다.
+        let mut next_id = now_ms;
+        let mut position = 0;
+        for (deck_index, exported) in certificates.iter().enumerate() {
+            let deck_id = now_ms + deck_index as i64 + 1;
+            let deck_name = format!("{}::{}", ROOT_DECK, exported.certificate.name.replace("::", ":"));
+            decks.insert(deck_id.to_string(), Self::deck_json(deck_id, &deck_name, now_secs));
+            let tags = format!(" {} {} ", ROOT_DECK, Self::tag(&exported.certificate.name));
+
+            for question in &exported.questions {
+                position += 1;
+                next_id += 1;
+                let note_id = next_id;
+                next_id += 1;
+                let card_id = next_id;
+
+                let question = images.localize(question, "");
+                let fields = Self::note_fields(&question, ContentSource::of(&exported.certificate));
+                let sort_field = Self::strip_html(&fields[0]);
+                db.insert(
+                    "notes",
+                    note_id,
+                    vec![
+                        SqlValue::Null,
+                        SqlValue::from(question.id.as_str()),
+                        SqlValue::Integer(MODEL_ID),
+                        SqlValue::Integer(now_secs),
+                        SqlValue::Integer(0),
+                        SqlValue::from(tags.as_str()),
+                        SqlValue::from(fields.join(&FIELD_SEPARATOR.to_string())),
+                        // sfld는 정수 열로 선언되어 있지만 Anki도 텍스트를 그대로 저장합니다.
+                        SqlValue::from(sort_field.as_str()),
+                        SqlValue::Integer(Self::checksum(&sort_field)),
+                        SqlValue::Integer(0),
+                        SqlValue::from(""),
+                    ],
+                )?;
+                db.insert(
+                    "cards",
+                    card_id,
+                    vec![
+                        SqlValue::Null,
+                        SqlValue::Integer(note_id),
+                        SqlValue::Integer(deck_id),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(now_secs),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0), // type: 새 카드
+                        SqlValue::Integer(0), // queue: 새 카드
+                        SqlValue::Integer(position),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0),
+                        SqlValue::Integer(0),
+                        SqlValue::from(""),
+                    ],
+                )?;
+            }
+        }
+
+        let conf = json!({
+            "activeDecks": [DEFAULT_DECK_ID],
+            "curDeck": DEFAULT_DECK_ID,
+            "newSpread": 0,
+            "collapseTime": 1200,
+            "timeLim": 0,
+            "estTimes": true,
+            "dueCounts": true,
+            "curModel": MODEL_ID,
+            "nextPos": position + 1,
+            "sortType": "noteFld",
+            "sortBackwards": false,
+            "addToCur": true
+        });
+        let mut models = Map::new();
+        models.insert(MODEL_ID.to_string(), Self::model_json(now_secs));
+        let mut dconf = Map::new();
+        dconf.insert(DEFAULT_CONF_ID.to_string(), Self::deck_conf_json());
+
+        db.insert(
+            "col",
+            1,
+            vec![
+                SqlValue::Null,
+                SqlValue::Integer(now_secs - now_secs % 86_400),
+                SqlValue::Integer(now_ms),
+                SqlValue::Integer(now_ms),
+                SqlValue::Integer(11),
+                SqlValue::Integer(0),
+                SqlValue::Integer(0),
+                SqlValue::Integer(0),
+                SqlValue::from(conf.to_string()),
+                SqlValue::from(Value::Object(models).to_string()),
+                SqlValue::from(Value::Object(decks).to_string()),
+                SqlValue::from(Value::Object(dconf).to_string()),
+                SqlValue::from("{}"),
+            ],
+        )?;
+
+        db.finish()
+    }
+
+    /// 노트 필드: 문제, 보기(앞면), 정답 표시된 보기(뒷면), 해설
+    fn note_fields(question: &Question, source: ContentSource) -> [String; 4] {
+        let mut options = String::from("<ol>");
+        let mut answer = String::from("<ol>");
+        for (index, option) in question.options.iter().enumerate() {
+            let number = ExamSheet::circled_number(index);
+            let content = render_html(&option.content, source);
+            options.push_str(&format!("<li><span class=\"qn-num\">{}</span>{}</li>", number, content));
+
+            let class = if option.is_correct { " class=\"correct\"" } else { "" };
+            answer.push_str(&format!("<li{}><span class=\"qn-num\">{}</span>{}", class, number, content));
+            if !option.explanation.is_empty() {
+                answer.push_str(&format!(
+                    "<div class=\"qn-option-explanation\">{}</div>",
+                    render_html(&option.explanation, source)
+                ));
+            }
+            answer.push_str("</li>");
+        }
+        options.push_str("</ol>");
+        answer.push_str("</ol>");
+
+        let explanation = if question.explanation.is_empty() {
+            String::new()
+        } else {
+            render_html(&question.explanation, source)
+        };
+
+        [render_html(&question.content, source), options, answer, explanation]
+    }
+
+    fn model_json(now_secs: i64) -> Value {
+        let field = |name: &str, ord: usize| {
+            json!({
+                "name": name,
+                "ord": ord,
+                "sticky": false,
+                "rtl": false,
+                "font": "Arial",
+                "size": 20,
+                "media": []
+            })
+        };
+        json!({
+            "id": MODEL_ID,
+            "name": "QuizNote 객관식",
+            "type": 0,
+            "mod": now_secs,
+            "usn": 0,
+            "sortf": 0,
+            "did": DEFAULT_DECK_ID,
+            "tmpls": [{
+                "name": "객관식",
+                "ord": 0,
+                "qfmt": FRONT_TEMPLATE,
+                "afmt": BACK_TEMPLATE,
+                "did": null,
+                "bqfmt": "",
+                "bafmt": ""
+            }],
+            "flds": [field("Question", 0), field("Options", 1), field("Answer", 2), field("Explanation", 3)],
+            "css": CARD_CSS,
+            "latexPre": "\\documentclass[12pt]{article}\n\\special{papersize=3in,5in}\n\\usepackage[utf8]{inputenc}\n\\usepackage{amssymb,amsmath}\n\\pagestyle{empty}\n\\setlength{\\parindent}{0in}\n\\begin{document}\n",
+            "latexPost": "\\end{document}",
+            "latexsvg": false,
+            "req": [[0, "any", [0]]],
+            "tags": [],
+            "vers": []
+        })
+    }
+
+    fn deck_json(id: i64, name: &str, now_secs: i64) -> Value {
+        json!({
+            "id": id,
+            "name": name,
+            "mod": now_secs,
+            "usn": 0,
+            "lrnToday": [0, 0],
+            "revToday": [0, 0],
+            "newToday": [0, 0],
+            "timeToday": [0, 0],
+            "collapsed": false,
+            "browserCollapsed": false,
+            "desc": "",
+            "dyn": 0,
+            "conf": DEFAULT_CONF_ID,
+            "extendNew": 0,
+            "extendRev": 0
+        })
+    }
+
+    fn deck_conf_json() -> Value {
+        json!({
+            "id": DEFAULT_CONF_ID,
+            "mod": 0,
+            "name": "Default",
+            "usn": 0,
+            "maxTaken": 60,
+            "autoplay": true,
+            "timer": 0,
+            "replayq": true,
+            "dyn": false,
+            "new": {
+                "bury": false,
+                "delays": [1.0, 10.0],
+                "initialFactor": 2500,
+                "ints": [1, 4, 0],
+                "order": 1,
+                "perDay": 20
+            },
+            "lapse": {
+                "delays": [10.0],
+                "leechAction": 1,
+                "leechFails": 8,
+                "minInt": 1,
+                "mult": 0.0
+            },
+            "rev": {
+                "bury": false,
+                "ease4": 1.3,
+                "ivlFct": 1.0,
+                "maxIvl": 36500,
+                "perDay": 200,
+                "hardFactor": 1.2
+            }
+        })
+    }
+
+    /// Anki 태그에는 공백을 쓸 수 없습니다.
+    fn tag(name: &str) -> String {
+        name.split_whitespace().collect::<Vec<_>>().join("_")
+    }
+
+    /// 정렬 필드와 중복 검사용으로 HTML 태그를 제거합니다.
+    fn strip_html(html: &str) -> String {
+        let mut text = String::new();
+        let mut in_tag = false;
+        for c in html.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' => in_tag = false,
+                _ if !in_tag => text.push(c),
+                _ => {}
+            }
+        }
+        text.trim().to_string()
+    }
+
+    /// Anki 중복 검사 체크섬: 정렬 필드 SHA-1의 앞 8자리 16진수
+    fn checksum(sort_field: &str) -> i64 {
+        let digest = Sha1::digest(sort_field.as_bytes());
+        u32::from_be_bytes([digest[0], digest[1], digest[2], digest[3]]) as i64
+    }
+}
@@ -1,53 +1,189 @@
-use crate::config::SUPABASE_CONFIG;
+use crate::config::{scoped_key, SUPABASE_CONFIG};
 use base64::{engine::general_purpose, Engine as _};
-use gloo::storage::{LocalStorage, Storage};
+use futures::future::{FutureExt, LocalBoxFuture, Shared};
+use gloo::storage::{LocalStorage, SessionStorage, Storage};
 use gloo_net::http::Request;
 use js_sys;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use web_sys::window;
+use sha2::{Digest, Sha256};
+use std::cell::{Cell, RefCell};
+use wasm_bindgen::JsValue;
+use web_sys::{window, UrlSearchParams};
+use yew::Callback;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct User {
     pub id: String,
     pub email: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Session {
     pub access_token: String,
     pub refresh_token: String,
     pub user: User,
 }
 
+/// PKCE 코드 교환(`grant_type=pkce`) 응답
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: String,
+    user: User,
+}
+
+/// 인증 서버 오류 응답. 엔드포인트에 따라 필드 이름이 다릅니다.
+#[derive(Deserialize, Default)]
+struct AuthErrorResponse {
+    #[serde(default, alias = "error")]
+    error_code: Option<String>,
+    #[serde(default, alias = "msg")]
+    error_description: Option<String>,
+}
+
+/// 처리한 인증 메일 링크의 종류
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AuthCallback {
+    /// 로그인 링크나 가입 확인 링크
+    SignedIn,
+    /// 비밀번호 재설정 링크. 새 비밀번호를 정하는 화면으로 보내야 합니다.
+    PasswordRecovery,
+}
+
+/// 여러 곳에서 함께 기다릴 수 있는 토큰 갱신
+type RefreshFuture = Shared<LocalBoxFuture<'static, Result<(), String>>>;
+
+thread_local! {
+    /// 세션이 바뀌면 알림을 받을 곳 (`AuthService::subscribe`)
+    static LISTENERS: RefCell<Vec<(usize, Callback<Option<Session>>)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_LISTENER_ID: Cell<usize> = const { Cell::new(0) };
+    /// 진행 중인 토큰 갱신 (`AuthService::refresh_token`)
+    static REFRESH_IN_FLIGHT: RefCell<Option<RefreshFuture>> = const { RefCell::new(None) };
+}
+
+/// 세션 변경 구독. 버리면 구독이 끝납니다.
+pub struct SessionSubscription {
+    id: usize,
+}
+
+impl Drop for SessionSubscription {
+    fn drop(&mut self) {
+        LISTENERS.with(|listeners| listeners.borrow_mut().retain(|(id, _)| *id != self.id));
+    }
+}
+
 pub struct AuthService;
 
 impl AuthService {
+    /// 세션, code verifier, 비밀번호 재설정 표시는 서버마다 따로 둡니다. (`scoped_key`)
     const SESSION_KEY: &'static str = "quiz_note_session";
-
+    /// 게스트 모드를 골랐는지. 로그인한 뒤에는 계정으로 옮길 게스트 데이터가 남았다는 뜻입니다.
+    const GUEST_KEY: &'static str = "quiz_note_guest";
+    /// Access Token이 만료되기 이만큼(초) 전에 미리 갱신합니다.
+    const REFRESH_MARGIN_SECS: i64 = 60;
+    /// 로그인 링크를 요청할 때 만든 PKCE code verifier. 링크가 새 탭에서 열려도 읽을 수 있게
+    /// LocalStorage에 둡니다. 메일을 요청할 때마다 덮어쓰므로 가장 최근에 받은 메일의 링크만 쓸 수
+    /// 있고, 그 전 메일의 링크는 `bad_code_verifier`로 거절됩니다.
+    const CODE_VERIFIER_KEY: &'static str = "quiz_note_code_verifier";
+    /// 콜백에서 난 오류를 로그인 화면에 보여주기 위해 잠시 담아 둡니다.
+    const CALLBACK_ERROR_KEY: &'static str = "quiz_note_auth_error";
+    /// 비밀번호 재설정 메일을 보냈는지. 링크로 돌아오면 새 비밀번호 설정 화면으로 보냅니다.
+    /// 그 뒤 다른 방법으로 로그인을 시작하면 지웁니다(`forget_password_recovery`).
+    const PASSWORD_RECOVERY_KEY: &'static str = "quiz_note_password_recovery";
+    /// 다음 메일을 보낼 수 있는 시각(ms)
+    const EMAIL_COOLDOWN_KEY: &'static str = "quiz_note_email_cooldown_until";
+    /// 메일을 보낸 뒤 다시 보낼 수 있을 때까지 기다리는 시간
+    pub const EMAIL_COOLDOWN_SECS: u32 = 60;
+    const RATE_LIMIT_PREFIX: &'static str = "요청이 너무 많습니다.";
+    /// Refresh Token이 무효라 다시 로그인해야 하는 갱신 오류 코드
+    const REFRESH_REJECTED_CODES: [&'static str; 4] =
+        ["invalid_grant", "refresh_token_not_found", "refresh_token_already_used", "session_not_found"];
+
+    /// 로그인 메일을 보냅니다. 메일에는 로그인 링크와 6자리 인증 코드가 함께 들어 있어
+    /// 링크를 누르거나(`handle_auth_callback`) 코드를 입력해(`verify_email_code`) 로그인합니다.
     pub async fn sign_in_with_email(email: &str) -> Result<(), String> {
-        let url = format!("{}/auth/v1/otp", SUPABASE_CONFIG.url);
+        Self::check_email_cooldown()?;
+        Self::forget_password_recovery();
+        let body = serde_json::json!({
+            "email": email,
+            "create_user": true,
+            "code_challenge": Self::create_code_challenge(),
+            "code_challenge_method": "s256",
+        });
+        Self::send_email_request("otp", body).await
+    }
 
-        // 현재 페이지 URL을 기반으로 리디렉션 URL을 동적으로 생성
-        let location = window().unwrap().location();
-        let redirect_url = format!(
-            "{}//{}",
-            location.protocol().unwrap(),
-            location.host().unwrap()
-        );
+    /// 메일로 받은 6자리 코드로 로그인합니다. 메일을 앱과 다른 브라우저에서 열었을 때 씁니다.
+    pub async fn verify_email_code(email: &str, code: &str) -> Result<(), String> {
+        Self::forget_password_recovery();
+        let body = serde_json::json!({ "type": "email", "email": email, "token": code.trim() });
+        let response = Self::post("verify", body).await?;
+        Self::save_session(Self::session_from(response)?);
+        Ok(())
+    }
 
-        web_sys::console::log_1(&format!("Email redirect URL: {}", redirect_url).into());
+    pub async fn sign_in_with_password(email: &str, password: &str) -> Result<(), String> {
+        Self::forget_password_recovery();
+        let body = serde_json::json!({ "email": email, "password": password });
+        let response = Self::post("token?grant_type=password", body).await?;
+        Self::save_session(Self::session_from(response)?);
+        Ok(())
+    }
 
+    /// 비밀번호로 가입합니다. 이메일 확인이 꺼져 있으면 바로 로그인하고 `true`를,
+    /// 확인 메일을 보냈으면 `false`를 돌려줍니다.
+    pub async fn sign_up_with_password(email: &str, password: &str) -> Result<bool, String> {
+        Self::check_email_cooldown()?;
+        Self::forget_password_recovery();
         let body = serde_json::json!({
             "email": email,
-            "options": {
-                "emailRedirectTo": redirect_url,
-            }
+            "password": password,
+            "code_challenge": Self::create_code_challenge(),
+            "code_challenge_method": "s256",
+        });
+        let response = Self::post(&format!("signup?redirect_to={}", Self::redirect_url()), body).await;
+        let response = Self::track_email_rate_limit(response)?;
+        if response.get("access_token").is_some() {
+            Self::save_session(Self::session_from(response)?);
+            Ok(true)
+        } else {
+            Self::start_email_cooldown(Self::EMAIL_COOLDOWN_SECS);
+            Ok(false)
+        }
+    }
+
+    /// 비밀번호 재설정 메일을 보냅니다. 링크로 돌아오면 새 비밀번호 설정 화면으로 갑니다.
+    pub async fn request_password_reset(email: &str) -> Result<(), String> {
+        Self::check_email_cooldown()?;
+        let body = serde_json::json!({
+            "email": email,
+            "code_challenge": Self::create_code_challenge(),
+            "code_challenge_method": "s256",
         });
+        Self::send_email_request("recover", body).await?;
+        let _ = LocalStorage::set(scoped_key(Self::PASSWORD_RECOVERY_KEY), true);
+        Ok(())
+    }
 
-        let response = Request::post(&url)
+    /// 재설정 메일을 보낸 뒤 다른 방법으로 로그인을 시작하면, 나중에 받은 로그인 링크가
+    /// 새 비밀번호 설정 화면으로 가지 않도록 재설정 표시를 지웁니다.
+    fn forget_password_recovery() {
+        LocalStorage::delete(scoped_key(Self::PASSWORD_RECOVERY_KEY));
+    }
+
+    /// 로그인한 사용자의 비밀번호를 바꿉니다. (재설정 링크로 로그인한 뒤에도 씁니다)
+    pub async fn update_password(password: &str) -> Result<(), String> {
+        if Self::is_token_expired() {
+            Self::refresh_token().await?;
+        }
+        let session = Self::get_session().ok_or_else(|| "로그인이 필요합니다".to_string())?;
+        let url = format!("{}/auth/v1/user", SUPABASE_CONFIG.url);
+        let response = Request::put(&url)
             .header("apikey", SUPABASE_CONFIG.anon_key)
+            .header("Authorization", &format!("Bearer {}", session.access_token))
             .header("Content-Type", "application/json")
-            .json(&body)
+            .json(&serde_json::json!({ "password": password }))
             .map_err(|e| e.to_string())?
             .send()
             .await
@@ -56,15 +192,89 @@ impl AuthService {
         if response.ok() {
             Ok(())
         } else {
-            let error = response
-                .text()
-                .await
-                .unwrap_or_else(|_| "로그인 요청 실패".to_string());
-            web_sys::console::error_1(&format!("Supabase error: {}", error).into());
-            Err(error)
+            let status = response.status();
+            Err(Self::error_message(status, &response.text().await.unwrap_or_default()))
+        }
+    }
+
+    /// 메일을 다시 보낼 수 있을 때까지 남은 초
+    pub fn email_cooldown() -> u32 {
+        let until: f64 = LocalStorage::get(Self::EMAIL_COOLDOWN_KEY).unwrap_or(0.0);
+        ((until - js_sys::Date::now()) / 1000.0).ceil().max(0.0) as u32
+    }
+
+    fn check_email_cooldown() -> Result<(), String> {
+        match Self::email_cooldown() {
+            0 => Ok(()),
+            seconds => Err(format!("메일은 {}초 후에 다시 보낼 수 있습니다.", seconds)),
+        }
+    }
+
+    fn start_email_cooldown(seconds: u32) {
+        let until = js_sys::Date::now() + seconds as f64 * 1000.0;
+        let _ = LocalStorage::set(Self::EMAIL_COOLDOWN_KEY, until);
+    }
+
+    /// 메일을 보내는 요청. 성공하면 재전송 대기 시간을 시작합니다.
+    async fn send_email_request(endpoint: &str, body: serde_json::Value) -> Result<(), String> {
+        let path = format!("{}?redirect_to={}", endpoint, Self::redirect_url());
+        Self::track_email_rate_limit(Self::post(&path, body).await)?;
+        Self::start_email_cooldown(Self::EMAIL_COOLDOWN_SECS);
+        Ok(())
+    }
+
+    /// 서버가 메일 전송 횟수 제한을 알려 오면 그 시간만큼 재전송을 막습니다.
+    fn track_email_rate_limit(result: Result<serde_json::Value, String>) -> Result<serde_json::Value, String> {
+        if let Err(message) = &result {
+            if message.starts_with(Self::RATE_LIMIT_PREFIX) {
+                let seconds = message
+                    .split(|c: char| !c.is_ascii_digit())
+                    .find_map(|part| part.parse().ok())
+                    .unwrap_or(Self::EMAIL_COOLDOWN_SECS);
+                Self::start_email_cooldown(seconds);
+            }
+        }
+        result
+    }
+
+    /// 인증 메일의 링크가 돌아올 주소 (URL 인코딩됨)
+    fn redirect_url() -> String {
+        let location = window().unwrap().location();
+        let url = format!("{}{}", location.origin().unwrap(), location.pathname().unwrap_or_default());
+        js_sys::encode_uri_component(&url).into()
+    }
+
+    /// `/auth/v1/<path>`에 JSON을 보내고 응답 본문을 돌려줍니다. 실패하면 사용자에게 보여줄 문장을 돌려줍니다.
+    async fn post(path: &str, body: serde_json::Value) -> Result<serde_json::Value, String> {
+        let url = format!("{}/auth/v1/{}", SUPABASE_CONFIG.url, path);
+        let response = Request::post(&url)
+            .header("apikey", SUPABASE_CONFIG.anon_key)
+            .header("Content-Type", "application/json")
+            .json(&body)
+            .map_err(|e| e.to_string())?
+            .send()
+            .await
+            .map_err(|_| "인증 서버에 연결할 수 없습니다. 네트워크를 확인해주세요.".to_string())?;
+
+        let status = response.status();
+        let text = response.text().await.unwrap_or_default();
+        if (200..300).contains(&status) {
+            Ok(serde_json::from_str(&text).unwrap_or(serde_json::Value::Null))
+        } else {
+            web_sys::console::error_1(&format!("Supabase auth error: {}", text).into());
+            Err(Self::error_message(status, &text))
         }
     }
 
+    fn session_from(response: serde_json::Value) -> Result<Session, String> {
+        let token: TokenResponse = serde_json::from_value(response).map_err(|e| e.to_string())?;
+        Ok(Session {
+            access_token: token.access_token,
+            refresh_token: token.refresh_token,
+            user: token.user,
+        })
+    }
+
     pub async fn sign_out() -> Result<(), String> {
         if let Some(session) = Self::get_session() {
             let url = format!("{}/auth/v1/logout", SUPABASE_CONFIG.url);
@@ -75,85 +285,258 @@ impl AuthService {
                 .await;
         }
 
-        LocalStorage::delete(Self::SESSION_KEY);
-        // 페이지를 새로고침하여 로그인 페이지로 리디렉션
-        window().unwrap().location().reload().unwrap();
+        Self::clear_session();
         Ok(())
     }
 
+    /// 모든 기기의 세션을 끝냅니다(`scope=global`). 이 브라우저의 Access Token은 만료될 때까지 쓸 수
+    /// 있지만 더는 갱신할 수 없으므로, 할 일을 마치면 `clear_session`으로 지웁니다.
+    pub async fn revoke_all_sessions() -> Result<(), String> {
+        let session = Self::get_session().ok_or_else(|| "로그인이 필요합니다".to_string())?;
+        let url = format!("{}/auth/v1/logout?scope=global", SUPABASE_CONFIG.url);
+        let response = Request::post(&url)
+            .header("apikey", SUPABASE_CONFIG.anon_key)
+            .header("Authorization", &format!("Bearer {}", session.access_token))
+            .send()
+            .await
+            .map_err(|_| "인증 서버에 연결할 수 없습니다. 네트워크를 확인해주세요.".to_string())?;
+
+        if response.ok() {
+            Ok(())
+        } else {
+            let status = response.status();
+            Err(Self::error_message(status, &response.text().await.unwrap_or_default()))
+        }
+    }
+
+    /// 세션을 저장하고 구독자(`AuthProvider`)에게 알립니다.
     pub fn save_session(session: Session) {
-        let _ = LocalStorage::set(Self::SESSION_KEY, session);
+        let _ = LocalStorage::set(scoped_key(Self::SESSION_KEY), &session);
+        Self::notify(Some(session));
     }
 
-    pub fn get_session() -> Option<Session> {
-        LocalStorage::get(Self::SESSION_KEY).ok()
+    /// 서버에 알리지 않고 이 브라우저의 세션만 지우고 구독자에게 알립니다.
+    pub fn clear_session() {
+        LocalStorage::delete(scoped_key(Self::SESSION_KEY));
+        Self::notify(None);
+    }
+
+    /// 로그인하지 않고 이 브라우저에만 저장하며 씁니다. (`GuestStore`)
+    pub fn start_guest() {
+        let _ = LocalStorage::set(Self::GUEST_KEY, true);
+        Self::notify(Self::get_session());
+    }
+
+    /// 로그인하지 않은 게스트 모드인지
+    pub fn is_guest() -> bool {
+        Self::guest_flag() && Self::get_session().is_none()
+    }
+
+    /// 게스트로 쓰다가 로그인해, 아직 계정으로 옮기지 않은 게스트 데이터가 있는지
+    pub fn has_pending_guest_data() -> bool {
+        Self::guest_flag() && Self::get_session().is_some()
+    }
+
+    /// 게스트 데이터를 계정으로 옮긴 뒤 부릅니다.
+    pub fn finish_guest_migration() {
+        LocalStorage::delete(Self::GUEST_KEY);
+        Self::notify(Self::get_session());
     }
 
-    pub fn is_authenticated() -> bool {
-        !Self::is_token_expired()
+    fn guest_flag() -> bool {
+        LocalStorage::get(Self::GUEST_KEY).unwrap_or(false)
+    }
+
+    /// 로그인·토큰 갱신·로그아웃·게스트 모드 전환으로 세션이 바뀔 때마다 `callback`을 부릅니다.
+    pub fn subscribe(callback: Callback<Option<Session>>) -> SessionSubscription {
+        let id = NEXT_LISTENER_ID.with(|next| {
+            let id = next.get();
+            next.set(id + 1);
+            id
+        });
+        LISTENERS.with(|listeners| listeners.borrow_mut().push((id, callback)));
+        SessionSubscription { id }
+    }
+
+    fn notify(session: Option<Session>) {
+        // 알림을 받은 쪽이 구독을 바꿀 수 있으므로 목록을 복사해 둡니다.
+        let callbacks: Vec<_> = LISTENERS.with(|listeners| {
+            listeners.borrow().iter().map(|(_, callback)| callback.clone()).collect()
+        });
+        for callback in callbacks {
+            callback.emit(session.clone());
+        }
+    }
+
+    pub fn get_session() -> Option<Session> {
+        LocalStorage::get(scoped_key(Self::SESSION_KEY)).ok()
     }
 
     pub fn get_current_user() -> Option<User> {
         Self::get_session().map(|s| s.user)
     }
 
-    pub async fn handle_auth_callback() -> Result<bool, String> {
+    /// 로그인 링크로 돌아왔을 때 `?code=`를 세션으로 바꿉니다. 처리할 콜백이 없으면 `Ok(None)`.
+    /// 인증 서버가 보낸 오류(`error_description`)는 사용자에게 보여줄 문장으로 바꿔 돌려주고,
+    /// 로그인 화면에서 `take_callback_error`로 꺼낼 수 있게 남겨 둡니다.
+    pub async fn handle_auth_callback() -> Result<Option<AuthCallback>, String> {
         let location = window().unwrap().location();
+        let search = location.search().unwrap_or_default();
         let hash = location.hash().unwrap_or_default();
+        let query = UrlSearchParams::new_with_str(&search).map_err(|_| "잘못된 주소입니다.".to_string())?;
+        // 오류는 쿼리로 오지만, 일부 설정에서는 `#error=...` 해시로 옵니다.
+        let hash_error = hash
+            .strip_prefix('#')
+            .filter(|fragment| fragment.starts_with("error"))
+            .and_then(|fragment| UrlSearchParams::new_with_str(fragment).ok());
+
+        let error_params = [Some(&query), hash_error.as_ref()]
+            .into_iter()
+            .flatten()
+            .find(|params| params.has("error") || params.has("error_description"));
+        if let Some(params) = error_params {
+            let message = Self::auth_error_message(
+                params.get("error_code").or_else(|| params.get("error")).as_deref(),
+                params.get("error_description").as_deref(),
+            );
+            Self::clear_callback_url(hash_error.is_some());
+            let _ = SessionStorage::set(Self::CALLBACK_ERROR_KEY, &message);
+            return Err(message);
+        }
 
-        if !hash.contains("access_token") {
-            return Ok(false);
+        let Some(code) = query.get("code") else {
+            return Ok(None);
+        };
+        // 주소에 남은 코드는 한 번 쓰면 무효이므로 교환 결과와 상관없이 지웁니다.
+        Self::clear_callback_url(false);
+
+        match Self::exchange_code(&code).await {
+            Ok(session) => {
+                Self::save_session(session);
+                if LocalStorage::get::<bool>(scoped_key(Self::PASSWORD_RECOVERY_KEY)).unwrap_or(false) {
+                    LocalStorage::delete(scoped_key(Self::PASSWORD_RECOVERY_KEY));
+                    Ok(Some(AuthCallback::PasswordRecovery))
+                } else {
+                    Ok(Some(AuthCallback::SignedIn))
+                }
+            }
+            Err(message) => {
+                let _ = SessionStorage::set(Self::CALLBACK_ERROR_KEY, &message);
+                Err(message)
+            }
         }
+    }
 
-        let params: std::collections::HashMap<String, String> = hash
-            .trim_start_matches('#')
-            .split('&')
-            .filter_map(|pair| {
-                let mut parts = pair.split('=');
-                Some((
-                    parts.next()?.to_string(),
-                    parts.next()?.replace("%23", "#").to_string(), // Handle encoded '#'
-                ))
-            })
-            .collect();
+    /// 콜백 처리 중 생긴 오류 메시지를 한 번만 꺼냅니다.
+    pub fn take_callback_error() -> Option<String> {
+        let message = SessionStorage::get(Self::CALLBACK_ERROR_KEY).ok();
+        SessionStorage::delete(Self::CALLBACK_ERROR_KEY);
+        message
+    }
 
-        if let (Some(access_token), Some(refresh_token)) =
-            (params.get("access_token"), params.get("refresh_token"))
-        {
-            let user = Self::get_user_info(access_token).await?;
-            let session = Session {
-                access_token: access_token.clone(),
-                refresh_token: refresh_token.clone(),
-                user,
-            };
-            Self::save_session(session);
+    async fn exchange_code(code: &str) -> Result<Session, String> {
+        let verifier: String = LocalStorage::get(scoped_key(Self::CODE_VERIFIER_KEY)).map_err(|_| {
+            "로그인 링크를 요청한 브라우저에서 링크를 열어주세요. 다른 브라우저에서는 로그인을 마칠 수 없습니다.".to_string()
+        })?;
 
-            // URL에서 토큰 정보 제거
-            location.set_hash("").unwrap();
-            return Ok(true);
-        }
-
-        Ok(false)
+        let body = serde_json::json!({ "auth_code": code, "code_verifier": verifier });
+        let response = Self::post("token?grant_type=pkce", body).await?;
+        LocalStorage::delete(scoped_key(Self::CODE_VERIFIER_KEY));
+        Self::session_from(response)
     }
 
-    async fn get_user_info(access_token: &str) -> Result<User, String> {
-        let url = format!("{}/auth/v1/user", SUPABASE_CONFIG.url);
-        let response = Request::get(&url)
-            .header("apikey", SUPABASE_CONFIG.anon_key)
-            .header("Authorization", &format!("Bearer {}", access_token))
-            .send()
-            .await
-            .map_err(|e| e.to_string())?;
+    /// 인증 서버 오류 응답을 사용자에게 보여줄 문장으로 바꿉니다.
+    fn error_message(status: u16, body: &str) -> String {
+        let error: AuthErrorResponse = serde_json::from_str(body).unwrap_or_default();
+        let code = error.error_code.as_deref().or(if status == 429 { Some("over_request_rate_limit") } else { None });
+        Self::auth_error_message(code, error.error_description.as_deref())
+    }
 
-        if !response.ok() {
-            return Err("Failed to get user info".to_string());
+    fn auth_error_message(code: Option<&str>, description: Option<&str>) -> String {
+        match code {
+            Some("otp_expired") => "로그인 링크나 인증 코드가 만료되었거나 이미 사용되었습니다. 새로 요청해주세요.".to_string(),
+            Some("bad_code_verifier") => {
+                "이전에 받은 메일의 링크입니다. 이 브라우저에서 가장 최근에 요청한 메일의 링크를 열어주세요.".to_string()
+            }
+            Some("flow_state_not_found" | "flow_state_expired") => {
+                "로그인 요청이 만료되었습니다. 이 브라우저에서 새 링크를 요청해주세요.".to_string()
+            }
+            Some("invalid_credentials") => "이메일 또는 비밀번호가 올바르지 않습니다.".to_string(),
+            Some("invalid_grant") if description.is_some_and(|d| d.contains("credentials")) => {
+                "이메일 또는 비밀번호가 올바르지 않습니다.".to_string()
+            }
+            Some("email_not_confirmed") => "이메일 확인이 끝나지 않았습니다. 가입할 때 받은 메일의 링크를 눌러주세요.".to_string(),
+            Some("user_already_exists" | "email_exists") => {
+                "이미 가입된 이메일입니다. 로그인하거나 비밀번호를 재설정해주세요.".to_string()
+            }
+            Some("weak_password") => "비밀번호가 너무 약합니다. 더 길고 추측하기 어려운 비밀번호를 써주세요.".to_string(),
+            Some("same_password") => "지금과 다른 비밀번호를 입력해주세요.".to_string(),
+            Some("over_email_send_rate_limit" | "over_request_rate_limit" | "over_sms_send_rate_limit") => {
+                // 서버 메시지에 대기 시간이 있으면 함께 알려줍니다. ("... after 43 seconds.")
+                let seconds = description.and_then(|d| {
+                    d.split(|c: char| !c.is_ascii_digit()).find_map(|part| part.parse::<u32>().ok())
+                });
+                match seconds {
+                    Some(seconds) => format!("{} {}초 후에 다시 시도해주세요.", Self::RATE_LIMIT_PREFIX, seconds),
+                    None => format!("{} 잠시 후에 다시 시도해주세요.", Self::RATE_LIMIT_PREFIX),
+                }
+            }
+            Some("access_denied") if description.is_none() => "로그인이 거부되었습니다.".to_string(),
+            _ => match description {
+                Some(description) if !description.is_empty() => format!("인증 요청에 실패했습니다: {}", description),
+                _ => "인증 요청에 실패했습니다. 다시 시도해주세요.".to_string(),
+            },
         }
+    }
 
-        response.json::<User>().await.map_err(|e| e.to_string())
+    /// 새 code verifier를 만들어 저장하고 S256 challenge를 돌려줍니다. (RFC 7636)
+    fn create_code_challenge() -> String {
+        const UNRESERVED: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-._~";
+        let mut rng = rand::thread_rng();
+        let verifier: String = (0..64)
+            .map(|_| UNRESERVED[rng.gen_range(0..UNRESERVED.len())] as char)
+            .collect();
+        let _ = LocalStorage::set(scoped_key(Self::CODE_VERIFIER_KEY), &verifier);
+        general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
     }
 
+    /// 주소창에서 콜백 파라미터를 지웁니다. 기록에 남지 않도록 현재 항목을 바꿔 씁니다.
+    fn clear_callback_url(clear_hash: bool) {
+        let window = window().unwrap();
+        let location = window.location();
+        let hash = if clear_hash { String::new() } else { location.hash().unwrap_or_default() };
+        let url = format!("{}{}", location.pathname().unwrap_or_default(), hash);
+        if let Ok(history) = window.history() {
+            let _ = history.replace_state_with_url(&JsValue::NULL, "", Some(&url));
+        }
+    }
+
+    /// Refresh Token으로 새 Access Token을 받습니다. 동시에 여러 요청이 갱신하려 해도 서버에는
+    /// 한 번만 보내고 모두 그 결과를 기다립니다. 같은 Refresh Token을 두 번 쓰면 서버가 세션을 끊습니다.
     pub async fn refresh_token() -> Result<(), String> {
+        let refresh = REFRESH_IN_FLIGHT.with(|in_flight| {
+            in_flight
+                .borrow_mut()
+                .get_or_insert_with(|| {
+                    async {
+                        let result = Self::request_refresh().await;
+                        REFRESH_IN_FLIGHT.with(|in_flight| in_flight.borrow_mut().take());
+                        result
+                    }
+                    .boxed_local()
+                    .shared()
+                })
+                .clone()
+        });
+        refresh.await
+    }
+
+    async fn request_refresh() -> Result<(), String> {
         let session = Self::get_session().ok_or("No session found")?;
+        // 다른 탭이 이미 갱신해 LocalStorage의 토큰이 바뀌었으면 보낼 필요가 없습니다.
+        if Self::refresh_delay_ms(&session) > 0 {
+            return Ok(());
+        }
         let url = format!(
             "{}/auth/v1/token?grant_type=refresh_token",
             SUPABASE_CONFIG.url
@@ -184,36 +567,58 @@ impl AuthService {
             } else {
                 Err("Failed to parse token refresh response".to_string())
             }
+        } else if Self::get_session().is_some_and(|current| current.refresh_token != session.refresh_token) {
+            // 그사이 다른 탭이 같은 토큰으로 먼저 갱신했습니다. 그 세션을 그대로 씁니다.
+            Self::notify(Self::get_session());
+            Ok(())
         } else {
-            LocalStorage::delete(Self::SESSION_KEY);
-            Err("Session expired. Please log in again.".to_string())
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            let error: AuthErrorResponse = serde_json::from_str(&text).unwrap_or_default();
+            // 서버가 Refresh Token을 거절했을 때만 로그아웃합니다. 서버 오류나 횟수 제한(5xx, 429)은
+            // 세션을 그대로 두고 오류를 돌려줘 호출한 쪽이 다시 시도하게 합니다.
+            let rejected = matches!(status, 400 | 401)
+                && error.error_code.as_deref().is_some_and(|code| Self::REFRESH_REJECTED_CODES.contains(&code));
+            if rejected {
+                Self::clear_session();
+                Err("Session expired. Please log in again.".to_string())
+            } else {
+                Err(format!("Token refresh failed ({}): {}", status, text))
+            }
         }
     }
 
-    pub fn is_token_expired() -> bool {
-        let session = match Self::get_session() {
-            Some(s) => s,
-            None => return true,
+    /// 만료 조금 전(`REFRESH_MARGIN_SECS`)에 미리 갱신하려면 몇 ms 뒤에 해야 하는지. 이미 지났으면 0
+    pub fn refresh_delay_ms(session: &Session) -> u32 {
+        let Some(exp) = Self::token_expires_at(&session.access_token) else {
+            return 0;
         };
+        let delay_ms = (exp - Self::REFRESH_MARGIN_SECS) as f64 * 1000.0 - js_sys::Date::now();
+        delay_ms.clamp(0.0, u32::MAX as f64) as u32
+    }
 
-        let parts: Vec<&str> = session.access_token.split('.').collect();
-        if parts.len() != 3 {
-            return true;
+    /// 다른 탭에서 로그인·로그아웃·토큰 갱신이나 게스트 모드 전환으로 세션이 바뀌었을 때(`storage` 이벤트)
+    /// 부릅니다. `key`가 `None`이면 LocalStorage 전체가 지워진 것입니다.
+    pub fn handle_storage_change(key: Option<&str>) {
+        if key.is_none_or(|key| key == scoped_key(Self::SESSION_KEY) || key == Self::GUEST_KEY) {
+            Self::notify(Self::get_session());
         }
+    }
 
-        let payload = match general_purpose::URL_SAFE_NO_PAD.decode(parts[1]) {
-            Ok(p) => p,
-            Err(_) => return true,
-        };
-
-        let claims: serde_json::Value = match serde_json::from_slice(&payload) {
-            Ok(c) => c,
-            Err(_) => return true,
-        };
+    pub fn is_token_expired() -> bool {
+        Self::get_session()
+            .and_then(|session| Self::token_expires_at(&session.access_token))
+            .is_none_or(|exp| (js_sys::Date::now() / 1000.0) as i64 > exp)
+    }
 
-        claims["exp"]
-            .as_i64()
-            .map(|exp| (js_sys::Date::now() / 1000.0) as i64 > exp)
-            .unwrap_or(true)
+    /// JWT의 `exp`(초)
+    fn token_expires_at(access_token: &str) -> Option<i64> {
+        let parts: Vec<&str> = access_token.split('.').collect();
+        if parts.len() != 3 {
+            return None;
+        }
+        let payload = general_purpose::URL_SAFE_NO_PAD.decode(parts[1]).ok()?;
+        let claims: serde_json::Value = serde_json::from_slice(&payload).ok()?;
+        claims["exp"].as_i64()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,244 @@
+// 암호로 보호된 백업 파일
+//
+// 암호에서 Argon2id로 64바이트를 유도해 앞 32바이트는 AES-256-GCM 키로, 뒤 32바이트 중
+// 16바이트는 암호 확인값으로 씁니다. 확인값 덕분에 "암호가 틀림"과 "파일이 변조됨"을
+// 구분해서 알려줄 수 있습니다. (확인값을 맞춰 보는 데도 Argon2 계산이 필요하므로
+// 암호 추측 비용은 그대로입니다.)
+//
+// 파일은 여전히 JSON이며 `format` 필드로 일반 백업과 구분합니다.
+
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose, Engine as _};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+
+const FORMAT: &str = "quiznote-encrypted";
+const VERSION: u32 = 1;
+const KDF_NAME: &str = "argon2id";
+const CIPHER_NAME: &str = "aes-256-gcm";
+/// 헤더를 바꿔치기한 파일도 복호화에 실패하도록 연관 데이터로 묶습니다.
+const ASSOCIATED_DATA: &[u8] = b"quiznote-encrypted:v1";
+
+// OWASP 권장 최소값 (19 MiB, 2회). 브라우저에서도 1초 안팎으로 끝납니다.
+const MEMORY_KIB: u32 = 19_456;
+const ITERATIONS: u32 = 2;
+const PARALLELISM: u32 = 1;
+/// 복호화할 때 파일 헤더의 계산 비용을 위 값의 몇 배까지 받아들일지
+const MAX_COST_FACTOR: u32 = 4;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const CHECK_LEN: usize = 16;
+
+#[derive(Serialize, Deserialize)]
+struct EncryptedBackup {
+    format: String,
+    version: u32,
+    kdf: KdfParams,
+    cipher: CipherParams,
+    ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParams {
+    name: String,
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+    salt: String,
+    check: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParams {
+    name: String,
+    nonce: String,
+}
+
+pub struct BackupCrypto;
+
+impl BackupCrypto {
+    /// 암호화된 백업 파일인지 확인합니다. (복호화는 하지 않습니다)
+    pub fn is_encrypted(text: &str) -> bool {
+        #[derive(Deserialize)]
+        struct Probe {
+            format: Option<String>,
+        }
+        matches!(
+            serde_json::from_str::<Probe>(text),
+            Ok(Probe { format: Some(format) }) if format == FORMAT
+        )
+    }
+
+    pub fn encrypt(plaintext: &[u8], passphrase: &str) -> Result<String, String> {
+        if passphrase.is_empty() {
+            return Err("암호를 입력해주세요.".to_string());
+        }
+
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut nonce);
+
+        let (key, check) = Self::derive(passphrase, &salt, MEMORY_KIB, ITERATIONS, PARALLELISM)?;
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), Payload { msg: plaintext, aad: ASSOCIATED_DATA })
+            .map_err(|_| "암호화에 실패했습니다.".to_string())?;
+
+        let backup = EncryptedBackup {
+            format: FORMAT.to_string(),
+            version: VERSION,
+            kdf: KdfParams {
+                name: KDF_NAME.to_string(),
+                memory_kib: MEMORY_KIB,
+                iterations: ITERATIONS,
+                parallelism: PARALLELISM,
+                salt: general_purpose::STANDARD.encode(salt),
+                check: general_purpose::STANDARD.encode(check),
+            },
+            cipher: CipherParams {
+                name: CIPHER_NAME.to_string(),
+                nonce: general_purpose::STANDARD.encode(nonce),
+            },
+            ciphertext: general_purpose::STANDARD.encode(ciphertext),
+        };
+        serde_json::to_string_pretty(&backup).map_err(|e| e.to_string())
+    }
+
+    pub fn decrypt(text: &str, passphrase: &str) -> Result<Vec<u8>, String> {
+        const DAMAGED: &str = "암호화된 백업 파일이 손상되었거나 변조되었습니다.";
+
+        let backup: EncryptedBackup = serde_json::from_str(text).map_err(|_| DAMAGED.to_string())?;
+        if backup.format != FORMAT || backup.kdf.name != KDF_NAME || backup.cipher.name != CIPHER_NAME {
+            return Err("지원하지 않는 암호화 방식입니다.".to_string());
+        }
+        if backup.version > VERSION {
+            return Err("더 새로운 버전의 QuizNote에서 만든 백업입니다.".to_string());
+        }
+        // 조작된 헤더로 브라우저가 멈추거나 메모리가 모자라 탭이 죽지 않도록, 이 앱이 쓰는 값의
+        // 몇 배까지만 받습니다. 변조 여부는 키를 유도한 뒤에야 알 수 있기 때문입니다.
+        if backup.kdf.memory_kib > MEMORY_KIB * MAX_COST_FACTOR
+            || backup.kdf.iterations > ITERATIONS * MAX_COST_FACTOR
+            || backup.kdf.parallelism > PARALLELISM * MAX_COST_FACTOR
+        {
+            return Err(DAMAGED.to_string());
+        }
+
+        let decode = |value: &str| general_purpose::STANDARD.decode(value).map_err(|_| DAMAGED.to_string());
+        let salt = decode(&backup.kdf.salt)?;
+        let check = decode(&backup.kdf.check)?;
+        let nonce = decode(&backup.cipher.nonce)?;
+        let ciphertext = decode(&backup.ciphertext)?;
+        if nonce.len() != NONCE_LEN {
+            return Err(DAMAGED.to_string());
+        }
+
+        let (key, expected_check) = Self::derive(
+            passphrase,
+            &salt,
+            backup.kdf.memory_kib,
+            backup.kdf.iterations,
+            backup.kdf.parallelism,
+        )?;
+        if expected_check[..] != check[..] {
+            return Err("암호가 올바르지 않습니다.".to_string());
+        }
+
+        let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key));
+        cipher
+            .decrypt(Nonce::from_slice(&nonce), Payload { msg: &ciphertext, aad: ASSOCIATED_DATA })
+            .map_err(|_| DAMAGED.to_string())
+    }
+
+    /// (암호화 키, 암호 확인값)
+    fn derive(
+        passphrase: &str,
+        salt: &[u8],
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    ) -> Result<([u8; 32], [u8; CHECK_LEN]), String> {
+        let params = Params::new(memory_kib, iterations, parallelism, Some(64))
+            .map_err(|e| format!("암호화 설정이 올바르지 않습니다: {}", e))?;
+        let mut output = [0u8; 64];
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password_into(passphrase.as_bytes(), salt, &mut output)
+            .map_err(|e| format!("암호에서 키를 만들 수 없습니다: {}", e))?;
+
+        let mut key = [0u8; 32];
+        let mut check = [0u8; CHECK_LEN];
+        key.copy_from_slice(&output[..32]);
+        check.copy_from_slice(&output[32..32 + CHECK_LEN]);
+        Ok((key, check))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSPHRASE: &str = "올바른 암호";
+    const PLAINTEXT: &[u8] = b"[{\"name\":\"QuizNote\"}]";
+
+    fn edit(text: &str, change: impl FnOnce(&mut serde_json::Value)) -> String {
+        let mut value: serde_json::Value = serde_json::from_str(text).unwrap();
+        change(&mut value);
+        value.to_string()
+    }
+
+    #[test]
+    fn round_trip() {
+        let text = BackupCrypto::encrypt(PLAINTEXT, PASSPHRASE).unwrap();
+        assert!(BackupCrypto::is_encrypted(&text));
+        assert_eq!(BackupCrypto::decrypt(&text, PASSPHRASE).unwrap(), PLAINTEXT);
+    }
+
+    #[test]
+    fn wrong_passphrase_is_reported() {
+        let text = BackupCrypto::encrypt(PLAINTEXT, PASSPHRASE).unwrap();
+        assert_eq!(BackupCrypto::decrypt(&text, "틀린 암호").unwrap_err(), "암호가 올바르지 않습니다.");
+    }
+
+    #[test]
+    fn modified_ciphertext_is_rejected() {
+        let text = BackupCrypto::encrypt(PLAINTEXT, PASSPHRASE).unwrap();
+        let tampered = edit(&text, |value| {
+            let mut bytes = general_purpose::STANDARD.decode(value["ciphertext"].as_str().unwrap()).unwrap();
+            bytes[0] ^= 1;
+            value["ciphertext"] = general_purpose::STANDARD.encode(bytes).into();
+        });
+        let error = BackupCrypto::decrypt(&tampered, PASSPHRASE).unwrap_err();
+        assert!(error.contains("변조"), "{}", error);
+    }
+
+    #[test]
+    fn modified_header_is_rejected() {
+        let text = BackupCrypto::encrypt(PLAINTEXT, PASSPHRASE).unwrap();
+        let tampered = edit(&text, |value| {
+            let mut nonce = general_purpose::STANDARD.decode(value["cipher"]["nonce"].as_str().unwrap()).unwrap();
+            nonce[0] ^= 1;
+            value["cipher"]["nonce"] = general_purpose::STANDARD.encode(nonce).into();
+        });
+        assert!(BackupCrypto::decrypt(&tampered, PASSPHRASE).unwrap_err().contains("변조"));
+
+        let tampered = edit(&text, |value| value["kdf"]["iterations"] = (ITERATIONS + 1).into());
+        assert!(BackupCrypto::decrypt(&tampered, PASSPHRASE).is_err());
+    }
+
+    #[test]
+    fn excessive_kdf_cost_is_rejected_before_deriving() {
+        let text = BackupCrypto::encrypt(PLAINTEXT, PASSPHRASE).unwrap();
+        for (field, limit) in [
+            ("memory_kib", MEMORY_KIB),
+            ("iterations", ITERATIONS),
+            ("parallelism", PARALLELISM),
+        ] {
+            let tampered = edit(&text, |value| value["kdf"][field] = (limit * MAX_COST_FACTOR + 1).into());
+            assert!(BackupCrypto::decrypt(&tampered, PASSPHRASE).unwrap_err().contains("변조"), "{}", field);
+        }
+    }
+}
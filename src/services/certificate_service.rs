@@ -1,30 +1,61 @@
 use crate::models::Certificate;
-use crate::services::SupabaseClient;
+use crate::services::{DataStore, ImageService, SnapshotService};
 
 pub struct CertificateService;
 
 impl CertificateService {
     pub async fn get_all() -> Result<Vec<Certificate>, String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         let mut certs = client.get_all_certificates().await?;
-        certs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        certs.sort_by_key(|c| std::cmp::Reverse(c.created_at));
         Ok(certs)
     }
 
     pub async fn get_by_id(id: &str) -> Result<Certificate, String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         client.get_certificate_by_id(id).await
     }
 
     pub async fn create(name: String, description: String) -> Result<Certificate, String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         let certificate = Certificate::new(name, description);
         client.create_certificate(&certificate).await?;
         Ok(certificate)
     }
 
+    /// 가져오기로 만드는 자격증. 원시 HTML은 가져온 콘텐츠 설정을 따릅니다.
+    pub async fn create_imported(name: String, description: String) -> Result<Certificate, String> {
+        let client = DataStore::current();
+        let mut certificate = Certificate::new(name, description);
+        certificate.imported = true;
+        client.create_certificate(&certificate).await?;
+        Ok(certificate)
+    }
+
+    /// 다른 저장소(게스트 데이터 등)의 자격증을 ID와 설정 그대로 만듭니다.
+    pub async fn create_copy(certificate: &Certificate) -> Result<Certificate, String> {
+        let client = DataStore::current();
+        client.create_certificate(certificate).await?;
+        Ok(certificate.clone())
+    }
+
+    /// 휴지통으로 옮깁니다. TrashService::RETENTION_DAYS가 지나면 영구 삭제됩니다.
     pub async fn delete(id: &str) -> Result<(), String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         client.delete_certificate(id).await
     }
+
+    pub async fn restore(id: &str) -> Result<(), String> {
+        let client = DataStore::current();
+        client.restore_certificate(id).await
+    }
+
+    pub async fn purge(id: &str) -> Result<(), String> {
+        // 영구 삭제는 문제까지 함께 지워지므로 먼저 스냅샷을 남깁니다.
+        SnapshotService::take_before("자격증 영구 삭제 전").await;
+        let client = DataStore::current();
+        client.purge_certificate(id).await?;
+        ImageService::sweep_after_purge().await;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -1,14 +1,19 @@
 // src/services/data_service.rs (새 파일)
 
 use crate::models::{ExportedCertificate, Question};
-use crate::services::{CertificateService, QuestionService};
-use wasm_bindgen_futures::spawn_local;
+use crate::services::markdown_bundle::IMAGE_REF_PREFIX;
+use crate::services::moodle_format::{FormatExport, FormatImport};
+use crate::services::guest_store::GuestStore;
+use crate::services::{
+    AccountService, AikenFormat, AnkiPackage, BackupCrypto, CertificateService, GiftFormat, ImageBundle, ImageService, MarkdownBundle,
+    QtiPackage, QuestionService, SnapshotService,
+};
 
 pub struct DataService;
 
 impl DataService {
-    /// 모든 자격증과 관련 문제들을 JSON 문자열로 내보냅니다.
-    pub async fn export_data() -> Result<String, String> {
+    /// 모든 자격증과 관련 문제들을 모읍니다.
+    pub async fn collect_export() -> Result<Vec<ExportedCertificate>, String> {
         let certificates = CertificateService::get_all().await?;
         let mut export_data = Vec::new();
 
@@ -19,45 +24,228 @@ impl DataService {
                 questions,
             });
         }
+        Ok(export_data)
+    }
 
+    /// 모든 자격증과 관련 문제들을 JSON 문자열로 내보냅니다.
+    pub async fn export_data() -> Result<String, String> {
+        let export_data = Self::collect_export().await?;
         serde_json::to_string_pretty(&export_data).map_err(|e| e.to_string())
     }
 
+    /// JSON 백업을 암호로 보호해 내보냅니다.
+    pub async fn export_data_encrypted(passphrase: &str) -> Result<String, String> {
+        let json_str = Self::export_data().await?;
+        BackupCrypto::encrypt(json_str.as_bytes(), passphrase)
+    }
+
+    /// 문제 한 개당 마크다운 파일 한 개로 묶은 zip을 만듭니다. 첨부 이미지도 함께 넣습니다.
+    pub async fn export_markdown_zip() -> Result<Vec<u8>, String> {
+        let export_data = Self::collect_export().await?;
+        let images = Self::collect_images(&export_data).await;
+        MarkdownBundle::write_zip(&export_data, &images)
+    }
+
+    /// Anki에서 바로 가져올 수 있는 덱 패키지(.apkg)를 만듭니다. 첨부 이미지는 미디어로 넣습니다.
+    pub async fn export_anki_package() -> Result<Vec<u8>, String> {
+        let export_data = Self::collect_export().await?;
+        let images = Self::collect_images(&export_data).await;
+        AnkiPackage::build(&export_data, &images)
+    }
+
+    /// LMS로 옮길 수 있는 QTI 2.1 콘텐츠 패키지(zip)를 만듭니다. 첨부 이미지도 함께 넣습니다.
+    pub async fn export_qti_package() -> Result<Vec<u8>, String> {
+        let export_data = Self::collect_export().await?;
+        let images = Self::collect_images(&export_data).await;
+        QtiPackage::build(&export_data, &images)
+    }
+
+    async fn collect_images(export_data: &[ExportedCertificate]) -> ImageBundle {
+        ImageBundle::collect(export_data.iter().flat_map(|c| &c.questions)).await
+    }
+
+    /// Moodle GIFT 형식 텍스트로 내보냅니다.
+    pub async fn export_gift() -> Result<FormatExport, String> {
+        let export_data = Self::collect_export().await?;
+        Ok(GiftFormat::write(&export_data))
+    }
+
+    /// Moodle Aiken 형식 텍스트로 내보냅니다. 정답이 하나인 문제만 포함됩니다.
+    pub async fn export_aiken() -> Result<FormatExport, String> {
+        let export_data = Self::collect_export().await?;
+        Ok(AikenFormat::write(&export_data))
+    }
+
     /// JSON 파일로부터 데이터를 가져와 DB에 저장합니다.
-    pub async fn import_data(json_str: &str) -> Result<String, String> {
-        let imported_data: Vec<ExportedCertificate> =
-            serde_json::from_str(json_str).map_err(|e| e.to_string())?;
+    /// 암호화된 백업이면 `passphrase`로 복호화한 뒤 가져옵니다.
+    pub async fn import_data(json_str: &str, passphrase: Option<&str>) -> Result<String, String> {
+        let decrypted;
+        let json_str = if BackupCrypto::is_encrypted(json_str) {
+            let passphrase = passphrase.ok_or_else(|| "암호화된 백업 파일입니다. 암호를 입력해주세요.".to_string())?;
+            let bytes = BackupCrypto::decrypt(json_str, passphrase)?;
+            decrypted = String::from_utf8(bytes).map_err(|e| e.to_string())?;
+            decrypted.as_str()
+        } else {
+            json_str
+        };
 
-        let total_certs = imported_data.len();
-        let mut created_certs = 0;
-        let mut created_questions = 0;
+        // 계정 내보내기 파일도 받습니다. (`AccountService::export`)
+        let imported_data: Vec<ExportedCertificate> = match AccountService::read_export(json_str)? {
+            Some(certificates) => certificates,
+            None => serde_json::from_str(json_str).map_err(|e| e.to_string())?,
+        };
+        Self::import_certificates(imported_data).await
+    }
 
-        for exported_cert in imported_data {
-            // 새 자격증 생성
-            let new_cert = CertificateService::create(
-                exported_cert.certificate.name,
-                exported_cert.certificate.description,
-            )
-            .await?;
-            created_certs += 1;
-
-            // 해당 자격증에 문제들 추가
-            for var in exported_cert.questions {
-                 let mut question = Question::new(new_cert.id.clone(), var.content.clone());
-                 question.explanation = var.explanation;
-                 question.options = var.options;
-                
-                 // 비동기로 각 문제를 생성
-                spawn_local(async move {
-                    let _ = QuestionService::create(question).await;
-                });
-                created_questions += 1;
+    /// 마크다운 zip 묶음으로부터 데이터를 가져와 DB에 저장합니다.
+    pub async fn import_markdown_zip(bytes: &[u8]) -> Result<String, String> {
+        let mut imported_data = MarkdownBundle::read_zip(bytes)?;
+        let images = MarkdownBundle::read_images(bytes)?;
+        ImageService::restore_bundled(&mut imported_data, &images, IMAGE_REF_PREFIX).await?;
+        Self::import_certificates(imported_data).await
+    }
+
+    /// GIFT(.gift) 또는 Aiken(.txt) 텍스트를 가져옵니다.
+    /// 변환하지 못한 항목은 결과 메시지와 함께 목록으로 반환합니다.
+    pub async fn import_moodle_text(file_name: &str, text: &str) -> Result<(String, Vec<String>), String> {
+        let (base_name, extension) = file_name.rsplit_once('.').unwrap_or((file_name, ""));
+        let parsed: FormatImport = if extension.eq_ignore_ascii_case("gift") || !Self::looks_like_aiken(text) {
+            GiftFormat::parse(text, base_name)
+        } else {
+            AikenFormat::parse(text, base_name)
+        };
+
+        if parsed.certificates.is_empty() {
+            let mut error = "가져올 수 있는 객관식 문제가 없습니다.".to_string();
+            if !parsed.warnings.is_empty() {
+                error.push_str(&format!(" ({})", parsed.warnings.join(" / ")));
             }
+            return Err(error);
         }
-        
+        let message = Self::import_certificates(parsed.certificates).await?;
+        Ok((message, parsed.warnings))
+    }
+
+    /// `ANSWER:` 줄이 있고 GIFT 답안 중괄호가 없으면 Aiken으로 봅니다.
+    fn looks_like_aiken(text: &str) -> bool {
+        text.lines().any(|line| line.trim_start().starts_with("ANSWER:")) && !text.contains('{')
+    }
+
+    async fn import_certificates(imported_data: Vec<ExportedCertificate>) -> Result<String, String> {
+        let summary = Self::import_into(imported_data, &[], ImportSource::File).await?;
         Ok(format!(
             "가져오기 완료! {}개의 자격증과 {}개의 문제가 생성되었습니다. 페이지가 새로고침됩니다.",
-            created_certs, created_questions
+            summary.created_certificates, summary.created_questions
         ))
     }
-}
\ No newline at end of file
+
+    /// 게스트 모드에서 만든 데이터를 로그인한 계정으로 옮기고 게스트 데이터를 지웁니다.
+    /// 계정에 이름이 같은 자격증이 있으면 새로 만들지 않고 그 자격증에 합치며, 내용이 같은 문제는
+    /// 건너뜁니다. 중간에 실패해도 게스트 데이터는 남으므로 다시 시도하면 이어서 옮깁니다.
+    pub async fn migrate_guest_data() -> Result<String, String> {
+        let guest = GuestStore.export().await?;
+        if guest.is_empty() {
+            GuestStore.clear().await?;
+            return Ok(String::new());
+        }
+        SnapshotService::take_before("게스트 데이터 옮기기 전").await;
+        let account = Self::collect_export().await?;
+        let summary = Self::import_into(guest, &account, ImportSource::Guest).await?;
+        GuestStore.clear().await?;
+
+        let mut message = format!(
+            "게스트 데이터를 계정으로 옮겼습니다. 자격증 {}개를 새로 만들고 문제 {}개를 옮겼습니다.",
+            summary.created_certificates, summary.created_questions
+        );
+        if summary.merged_certificates > 0 {
+            message.push_str(&format!(
+                " 이름이 같은 자격증 {}개는 계정의 자격증에 합쳤습니다.",
+                summary.merged_certificates
+            ));
+        }
+        if summary.skipped_questions > 0 {
+            message.push_str(&format!(" 이미 있는 문제 {}개는 건너뛰었습니다.", summary.skipped_questions));
+        }
+        Ok(message)
+    }
+
+    /// 자격증과 문제를 만듭니다. `existing`에 이름이 같은 자격증이 있으면 그 자격증에 합치고,
+    /// 그 자격증에 내용이 같은 문제가 있으면 건너뜁니다.
+    async fn import_into(
+        imported_data: Vec<ExportedCertificate>,
+        existing: &[ExportedCertificate],
+        source: ImportSource,
+    ) -> Result<ImportSummary, String> {
+        let mut summary = ImportSummary::default();
+
+        for exported_cert in imported_data {
+            let name = exported_cert.certificate.name.trim();
+            let target = existing.iter().find(|c| c.certificate.name.trim() == name);
+            let (certificate_id, present) = match target {
+                Some(target) => {
+                    summary.merged_certificates += 1;
+                    (target.certificate.id.clone(), target.questions.as_slice())
+                }
+                None => {
+                    let new_cert = match source {
+                        ImportSource::File => {
+                            CertificateService::create_imported(
+                                exported_cert.certificate.name.clone(),
+                                exported_cert.certificate.description.clone(),
+                            )
+                            .await?
+                        }
+                        // 게스트에서 만든 자격증은 ID와 원시 HTML 설정을 그대로 둡니다.
+                        ImportSource::Guest => CertificateService::create_copy(&exported_cert.certificate).await?,
+                    };
+                    summary.created_certificates += 1;
+                    (new_cert.id, &[][..])
+                }
+            };
+
+            for var in exported_cert.questions {
+                if present.iter().any(|q| SnapshotService::same_question(q, &var)) {
+                    summary.skipped_questions += 1;
+                    continue;
+                }
+                let question = match source {
+                    ImportSource::File => {
+                        let mut question = Question::new(certificate_id.clone(), var.content.clone());
+                        question.explanation = var.explanation;
+                        question.options = var.options;
+                        // 계정 내보내기 파일에만 풀이 기록이 있습니다. 다른 파일은 0이라 그대로입니다.
+                        question.attempt_count = var.attempt_count;
+                        question.correct_count = var.correct_count;
+                        question.last_attempt = var.last_attempt;
+                        question
+                    }
+                    // 풀이 기록을 잃지 않도록 ID와 통계를 그대로 옮깁니다.
+                    ImportSource::Guest => Question { certificate_id: certificate_id.clone(), ..var },
+                };
+                let question = QuestionService::create(question).await?;
+                if question.attempt_count > 0 {
+                    QuestionService::update_stats(&question).await?;
+                }
+                summary.created_questions += 1;
+            }
+        }
+        Ok(summary)
+    }
+}
+
+/// 가져오는 데이터가 어디서 왔는지
+#[derive(Clone, Copy, PartialEq)]
+enum ImportSource {
+    /// 백업·내보내기 파일. 모두 새 자격증과 새 문제로 만들고, 풀이 기록이 있으면 옮깁니다.
+    File,
+    /// 이 브라우저의 게스트 데이터 (`GuestStore`)
+    Guest,
+}
+
+#[derive(Default)]
+struct ImportSummary {
+    created_certificates: usize,
+    merged_certificates: usize,
+    created_questions: usize,
+    skipped_questions: usize,
+}
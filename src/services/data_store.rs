@@ -0,0 +1,158 @@
+// 데이터 저장소 선택
+//
+// 로그인했으면 Supabase(`SupabaseClient`), 게스트 모드면 브라우저의 IndexedDB(`GuestStore`)를 씁니다.
+// 서비스는 `DataStore::current()`로 받아 어느 쪽인지 신경 쓰지 않고 같은 메서드를 부릅니다.
+
+use crate::models::{Certificate, Question};
+use crate::services::guest_store::GuestStore;
+use crate::services::{AuthService, SupabaseClient};
+use chrono::{DateTime, Utc};
+
+#[derive(Clone)]
+pub enum DataStore {
+    Remote(SupabaseClient),
+    Guest(GuestStore),
+}
+
+impl DataStore {
+    pub fn current() -> Self {
+        if AuthService::is_guest() {
+            Self::Guest(GuestStore)
+        } else {
+            Self::Remote(SupabaseClient::new())
+        }
+    }
+
+    pub async fn get_all_certificates(&self) -> Result<Vec<Certificate>, String> {
+        match self {
+            Self::Remote(client) => client.get_all_certificates().await,
+            Self::Guest(store) => store.get_all_certificates().await,
+        }
+    }
+
+    pub async fn get_certificate_by_id(&self, id: &str) -> Result<Certificate, String> {
+        match self {
+            Self::Remote(client) => client.get_certificate_by_id(id).await,
+            Self::Guest(store) => store.get_certificate_by_id(id).await,
+        }
+    }
+
+    pub async fn create_certificate(&self, cert: &Certificate) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.create_certificate(cert).await,
+            Self::Guest(store) => store.create_certificate(cert).await,
+        }
+    }
+
+    pub async fn update_certificate(&self, cert: &Certificate) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.update_certificate(cert).await,
+            Self::Guest(store) => store.update_certificate(cert).await,
+        }
+    }
+
+    pub async fn delete_certificate(&self, id: &str) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.delete_certificate(id).await,
+            Self::Guest(store) => store.delete_certificate(id).await,
+        }
+    }
+
+    pub async fn restore_certificate(&self, id: &str) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.restore_certificate(id).await,
+            Self::Guest(store) => store.restore_certificate(id).await,
+        }
+    }
+
+    pub async fn purge_certificate(&self, id: &str) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.purge_certificate(id).await,
+            Self::Guest(store) => store.purge_certificate(id).await,
+        }
+    }
+
+    pub async fn get_deleted_certificates(&self) -> Result<Vec<Certificate>, String> {
+        match self {
+            Self::Remote(client) => client.get_deleted_certificates().await,
+            Self::Guest(store) => store.get_deleted_certificates().await,
+        }
+    }
+
+    pub async fn get_questions_by_certificate(&self, cert_id: &str) -> Result<Vec<Question>, String> {
+        match self {
+            Self::Remote(client) => client.get_questions_by_certificate(cert_id).await,
+            Self::Guest(store) => store.get_questions_by_certificate(cert_id).await,
+        }
+    }
+
+    pub async fn get_question_by_id(&self, id: &str) -> Result<Question, String> {
+        match self {
+            Self::Remote(client) => client.get_question_by_id(id).await,
+            Self::Guest(store) => store.get_question_by_id(id).await,
+        }
+    }
+
+    pub async fn create_question(&self, question: &Question) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.create_question(question).await,
+            Self::Guest(store) => store.create_question(question).await,
+        }
+    }
+
+    pub async fn update_question(&self, question: &Question) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.update_question(question).await,
+            Self::Guest(store) => store.update_question(question).await,
+        }
+    }
+
+    pub async fn update_question_stats(&self, question: &Question) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.update_question_stats(question).await,
+            Self::Guest(store) => store.update_question_stats(question).await,
+        }
+    }
+
+    pub async fn delete_question(&self, id: &str) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.delete_question(id).await,
+            Self::Guest(store) => store.delete_question(id).await,
+        }
+    }
+
+    pub async fn restore_question(&self, id: &str) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.restore_question(id).await,
+            Self::Guest(store) => store.restore_question(id).await,
+        }
+    }
+
+    pub async fn purge_question(&self, id: &str) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.purge_question(id).await,
+            Self::Guest(store) => store.purge_question(id).await,
+        }
+    }
+
+    pub async fn get_deleted_questions(&self) -> Result<Vec<Question>, String> {
+        match self {
+            Self::Remote(client) => client.get_deleted_questions().await,
+            Self::Guest(store) => store.get_deleted_questions().await,
+        }
+    }
+
+    pub async fn get_all_questions(&self) -> Result<Vec<Question>, String> {
+        match self {
+            Self::Remote(client) => client.get_all_questions().await,
+            Self::Guest(store) => store.get_all_questions().await,
+        }
+    }
+
+    pub async fn purge_deleted_before(&self, before: DateTime<Utc>) -> Result<(), String> {
+        match self {
+            Self::Remote(client) => client.purge_deleted_before(before).await,
+            Self::Guest(store) => store.purge_deleted_before(before).await,
+        }
+    }
+}
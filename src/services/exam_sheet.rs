@@ -0,0 +1,243 @@
+// 종이 시험지 / 정답표 / 해설집 생성
+//
+// 화면 미리보기와 내려받는 HTML 파일이 같은 마크업과 CSS를 사용하도록,
+// 레이아웃은 모두 여기서 HTML 문자열로 만듭니다.
+
+use crate::components::markdown::{render_html, ContentSource};
+use crate::models::{Certificate, Question};
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// 시험지와 내려받은 HTML 양쪽에 쓰는 스타일. `@media print`에서 앱 화면 요소는 숨깁니다.
+pub const EXAM_SHEET_CSS: &str = r#"
+.exam-sheet { font-family: "Noto Serif KR", "Batang", serif; color: #111; line-height: 1.6; max-width: 210mm; margin: 0 auto; background: #fff; }
+.exam-sheet .exam-section { padding: 12mm 14mm; }
+.exam-sheet .exam-section + .exam-section { border-top: 1px dashed #bbb; }
+.exam-sheet header { border-bottom: 2px solid #111; padding-bottom: 4mm; margin-bottom: 6mm; }
+.exam-sheet header h1 { font-size: 20pt; font-weight: 700; margin: 0; text-align: center; }
+.exam-sheet header h2 { font-size: 13pt; font-weight: 400; margin: 2mm 0 0; text-align: center; }
+.exam-sheet header .exam-meta { display: flex; justify-content: space-between; font-size: 10pt; margin-top: 4mm; }
+.exam-sheet .exam-question { break-inside: avoid; margin-bottom: 6mm; }
+.exam-sheet .exam-stem { display: flex; gap: 2mm; font-weight: 600; }
+.exam-sheet .exam-stem > .exam-number { flex: none; }
+.exam-sheet .exam-stem p, .exam-sheet .exam-options p { margin: 0; }
+.exam-sheet .exam-options { list-style: none; padding: 0; margin: 2mm 0 0 6mm; }
+.exam-sheet .exam-options li { display: flex; gap: 2mm; margin: 1mm 0; }
+.exam-sheet .exam-options .exam-circled { flex: none; }
+.exam-sheet pre { background: #f5f5f5; padding: 2mm 3mm; font-size: 9pt; white-space: pre-wrap; }
+.exam-sheet .hl-code code { counter-reset: hl-line; }
+.exam-sheet .hl-line { display: block; }
+.exam-sheet .hl-line::before { counter-increment: hl-line; content: counter(hl-line); display: inline-block; width: 1.5em; margin-right: 2mm; text-align: right; color: #888; }
+.exam-sheet .hl-marked { background: #e5e5e5; }
+.exam-sheet .hl-keyword { font-weight: 700; }
+.exam-sheet .hl-comment { color: #666; font-style: italic; }
+.exam-sheet .hl-string { color: #333; }
+.exam-sheet .callout { border-left: 3px solid #666; background: #f5f5f5; padding: 1mm 3mm; margin: 2mm 0; }
+.exam-sheet .callout-title { font-weight: 700; margin: 0; }
+.exam-sheet .callout-warning, .exam-sheet .callout-caution { border-left-color: #111; }
+.exam-sheet mark { background: #e5e5e5; color: inherit; }
+.exam-sheet .circled-number { font-family: inherit; }
+.exam-sheet table { border-collapse: collapse; }
+.exam-sheet td, .exam-sheet th { border: 1px solid #999; padding: 1mm 2mm; }
+.exam-sheet img { max-width: 100%; }
+.exam-sheet .answer-key { width: 100%; text-align: center; }
+.exam-sheet .answer-key th { background: #f0f0f0; width: 10%; }
+.exam-sheet .explanation { break-inside: avoid; margin-bottom: 6mm; }
+.exam-sheet .explanation h3 { font-size: 11pt; margin: 0 0 1mm; }
+.exam-sheet .explanation .option-explanation { margin-left: 6mm; font-size: 10pt; color: #333; }
+@media print {
+  @page { size: A4; margin: 0; }
+  body { background: #fff !important; }
+  nav, .no-print { display: none !important; }
+  main { max-width: none !important; padding: 0 !important; }
+  .exam-sheet { max-width: none; }
+  .exam-sheet .exam-section + .exam-section { border-top: none; break-before: page; }
+}
+"#;
+
+/// 시험지에 포함할 구역
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ExamSections {
+    pub questions: bool,
+    pub answer_key: bool,
+    pub explanations: bool,
+}
+
+impl Default for ExamSections {
+    fn default() -> Self {
+        Self { questions: true, answer_key: true, explanations: true }
+    }
+}
+
+pub struct ExamSheet {
+    pub certificate: Certificate,
+    pub questions: Vec<Question>,
+    /// 무작위 추출에 사용한 시드. 같은 시드면 같은 시험지가 만들어집니다.
+    pub seed: Option<u64>,
+}
+
+impl ExamSheet {
+    /// `random`이 `Some((문항 수, 시드))`이면 시드로 섞은 뒤 앞에서부터 문항 수만큼 뽑고,
+    /// `None`이면 전체 문제를 등록한 순서대로 싣습니다.
+    pub fn new(certificate: Certificate, mut questions: Vec<Question>, random: Option<(usize, u64)>) -> Self {
+        // 같은 시드가 같은 결과를 내도록 섞기 전에 순서를 고정합니다.
+        questions.sort_by(|a, b| a.created_at.cmp(&b.created_at).then_with(|| a.id.cmp(&b.id)));
+
+        let seed = random.map(|(count, seed)| {
+            Self::shuffle(&mut questions, seed);
+            questions.truncate(count);
+            seed
+        });
+        Self { certificate, questions, seed }
+    }
+
+    /// 시드로 섞습니다. 인쇄한 시험지를 나중에 같은 시드로 다시 만들 수 있어야 하므로, 구현이 바뀔 수 있는
+    /// `StdRng`·`SliceRandom::shuffle` 대신 출력이 고정된 ChaCha8과 직접 쓴 Fisher-Yates 섞기를 씁니다.
+    fn shuffle<T>(items: &mut [T], seed: u64) {
+        let mut key = [0u8; 32];
+        key[..8].copy_from_slice(&seed.to_le_bytes());
+        let mut rng = ChaCha8Rng::from_seed(key);
+        for i in (1..items.len()).rev() {
+            let j = (rng.next_u64() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// ①~⑳ 원문자 보기 번호. 그 이상은 "(21)"처럼 표시합니다.
+    pub fn circled_number(index: usize) -> String {
+        match char::from_u32(0x2460 + index as u32) {
+            Some(c) if index < 20 => c.to_string(),
+            _ => format!("({})", index + 1),
+        }
+    }
+
+    /// 정답 보기 번호들 (예: "②④")
+    pub fn answer_label(question: &Question) -> String {
+        let label: String = question
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.is_correct)
+            .map(|(i, _)| Self::circled_number(i))
+            .collect();
+        if label.is_empty() { "-".to_string() } else { label }
+    }
+
+    /// 화면과 파일에 공통으로 쓰는 본문 HTML
+    pub fn render_body(&self, sections: ExamSections) -> String {
+        let mut out = String::from("<div class=\"exam-sheet\">");
+        if sections.questions {
+            out.push_str(&self.render_questions());
+        }
+        if sections.answer_key {
+            out.push_str(&self.render_answer_key());
+        }
+        if sections.explanations {
+            out.push_str(&self.render_explanations());
+        }
+        out.push_str("</div>");
+        out
+    }
+
+    /// 내려받기용 단독 HTML 문서
+    pub fn render_document(&self, sections: ExamSections) -> String {
+        format!(
+            "<!DOCTYPE html>\n<html lang=\"ko\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n<style>{}</style>\n</head>\n<body>\n{}\n</body>\n</html>\n",
+            escape(&self.certificate.name),
+            EXAM_SHEET_CSS,
+            self.render_body(sections)
+        )
+    }
+
+    fn render_header(&self, subtitle: &str, with_name_field: bool) -> String {
+        let selection = match self.seed {
+            Some(seed) => format!("{}문항 · 무작위 추출 (시드 {})", self.questions.len(), seed),
+            None => format!("{}문항", self.questions.len()),
+        };
+        let name_field = if with_name_field { "성명: ________________" } else { "" };
+        format!(
+            "<header><h1>{}</h1><h2>{}</h2><div class=\"exam-meta\"><span>{}</span><span>{}</span></div></header>",
+            escape(&self.certificate.name),
+            subtitle,
+            selection,
+            name_field
+        )
+    }
+
+    fn render_questions(&self) -> String {
+        let mut out = String::from("<section class=\"exam-section\">");
+        out.push_str(&self.render_header("시험지", true));
+        for (index, question) in self.questions.iter().enumerate() {
+            out.push_str("<div class=\"exam-question\">");
+            out.push_str(&format!(
+                "<div class=\"exam-stem\"><span class=\"exam-number\">{}.</span><div>{}</div></div>",
+                index + 1,
+                self.render_markdown(&question.content)
+            ));
+            out.push_str("<ol class=\"exam-options\">");
+            for (option_index, option) in question.options.iter().enumerate() {
+                out.push_str(&format!(
+                    "<li><span class=\"exam-circled\">{}</span><div>{}</div></li>",
+                    Self::circled_number(option_index),
+                    self.render_markdown(&option.content)
+                ));
+            }
+            out.push_str("</ol></div>");
+        }
+        out.push_str("</section>");
+        out
+    }
+
+    /// 한 줄에 10문항씩 번호/정답 표로 보여줍니다.
+    fn render_answer_key(&self) -> String {
+        let mut out = String::from("<section class=\"exam-section\">");
+        out.push_str(&self.render_header("정답표", false));
+        out.push_str("<table class=\"answer-key\">");
+        for (row, chunk) in self.questions.chunks(10).enumerate() {
+            out.push_str("<tr>");
+            for i in 0..chunk.len() {
+                out.push_str(&format!("<th>{}</th>", row * 10 + i + 1));
+            }
+            out.push_str("</tr><tr>");
+            for question in chunk {
+                out.push_str(&format!("<td>{}</td>", Self::answer_label(question)));
+            }
+            out.push_str("</tr>");
+        }
+        out.push_str("</table></section>");
+        out
+    }
+
+    fn render_explanations(&self) -> String {
+        let mut out = String::from("<section class=\"exam-section\">");
+        out.push_str(&self.render_header("해설집", false));
+        for (index, question) in self.questions.iter().enumerate() {
+            out.push_str("<div class=\"explanation\">");
+            out.push_str(&format!("<h3>{}. 정답 {}</h3>", index + 1, Self::answer_label(question)));
+            if !question.explanation.is_empty() {
+                out.push_str(&self.render_markdown(&question.explanation));
+            }
+            for (option_index, option) in question.options.iter().enumerate() {
+                if option.explanation.is_empty() {
+                    continue;
+                }
+                out.push_str(&format!(
+                    "<div class=\"option-explanation\"><strong>{}</strong> {}</div>",
+                    Self::circled_number(option_index),
+                    self.render_markdown(&option.explanation)
+                ));
+            }
+            out.push_str("</div>");
+        }
+        out.push_str("</section>");
+        out
+    }
+
+    fn render_markdown(&self, content: &str) -> String {
+        render_html(content, ContentSource::of(&self.certificate))
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;")
+}
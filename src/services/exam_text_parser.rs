@@ -0,0 +1,250 @@
+// 기출문제 텍스트(①②③④ 보기 형식)를 Question 목록으로 변환하는 파서
+
+use crate::models::{Question, QuestionOption};
+use std::collections::HashMap;
+
+/// 파싱된 문제 한 개. 원문의 문제 번호를 함께 보관해 정답표와 맞춰봅니다.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParsedQuestion {
+    pub number: u32,
+    pub question: Question,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ParsedExam {
+    pub questions: Vec<ParsedQuestion>,
+    pub warnings: Vec<String>,
+}
+
+pub struct ExamTextParser;
+
+impl ExamTextParser {
+    /// 기출문제 본문과 정답표("1-③ 2-①" 형식)를 파싱합니다.
+    /// 자격증 ID는 비어 있으므로 저장하기 전에 채워야 합니다.
+    pub fn parse(text: &str, answer_key: &str) -> ParsedExam {
+        let answers = Self::parse_answer_key(answer_key);
+        let mut exam = ParsedExam::default();
+        let mut current: Option<Block> = None;
+
+        for raw_line in text.lines() {
+            let line = raw_line.trim();
+
+            if let Some((number, rest)) = Self::split_stem_number(line) {
+                let expected = current.as_ref().map(|b| b.number + 1);
+                if expected.is_none() || expected == Some(number) {
+                    if let Some(block) = current.take() {
+                        Self::finish_block(block, &answers, &mut exam);
+                    }
+                    let mut block = Block::new(number);
+                    block.push_text(rest);
+                    current = Some(block);
+                    continue;
+                }
+            }
+
+            let Some(block) = current.as_mut() else {
+                // 첫 문제 번호가 나오기 전의 머리말은 무시합니다.
+                continue;
+            };
+
+            if let Some(answer) = Self::strip_label(line, &["정답", "답"]) {
+                block.inline_answer = answer.chars().find_map(Self::option_index);
+            } else if let Some(explanation) = Self::strip_label(line, &["해설"]) {
+                block.explanation.push(explanation.to_string());
+                block.in_explanation = true;
+            } else {
+                block.push_text(line);
+            }
+        }
+
+        if let Some(block) = current.take() {
+            Self::finish_block(block, &answers, &mut exam);
+        }
+
+        if exam.questions.is_empty() && !text.trim().is_empty() {
+            exam.warnings
+                .push("문제를 찾지 못했습니다. '1. 문제' 형식의 번호가 있는지 확인해주세요.".to_string());
+        }
+        exam
+    }
+
+    /// "1-③ 2-① 3.② 4:4" 처럼 문제 번호와 보기 번호가 짝지어진 정답표를 읽습니다.
+    /// 반환값은 문제 번호 → 0부터 시작하는 보기 인덱스입니다.
+    pub fn parse_answer_key(answer_key: &str) -> HashMap<u32, usize> {
+        let mut answers = HashMap::new();
+        let chars: Vec<char> = answer_key.chars().collect();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if !chars[i].is_ascii_digit() {
+                i += 1;
+                continue;
+            }
+
+            let mut number = 0u32;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                number = number * 10 + chars[i].to_digit(10).unwrap();
+                i += 1;
+            }
+
+            let mut has_separator = false;
+            while i < chars.len() && matches!(chars[i], '-' | '.' | ':' | ')' | '=' | ' ' | '\t') {
+                has_separator = true;
+                i += 1;
+            }
+
+            if let Some(index) = chars.get(i).and_then(|c| Self::circled_index(*c)) {
+                answers.insert(number, index);
+                i += 1;
+            } else if has_separator && chars.get(i).is_some_and(|c| ('1'..='9').contains(c)) {
+                // 원문자 대신 숫자로 적힌 정답(예: "1-3")도 한 자리 숫자면 허용합니다.
+                let next_is_digit = chars.get(i + 1).is_some_and(|c| c.is_ascii_digit());
+                if !next_is_digit {
+                    answers.insert(number, chars[i].to_digit(10).unwrap() as usize - 1);
+                    i += 1;
+                }
+            }
+        }
+        answers
+    }
+
+    /// ①~⑩ 원문자를 0부터 시작하는 보기 인덱스로 바꿉니다.
+    pub fn circled_index(c: char) -> Option<usize> {
+        match c {
+            '\u{2460}'..='\u{2469}' => Some(c as usize - 0x2460),
+            _ => None,
+        }
+    }
+
+    fn option_index(c: char) -> Option<usize> {
+        Self::circled_index(c).or_else(|| c.to_digit(10).filter(|d| *d > 0).map(|d| d as usize - 1))
+    }
+
+    /// "12. 문제" / "12) 문제" 형태의 줄에서 문제 번호와 나머지 본문을 분리합니다.
+    fn split_stem_number(line: &str) -> Option<(u32, &str)> {
+        let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+        if digits == 0 || digits > 3 {
+            return None;
+        }
+        let rest = &line[digits..];
+        let rest = rest
+            .strip_prefix('.')
+            .or_else(|| rest.strip_prefix(')'))
+            .or_else(|| rest.strip_prefix('．'))?;
+        // "3.14" 같은 소수는 문제 번호로 보지 않습니다.
+        if rest.chars().next().is_some_and(|c| c.is_ascii_digit()) {
+            return None;
+        }
+        Some((line[..digits].parse().ok()?, rest.trim_start()))
+    }
+
+    fn strip_label<'a>(line: &'a str, labels: &[&str]) -> Option<&'a str> {
+        let line = line.trim_start_matches(['[', '(', '<']);
+        labels.iter().find_map(|label| {
+            let rest = line.strip_prefix(label)?;
+            // "답안지"처럼 라벨로 시작하는 일반 문장은 제외합니다.
+            let next = rest.chars().next();
+            let is_label = next.is_none_or(|c| {
+                matches!(c, ']' | ')' | '>' | ':' | '.' | ' ' | '\t') || Self::circled_index(c).is_some()
+            });
+            if !is_label {
+                return None;
+            }
+            let rest = rest.trim_start_matches([']', ')', '>', ':', '.', ' ', '\t']);
+            Some(rest.trim())
+        })
+    }
+
+    fn finish_block(block: Block, answers: &HashMap<u32, usize>, exam: &mut ParsedExam) {
+        let number = block.number;
+        let mut question = Question::new(String::new(), block.stem.join("\n").trim().to_string());
+        question.explanation = block.explanation.join("\n").trim().to_string();
+        question.options = block
+            .options
+            .into_iter()
+            .enumerate()
+            .map(|(index, content)| {
+                let mut option = QuestionOption::new(content.trim().to_string(), false);
+                option.display_order = index as i32;
+                option
+            })
+            .collect();
+
+        if question.options.len() < 2 {
+            exam.warnings
+                .push(format!("{}번: 보기가 {}개뿐입니다.", number, question.options.len()));
+        }
+
+        match answers.get(&number).copied().or(block.inline_answer) {
+            Some(index) if index < question.options.len() => {
+                question.options[index].is_correct = true;
+            }
+            Some(index) => exam
+                .warnings
+                .push(format!("{}번: 정답 {}번 보기가 없습니다.", number, index + 1)),
+            None => exam.warnings.push(format!("{}번: 정답을 찾지 못했습니다.", number)),
+        }
+
+        exam.questions.push(ParsedQuestion { number, question });
+    }
+}
+
+// 파싱 중인 문제 한 개의 임시 상태
+struct Block {
+    number: u32,
+    stem: Vec<String>,
+    options: Vec<String>,
+    explanation: Vec<String>,
+    inline_answer: Option<usize>,
+    in_explanation: bool,
+}
+
+impl Block {
+    fn new(number: u32) -> Self {
+        Self {
+            number,
+            stem: Vec::new(),
+            options: Vec::new(),
+            explanation: Vec::new(),
+            inline_answer: None,
+            in_explanation: false,
+        }
+    }
+
+    /// 한 줄을 원문자 기준으로 잘라 본문/보기/해설에 이어 붙입니다.
+    /// 한 줄에 "① a ② b" 처럼 여러 보기가 있어도 처리합니다.
+    fn push_text(&mut self, line: &str) {
+        if self.in_explanation {
+            self.explanation.push(line.to_string());
+            return;
+        }
+
+        let mut segment = String::new();
+        for c in line.chars() {
+            if ExamTextParser::circled_index(c).is_some() {
+                self.flush_segment(&segment);
+                segment.clear();
+                self.options.push(String::new());
+            } else {
+                segment.push(c);
+            }
+        }
+        self.flush_segment(&segment);
+    }
+
+    // 보기가 시작된 뒤의 텍스트는 마지막 보기에, 그 전에는 문제 본문에 붙입니다.
+    fn flush_segment(&mut self, segment: &str) {
+        let text = segment.trim();
+        match self.options.last_mut() {
+            Some(option) => {
+                if !text.is_empty() {
+                    if !option.is_empty() {
+                        option.push('\n');
+                    }
+                    option.push_str(text);
+                }
+            }
+            None => self.stem.push(text.to_string()),
+        }
+    }
+}
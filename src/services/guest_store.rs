@@ -0,0 +1,285 @@
+// 게스트 모드 저장소 (IndexedDB)
+//
+// 로그인하지 않고 쓰는 동안 자격증과 문제를 브라우저에 저장합니다. 메서드는 `SupabaseClient`와
+// 이름과 동작(휴지통, 영구 삭제 시 문제까지 삭제, 문제 수 계산)을 맞춰 `DataStore`가 그대로 바꿔 끼울 수 있게 합니다.
+
+use crate::models::{Certificate, ExportedCertificate, Question};
+use crate::services::local_db::{LocalDb, GUEST_CERTIFICATE_STORE, GUEST_QUESTION_STORE};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 모델은 서버가 채우는 필드(생성 시각, 통계, 휴지통)를 직렬화하지 않으므로 따로 담아 저장합니다.
+#[derive(Serialize, Deserialize)]
+struct StoredCertificate {
+    certificate: Certificate,
+    created_at: DateTime<Utc>,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredQuestion {
+    question: Question,
+    created_at: DateTime<Utc>,
+    last_attempt: Option<DateTime<Utc>>,
+    attempt_count: u32,
+    correct_count: u32,
+    deleted_at: Option<DateTime<Utc>>,
+}
+
+impl StoredCertificate {
+    fn new(certificate: &Certificate) -> Self {
+        Self {
+            certificate: certificate.clone(),
+            created_at: Utc::now(),
+            deleted_at: None,
+        }
+    }
+
+    fn into_certificate(self, question_count: u32) -> Certificate {
+        Certificate {
+            question_count,
+            created_at: self.created_at,
+            deleted_at: self.deleted_at,
+            ..self.certificate
+        }
+    }
+}
+
+impl StoredQuestion {
+    fn new(question: &Question) -> Self {
+        let mut question = question.clone();
+        for option in question.options.iter_mut() {
+            option.question_id = question.id.clone();
+        }
+        Self {
+            question,
+            created_at: Utc::now(),
+            last_attempt: None,
+            attempt_count: 0,
+            correct_count: 0,
+            deleted_at: None,
+        }
+    }
+
+    fn into_question(self) -> Question {
+        let mut question = Question {
+            created_at: self.created_at,
+            last_attempt: self.last_attempt,
+            attempt_count: self.attempt_count,
+            correct_count: self.correct_count,
+            deleted_at: self.deleted_at,
+            ..self.question
+        };
+        question.options.sort_by_key(|opt| opt.display_order);
+        question
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct GuestStore;
+
+impl GuestStore {
+    // --- Certificate ---
+    pub async fn get_all_certificates(&self) -> Result<Vec<Certificate>, String> {
+        self.certificates(|c| c.deleted_at.is_none()).await
+    }
+
+    pub async fn get_certificate_by_id(&self, id: &str) -> Result<Certificate, String> {
+        self.certificates(|c| c.certificate.id == id && c.deleted_at.is_none())
+            .await?
+            .pop()
+            .ok_or_else(|| "해당 자격증을 찾을 수 없습니다.".to_string())
+    }
+
+    pub async fn create_certificate(&self, cert: &Certificate) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        if db.get::<StoredCertificate>(GUEST_CERTIFICATE_STORE, &cert.id).await?.is_some() {
+            return Err("자격증 생성 실패: 같은 ID의 자격증이 이미 있습니다.".to_string());
+        }
+        db.put(GUEST_CERTIFICATE_STORE, &cert.id, &StoredCertificate::new(cert)).await
+    }
+
+    pub async fn update_certificate(&self, cert: &Certificate) -> Result<(), String> {
+        self.modify_certificate(&cert.id, |stored| {
+            stored.certificate.name = cert.name.clone();
+            stored.certificate.description = cert.description.clone();
+        })
+        .await
+        .map_err(|e| format!("자격증 수정 실패: {}", e))
+    }
+
+    /// 자격증을 휴지통으로 옮깁니다. 문제는 그대로 두고 자격증과 함께 숨겨집니다.
+    pub async fn delete_certificate(&self, id: &str) -> Result<(), String> {
+        self.modify_certificate(id, |stored| stored.deleted_at = Some(Utc::now()))
+            .await
+            .map_err(|e| format!("자격증 삭제 실패: {}", e))
+    }
+
+    pub async fn restore_certificate(&self, id: &str) -> Result<(), String> {
+        self.modify_certificate(id, |stored| stored.deleted_at = None)
+            .await
+            .map_err(|e| format!("자격증 복원 실패: {}", e))
+    }
+
+    /// 자격증과 문제를 완전히 지웁니다.
+    pub async fn purge_certificate(&self, id: &str) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        let questions: Vec<StoredQuestion> = db.get_all(GUEST_QUESTION_STORE).await?;
+        for stored in questions.iter().filter(|q| q.question.certificate_id == id) {
+            db.delete(GUEST_QUESTION_STORE, &stored.question.id).await?;
+        }
+        db.delete(GUEST_CERTIFICATE_STORE, id).await
+    }
+
+    pub async fn get_deleted_certificates(&self) -> Result<Vec<Certificate>, String> {
+        self.certificates(|c| c.deleted_at.is_some()).await
+    }
+
+    // --- Question ---
+    pub async fn get_questions_by_certificate(&self, cert_id: &str) -> Result<Vec<Question>, String> {
+        self.questions(|q| q.question.certificate_id == cert_id && q.deleted_at.is_none()).await
+    }
+
+    pub async fn get_question_by_id(&self, id: &str) -> Result<Question, String> {
+        let db = LocalDb::open().await?;
+        db.get::<StoredQuestion>(GUEST_QUESTION_STORE, id)
+            .await?
+            .filter(|stored| stored.deleted_at.is_none())
+            .map(StoredQuestion::into_question)
+            .ok_or_else(|| "문제를 찾을 수 없습니다.".to_string())
+    }
+
+    pub async fn create_question(&self, question: &Question) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        if db.get::<StoredQuestion>(GUEST_QUESTION_STORE, &question.id).await?.is_some() {
+            return Err("문제 생성 실패: 같은 ID의 문제가 이미 있습니다.".to_string());
+        }
+        db.put(GUEST_QUESTION_STORE, &question.id, &StoredQuestion::new(question)).await
+    }
+
+    pub async fn update_question(&self, question: &Question) -> Result<(), String> {
+        self.modify_question(&question.id, |stored| {
+            let updated = StoredQuestion::new(question).question;
+            stored.question.content = updated.content;
+            stored.question.explanation = updated.explanation;
+            stored.question.options = updated.options;
+        })
+        .await
+        .map_err(|e| format!("문제 업데이트 실패: {}", e))
+    }
+
+    pub async fn update_question_stats(&self, question: &Question) -> Result<(), String> {
+        self.modify_question(&question.id, |stored| {
+            stored.attempt_count = question.attempt_count;
+            stored.correct_count = question.correct_count;
+            stored.last_attempt = question.last_attempt;
+        })
+        .await
+        .map_err(|e| format!("문제 통계 업데이트 실패: {}", e))
+    }
+
+    /// 문제를 휴지통으로 옮깁니다.
+    pub async fn delete_question(&self, id: &str) -> Result<(), String> {
+        self.modify_question(id, |stored| stored.deleted_at = Some(Utc::now()))
+            .await
+            .map_err(|e| format!("문제 삭제 실패: {}", e))
+    }
+
+    pub async fn restore_question(&self, id: &str) -> Result<(), String> {
+        self.modify_question(id, |stored| stored.deleted_at = None)
+            .await
+            .map_err(|e| format!("문제 복원 실패: {}", e))
+    }
+
+    pub async fn purge_question(&self, id: &str) -> Result<(), String> {
+        LocalDb::open().await?.delete(GUEST_QUESTION_STORE, id).await
+    }
+
+    pub async fn get_deleted_questions(&self) -> Result<Vec<Question>, String> {
+        self.questions(|q| q.deleted_at.is_some()).await
+    }
+
+    /// 휴지통에 있는 것까지 포함한 모든 문제
+    pub async fn get_all_questions(&self) -> Result<Vec<Question>, String> {
+        self.questions(|_| true).await
+    }
+
+    /// `before`보다 먼저 휴지통에 들어간 자격증(문제 포함)과 문제를 영구 삭제합니다.
+    pub async fn purge_deleted_before(&self, before: DateTime<Utc>) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        let certificates: Vec<StoredCertificate> = db.get_all(GUEST_CERTIFICATE_STORE).await?;
+        let expired = |deleted_at: Option<DateTime<Utc>>| deleted_at.is_some_and(|at| at < before);
+        for stored in certificates.iter().filter(|c| expired(c.deleted_at)) {
+            self.purge_certificate(&stored.certificate.id).await?;
+        }
+        let questions: Vec<StoredQuestion> = db.get_all(GUEST_QUESTION_STORE).await?;
+        for stored in questions.iter().filter(|q| expired(q.deleted_at)) {
+            db.delete(GUEST_QUESTION_STORE, &stored.question.id).await?;
+        }
+        Ok(())
+    }
+
+    // --- 계정으로 옮기기 ---
+    /// 휴지통에 있지 않은 자격증과 문제 (통계 포함)
+    pub async fn export(&self) -> Result<Vec<ExportedCertificate>, String> {
+        let mut certificates = self.get_all_certificates().await?;
+        certificates.sort_by_key(|c| c.created_at);
+        let mut exported = Vec::new();
+        for certificate in certificates {
+            let mut questions = self.get_questions_by_certificate(&certificate.id).await?;
+            questions.sort_by_key(|q| q.created_at);
+            exported.push(ExportedCertificate { certificate, questions });
+        }
+        Ok(exported)
+    }
+
+    /// 게스트 데이터를 모두 지웁니다.
+    pub async fn clear(&self) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        db.clear(GUEST_QUESTION_STORE).await?;
+        db.clear(GUEST_CERTIFICATE_STORE).await
+    }
+
+    async fn certificates(&self, filter: impl Fn(&StoredCertificate) -> bool) -> Result<Vec<Certificate>, String> {
+        let db = LocalDb::open().await?;
+        let questions: Vec<StoredQuestion> = db.get_all(GUEST_QUESTION_STORE).await?;
+        let certificates: Vec<StoredCertificate> = db.get_all(GUEST_CERTIFICATE_STORE).await?;
+        Ok(certificates
+            .into_iter()
+            .filter(|c| filter(c))
+            .map(|c| {
+                // 서버의 트리거처럼 휴지통에 있지 않은 문제만 셉니다.
+                let count = questions
+                    .iter()
+                    .filter(|q| q.question.certificate_id == c.certificate.id && q.deleted_at.is_none())
+                    .count();
+                c.into_certificate(count as u32)
+            })
+            .collect())
+    }
+
+    async fn questions(&self, filter: impl Fn(&StoredQuestion) -> bool) -> Result<Vec<Question>, String> {
+        let db = LocalDb::open().await?;
+        let questions: Vec<StoredQuestion> = db.get_all(GUEST_QUESTION_STORE).await?;
+        Ok(questions.into_iter().filter(|q| filter(q)).map(StoredQuestion::into_question).collect())
+    }
+
+    /// 없는 ID는 서버의 PATCH처럼 아무것도 바꾸지 않고 성공합니다.
+    async fn modify_certificate(&self, id: &str, change: impl FnOnce(&mut StoredCertificate)) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        if let Some(mut stored) = db.get::<StoredCertificate>(GUEST_CERTIFICATE_STORE, id).await? {
+            change(&mut stored);
+            db.put(GUEST_CERTIFICATE_STORE, id, &stored).await?;
+        }
+        Ok(())
+    }
+
+    async fn modify_question(&self, id: &str, change: impl FnOnce(&mut StoredQuestion)) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        if let Some(mut stored) = db.get::<StoredQuestion>(GUEST_QUESTION_STORE, id).await? {
+            change(&mut stored);
+            db.put(GUEST_QUESTION_STORE, id, &stored).await?;
+        }
+        Ok(())
+    }
+}
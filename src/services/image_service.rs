@@ -0,0 +1,309 @@
+// 문제 첨부 이미지
+//
+// 이미지는 Storage 버킷의 `<사용자 ID>/<UUID>.<확장자>`에 올리고, 문제 본문에는 공개 주소를
+// 가리키는 마크다운 이미지(`![이름](주소)`)로 넣습니다. 어떤 문제도(휴지통과 로컬 스냅샷 포함)
+// 가리키지 않는 이미지는 영구 삭제 뒤에 정리합니다.
+
+use crate::components::markdown::markdown_options;
+use crate::models::{ExportedCertificate, Question};
+use crate::services::storage_client::StorageClient;
+use crate::services::{AuthService, DataStore, SnapshotService};
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use gloo_file::futures::read_as_bytes;
+use gloo_file::File;
+use pulldown_cmark::{Event, Parser, Tag};
+use std::collections::{BTreeMap, HashSet};
+use uuid::Uuid;
+
+/// 올릴 수 있는 최대 파일 크기 (5MB)
+pub const MAX_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// 올릴 수 있는 형식과 확장자. SVG는 스크립트를 담을 수 있어 받지 않습니다.
+const IMAGE_TYPES: [(&str, &str); 4] = [
+    ("image/png", "png"),
+    ("image/jpeg", "jpg"),
+    ("image/gif", "gif"),
+    ("image/webp", "webp"),
+];
+
+/// 올린 직후 아직 저장하지 않은 문제의 이미지를 지우지 않도록, 이보다 오래된 것만 정리합니다.
+const ORPHAN_GRACE_HOURS: i64 = 24;
+
+pub struct ImageService;
+
+impl ImageService {
+    /// 파일을 올리고 본문에 넣을 마크다운 이미지 참조를 돌려줍니다.
+    pub async fn upload(file: &File) -> Result<String, String> {
+        let content_type = file.raw_mime_type();
+        if Self::extension_for(&content_type).is_none() {
+            return Err(format!("{}: PNG, JPEG, GIF, WebP 이미지만 올릴 수 있습니다.", file.name()));
+        }
+        if file.size() > MAX_IMAGE_BYTES {
+            return Err(format!("{}: 이미지는 {}MB 이하만 올릴 수 있습니다.", file.name(), MAX_IMAGE_BYTES / 1024 / 1024));
+        }
+        let bytes = read_as_bytes(file).await.map_err(|e| e.to_string())?;
+        let url = Self::upload_bytes(&bytes, &content_type).await?;
+        Ok(Self::markdown_reference(&file.name(), &url))
+    }
+
+    /// 바이트를 올리고 공개 주소를 돌려줍니다.
+    pub async fn upload_bytes(bytes: &[u8], content_type: &str) -> Result<String, String> {
+        let extension = Self::extension_for(content_type)
+            .ok_or_else(|| format!("지원하지 않는 이미지 형식입니다: {}", content_type))?;
+        if AuthService::is_guest() {
+            return Err("게스트 모드에서는 이미지를 첨부할 수 없습니다. 로그인하면 이미지를 올릴 수 있습니다.".to_string());
+        }
+        let user = AuthService::get_current_user().ok_or_else(|| "로그인이 필요합니다".to_string())?;
+        let path = format!("{}/{}.{}", user.id, Uuid::new_v4(), extension);
+
+        let storage = StorageClient::new();
+        storage.upload(&path, bytes, content_type).await?;
+        Ok(storage.public_url(&path))
+    }
+
+    pub fn is_image_type(content_type: &str) -> bool {
+        Self::extension_for(content_type).is_some()
+    }
+
+    fn extension_for(content_type: &str) -> Option<&'static str> {
+        IMAGE_TYPES.iter().find(|(mime, _)| *mime == content_type).map(|(_, ext)| *ext)
+    }
+
+    /// 파일 이름의 확장자로 형식을 추측합니다. (zip에서 꺼낸 이미지용)
+    pub fn content_type_for(file_name: &str) -> Option<&'static str> {
+        let extension = file_name.rsplit_once('.')?.1.to_ascii_lowercase();
+        let extension = if extension == "jpeg" { "jpg".to_string() } else { extension };
+        IMAGE_TYPES.iter().find(|(_, ext)| *ext == extension).map(|(mime, _)| *mime)
+    }
+
+    /// 대체 텍스트는 확장자를 뺀 파일 이름입니다. 마크다운 문법과 겹치는 괄호는 뺍니다.
+    fn markdown_reference(file_name: &str, url: &str) -> String {
+        let stem = file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem);
+        let alt: String = stem.chars().filter(|c| !matches!(c, '[' | ']' | '\n')).collect();
+        format!("![{}]({})", alt.trim(), url)
+    }
+
+    /// 마크다운 본문에 들어 있는 이미지 주소 (등장 순서, 중복 제거)
+    pub fn image_urls(text: &str) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for event in Parser::new_ext(text, markdown_options()) {
+            if let Event::Start(Tag::Image(_, url, _)) = event {
+                if !urls.iter().any(|u| u.as_str() == url.as_ref()) {
+                    urls.push(url.to_string());
+                }
+            }
+        }
+        urls
+    }
+
+    /// 문제의 모든 마크다운 필드 (문제, 해설, 보기, 보기 해설)
+    pub fn question_texts(question: &Question) -> impl Iterator<Item = &str> {
+        [question.content.as_str(), question.explanation.as_str()]
+            .into_iter()
+            .chain(question.options.iter().flat_map(|o| [o.content.as_str(), o.explanation.as_str()]))
+    }
+
+    pub fn question_image_urls(question: &Question) -> Vec<String> {
+        let mut urls: Vec<String> = Vec::new();
+        for url in Self::question_texts(question).flat_map(Self::image_urls) {
+            if !urls.contains(&url) {
+                urls.push(url);
+            }
+        }
+        urls
+    }
+
+    /// 본문에서 `url`을 가리키는 이미지 참조를 모두 지웁니다.
+    pub fn remove_references(text: &str, url: &str) -> String {
+        let mut ranges = Vec::new();
+        for (event, range) in Parser::new_ext(text, markdown_options()).into_offset_iter() {
+            if let Event::Start(Tag::Image(_, image_url, _)) = event {
+                if image_url.as_ref() == url {
+                    ranges.push(range);
+                }
+            }
+        }
+        let mut output = text.to_string();
+        for range in ranges.into_iter().rev() {
+            output.replace_range(range, "");
+        }
+        output
+    }
+
+    /// 어떤 문제도 가리키지 않는 내 이미지를 지우고 지운 개수를 돌려줍니다.
+    /// 휴지통의 문제와 로컬 스냅샷이 가리키는 이미지는 복원할 수 있도록 남겨 둡니다.
+    pub async fn sweep_orphans() -> Result<usize, String> {
+        let user = AuthService::get_current_user().ok_or_else(|| "로그인이 필요합니다".to_string())?;
+        let storage = StorageClient::new();
+        let objects = storage.list(&user.id).await?;
+        if objects.is_empty() {
+            return Ok(0);
+        }
+
+        let mut referenced: HashSet<String> = HashSet::new();
+        let mut collect = |questions: &[Question]| {
+            for question in questions {
+                referenced.extend(
+                    Self::question_image_urls(question)
+                        .iter()
+                        .filter_map(|url| storage.path_from_url(url))
+                        .map(str::to_string),
+                );
+            }
+        };
+        collect(&DataStore::current().get_all_questions().await?);
+        for meta in SnapshotService::list().await? {
+            let snapshot = SnapshotService::load(&meta.id).await?;
+            for exported in &snapshot.certificates {
+                collect(&exported.questions);
+            }
+        }
+
+        let cutoff = Utc::now() - Duration::hours(ORPHAN_GRACE_HOURS);
+        let orphans: Vec<String> = objects
+            .into_iter()
+            .filter(|o| o.created_at.is_some_and(|at| at < cutoff))
+            .map(|o| format!("{}/{}", user.id, o.name))
+            .filter(|path| !referenced.contains(path))
+            .collect();
+        storage.remove(&orphans).await?;
+        Ok(orphans.len())
+    }
+
+    /// 영구 삭제 뒤에 호출합니다. 정리에 실패해도 삭제 자체는 끝났으므로 기록만 합니다.
+    /// 게스트 모드는 올린 이미지가 없으므로 건너뜁니다.
+    pub async fn sweep_after_purge() {
+        if AuthService::is_guest() {
+            return;
+        }
+        if let Err(e) = Self::sweep_orphans().await {
+            web_sys::console::warn_1(&format!("이미지를 정리하지 못했습니다: {}", e).into());
+        }
+    }
+
+    /// 내가 올린 이미지를 모두 지우고 지운 개수를 돌려줍니다. 계정을 삭제하기 전에 부릅니다.
+    pub async fn delete_all() -> Result<usize, String> {
+        let user = AuthService::get_current_user().ok_or_else(|| "로그인이 필요합니다".to_string())?;
+        let storage = StorageClient::new();
+        let paths: Vec<String> = storage
+            .list(&user.id)
+            .await?
+            .into_iter()
+            .map(|o| format!("{}/{}", user.id, o.name))
+            .collect();
+        storage.remove(&paths).await?;
+        Ok(paths.len())
+    }
+
+    /// 내보내기 파일에 함께 넣은 이미지(`<prefix><파일 이름>`)를 다시 올리고 본문의 참조를
+    /// 새 공개 주소로 바꿉니다.
+    pub async fn restore_bundled(
+        certificates: &mut [ExportedCertificate],
+        files: &BTreeMap<String, Vec<u8>>,
+        prefix: &str,
+    ) -> Result<(), String> {
+        let mut uploaded: BTreeMap<&str, String> = BTreeMap::new();
+        for (name, bytes) in files {
+            let local = format!("{}{}", prefix, name);
+            let used = certificates
+                .iter()
+                .flat_map(|c| &c.questions)
+                .any(|q| Self::question_texts(q).any(|text| text.contains(&local)));
+            if !used {
+                continue;
+            }
+            let content_type = Self::content_type_for(name)
+                .ok_or_else(|| format!("{}: 지원하지 않는 이미지 형식입니다.", name))?;
+            uploaded.insert(name, Self::upload_bytes(bytes, content_type).await?);
+        }
+
+        for question in certificates.iter_mut().flat_map(|c| c.questions.iter_mut()) {
+            Self::map_texts(question, |text| {
+                uploaded
+                    .iter()
+                    .fold(text.to_string(), |text, (name, url)| text.replace(&format!("{}{}", prefix, name), url))
+            });
+        }
+        Ok(())
+    }
+
+    fn map_texts(question: &mut Question, mut map: impl FnMut(&str) -> String) {
+        question.content = map(&question.content);
+        question.explanation = map(&question.explanation);
+        for option in question.options.iter_mut() {
+            option.content = map(&option.content);
+            option.explanation = map(&option.explanation);
+        }
+    }
+}
+
+/// 내보내기 파일에 함께 넣을 이미지
+pub struct BundledImage {
+    /// 문제 본문에 들어 있는 공개 주소
+    pub url: String,
+    /// 묶음 안에서 쓰는 파일 이름 (`<UUID>.<확장자>`)
+    pub file_name: String,
+    pub content_type: String,
+    pub bytes: Vec<u8>,
+}
+
+/// 문제들이 가리키는 이미지를 내려받아 둔 묶음. 내 버킷의 이미지만 담고, 외부 주소는 그대로 둡니다.
+#[derive(Default)]
+pub struct ImageBundle {
+    images: Vec<BundledImage>,
+}
+
+impl ImageBundle {
+    /// 내려받지 못한 이미지는 건너뛰고 원래 주소를 그대로 남깁니다.
+    pub async fn collect<'a>(questions: impl IntoIterator<Item = &'a Question>) -> Self {
+        let storage = StorageClient::new();
+        let mut images: Vec<BundledImage> = Vec::new();
+        for question in questions {
+            for url in ImageService::question_image_urls(question) {
+                if images.iter().any(|image| image.url == url) {
+                    continue;
+                }
+                let Some(path) = storage.path_from_url(&url) else {
+                    continue;
+                };
+                let file_name = path.rsplit('/').next().unwrap_or(path).to_string();
+                match storage.download(&url).await {
+                    Ok((bytes, content_type)) => images.push(BundledImage { url, file_name, content_type, bytes }),
+                    Err(e) => web_sys::console::warn_1(&e.into()),
+                }
+            }
+        }
+        Self { images }
+    }
+
+    pub fn images(&self) -> &[BundledImage] {
+        &self.images
+    }
+
+    /// 문제가 가리키는 묶음 안의 이미지
+    pub fn used_by<'a>(&'a self, question: &'a Question) -> impl Iterator<Item = &'a BundledImage> + 'a {
+        self.images
+            .iter()
+            .filter(move |image| ImageService::question_texts(question).any(|text| text.contains(&image.url)))
+    }
+
+    /// 이미지 주소를 `<prefix><파일 이름>`으로 바꾼 문제 사본을 만듭니다.
+    pub fn localize(&self, question: &Question, prefix: &str) -> Question {
+        let mut question = question.clone();
+        ImageService::map_texts(&mut question, |text| {
+            self.images.iter().fold(text.to_string(), |text, image| {
+                text.replace(&image.url, &format!("{}{}", prefix, image.file_name))
+            })
+        });
+        question
+    }
+
+    /// HTML 안의 이미지 주소를 data URI로 바꿔 파일 하나로 볼 수 있게 합니다.
+    pub fn embed(&self, html: &str) -> String {
+        self.images.iter().fold(html.to_string(), |html, image| {
+            let data = general_purpose::STANDARD.encode(&image.bytes);
+            html.replace(&image.url, &format!("data:{};base64,{}", image.content_type, data))
+        })
+    }
+}
@@ -0,0 +1,201 @@
+// 브라우저 IndexedDB 래퍼
+//
+// 값은 JSON 문자열로 저장합니다. 키는 모두 문자열(out-of-line key)입니다.
+// 저장소를 추가할 때는 STORES에 이름을 넣고 DB_VERSION을 올리면 됩니다.
+// 연결은 작업이 끝나면(`LocalDb`를 버리면) 닫고, 다른 탭이 버전을 올리려 하면 바로 닫아 업그레이드를 막지 않습니다.
+
+use js_sys::{Array, Function, Promise};
+use serde::{de::DeserializeOwned, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{IdbDatabase, IdbObjectStore, IdbOpenDbRequest, IdbRequest, IdbTransactionMode};
+
+const DB_NAME: &str = "quiznote";
+const DB_VERSION: u32 = 2;
+
+pub const SNAPSHOT_META_STORE: &str = "snapshot_meta";
+pub const SNAPSHOT_STORE: &str = "snapshots";
+/// 게스트 모드의 자격증과 문제 (`GuestStore`)
+pub const GUEST_CERTIFICATE_STORE: &str = "guest_certificates";
+pub const GUEST_QUESTION_STORE: &str = "guest_questions";
+
+const STORES: [&str; 4] = [SNAPSHOT_META_STORE, SNAPSHOT_STORE, GUEST_CERTIFICATE_STORE, GUEST_QUESTION_STORE];
+
+pub struct LocalDb {
+    db: IdbDatabase,
+    _on_version_change: Closure<dyn FnMut(web_sys::Event)>,
+}
+
+impl Drop for LocalDb {
+    fn drop(&mut self) {
+        self.db.set_onversionchange(None);
+        self.db.close();
+    }
+}
+
+impl LocalDb {
+    pub async fn open() -> Result<Self, String> {
+        let factory = web_sys::window()
+            .ok_or_else(|| "window를 찾을 수 없습니다.".to_string())?
+            .indexed_db()
+            .map_err(|_| "IndexedDB를 사용할 수 없습니다.".to_string())?
+            .ok_or_else(|| "이 브라우저는 IndexedDB를 지원하지 않습니다.".to_string())?;
+        let request = factory
+            .open_with_u32(DB_NAME, DB_VERSION)
+            .map_err(|_| "로컬 DB를 열 수 없습니다.".to_string())?;
+
+        // 처음 열거나 버전이 올라가면 없는 저장소를 만듭니다.
+        let on_upgrade = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+            let Some(request) = event.target().and_then(|t| t.dyn_into::<IdbOpenDbRequest>().ok()) else {
+                return;
+            };
+            let Ok(db) = request.result().and_then(|r| r.dyn_into::<IdbDatabase>()) else {
+                return;
+            };
+            let existing = db.object_store_names();
+            for store in STORES {
+                if !existing.contains(store) {
+                    let _ = db.create_object_store(store);
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.as_ref().unchecked_ref()));
+
+        let result = Self::wait_open(&request).await;
+        if result.is_ok() {
+            request.set_onupgradeneeded(None);
+            drop(on_upgrade);
+        } else {
+            // 막혔던 요청은 나중에 이어서 업그레이드할 수 있으므로 저장소를 만드는 처리를 남겨 둡니다.
+            on_upgrade.forget();
+        }
+
+        let db = result?
+            .dyn_into::<IdbDatabase>()
+            .map_err(|_| "로컬 DB를 열 수 없습니다.".to_string())?;
+        let closing = db.clone();
+        let on_version_change = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| closing.close());
+        db.set_onversionchange(Some(on_version_change.as_ref().unchecked_ref()));
+        Ok(Self { db, _on_version_change: on_version_change })
+    }
+
+    /// `wait`과 같지만, 예전 버전의 연결을 가진 다른 탭 때문에 업그레이드가 막히면(`blocked`)
+    /// 끝없이 기다리지 않고 오류로 끝냅니다.
+    async fn wait_open(request: &IdbOpenDbRequest) -> Result<JsValue, String> {
+        let blocked = JsValue::from_str("blocked");
+        let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+            let success_request = request.clone();
+            let on_success = Closure::once_into_js(move |_: web_sys::Event| {
+                let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::NULL, &result);
+            });
+            let error_reject = reject.clone();
+            let on_error = Closure::once_into_js(move |_: web_sys::Event| {
+                let _ = error_reject.call0(&JsValue::NULL);
+            });
+            let blocked = blocked.clone();
+            let on_blocked = Closure::once_into_js(move |_: web_sys::Event| {
+                let _ = reject.call1(&JsValue::NULL, &blocked);
+            });
+            request.set_onsuccess(Some(on_success.unchecked_ref()));
+            request.set_onerror(Some(on_error.unchecked_ref()));
+            request.set_onblocked(Some(on_blocked.unchecked_ref()));
+        });
+        let result = JsFuture::from(promise).await;
+        request.set_onblocked(None);
+        if result.as_ref().is_err_and(|e| *e == blocked) {
+            // 다른 탭이 닫히면 요청이 뒤늦게 성공하므로, 그때 열린 연결은 바로 닫습니다.
+            let late_request = request.clone();
+            let close_late = Closure::once_into_js(move |_: web_sys::Event| {
+                if let Ok(db) = late_request.result().and_then(|r| r.dyn_into::<IdbDatabase>()) {
+                    db.close();
+                }
+            });
+            request.set_onsuccess(Some(close_late.unchecked_ref()));
+        }
+        result.map_err(|e| {
+            if e == blocked {
+                "다른 탭에서 이전 버전의 QuizNote가 로컬 DB를 쓰고 있습니다. 다른 QuizNote 탭을 닫거나 새로고침한 뒤 다시 시도해주세요.".to_string()
+            } else {
+                "로컬 DB를 열 수 없습니다.".to_string()
+            }
+        })
+    }
+
+    pub async fn put<T: Serialize>(&self, store: &str, key: &str, value: &T) -> Result<(), String> {
+        let json = serde_json::to_string(value).map_err(|e| e.to_string())?;
+        let request = self
+            .store(store, IdbTransactionMode::Readwrite)?
+            .put_with_key(&JsValue::from_str(&json), &JsValue::from_str(key))
+            .map_err(|_| format!("{} 저장에 실패했습니다.", store))?;
+        Self::wait(&request).await.map(|_| ())
+    }
+
+    pub async fn get<T: DeserializeOwned>(&self, store: &str, key: &str) -> Result<Option<T>, String> {
+        let request = self
+            .store(store, IdbTransactionMode::Readonly)?
+            .get(&JsValue::from_str(key))
+            .map_err(|_| format!("{} 읽기에 실패했습니다.", store))?;
+        match Self::wait(&request).await?.as_string() {
+            Some(json) => serde_json::from_str(&json).map(Some).map_err(|e| e.to_string()),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn get_all<T: DeserializeOwned>(&self, store: &str) -> Result<Vec<T>, String> {
+        let request = self
+            .store(store, IdbTransactionMode::Readonly)?
+            .get_all()
+            .map_err(|_| format!("{} 읽기에 실패했습니다.", store))?;
+        let values: Array = Self::wait(&request).await?.unchecked_into();
+        values
+            .iter()
+            .filter_map(|value| value.as_string())
+            .map(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+            .collect()
+    }
+
+    pub async fn delete(&self, store: &str, key: &str) -> Result<(), String> {
+        let request = self
+            .store(store, IdbTransactionMode::Readwrite)?
+            .delete(&JsValue::from_str(key))
+            .map_err(|_| format!("{} 삭제에 실패했습니다.", store))?;
+        Self::wait(&request).await.map(|_| ())
+    }
+
+    /// 저장소의 값을 모두 지웁니다.
+    pub async fn clear(&self, store: &str) -> Result<(), String> {
+        let request = self
+            .store(store, IdbTransactionMode::Readwrite)?
+            .clear()
+            .map_err(|_| format!("{} 삭제에 실패했습니다.", store))?;
+        Self::wait(&request).await.map(|_| ())
+    }
+
+    fn store(&self, store: &str, mode: IdbTransactionMode) -> Result<IdbObjectStore, String> {
+        self.db
+            .transaction_with_str_and_mode(store, mode)
+            .and_then(|tx| tx.object_store(store))
+            .map_err(|_| format!("로컬 저장소 '{}'를 열 수 없습니다.", store))
+    }
+
+    /// IdbRequest의 success/error 이벤트를 Future로 기다립니다.
+    async fn wait(request: &IdbRequest) -> Result<JsValue, String> {
+        let promise = Promise::new(&mut |resolve: Function, reject: Function| {
+            let success_request = request.clone();
+            let on_success = Closure::once_into_js(move |_: web_sys::Event| {
+                let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+                let _ = resolve.call1(&JsValue::NULL, &result);
+            });
+            let on_error = Closure::once_into_js(move |_: web_sys::Event| {
+                let _ = reject.call0(&JsValue::NULL);
+            });
+            request.set_onsuccess(Some(on_success.unchecked_ref()));
+            request.set_onerror(Some(on_error.unchecked_ref()));
+        });
+        JsFuture::from(promise)
+            .await
+            .map_err(|_| "로컬 DB 요청이 실패했습니다.".to_string())
+    }
+}
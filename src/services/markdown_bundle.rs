@@ -0,0 +1,323 @@
+// 문제 한 개당 마크다운 파일 한 개로 구성된 zip 묶음 (git 버전 관리용)
+//
+// 폴더 구조:
+//   <자격증 이름>-<ID 앞 8자리>/_certificate.md
+//   <자격증 이름>-<ID 앞 8자리>/001-<문제 ID 앞 8자리>.md      순번은 문제 수 자릿수에 맞춰 0을 채움 (최소 3자리)
+//   images/<UUID>.<확장자>                                   첨부 이미지
+//
+// 문제 본문의 첨부 이미지는 `../images/<파일 이름>` 상대 경로로 바꿔 넣으므로 저장소에서도
+// 그대로 보이고, 가져올 때 다시 올려 새 주소로 바꿉니다.
+// 본문 구역은 `<!-- quiznote:... -->` 주석 줄로 구분합니다. 렌더링 시에는 보이지 않고,
+// 문제 내용에 `##` 제목이 들어 있어도 구역 경계를 잘못 읽지 않습니다.
+
+use crate::models::{Certificate, ExportedCertificate, Question, QuestionOption};
+use crate::services::ImageBundle;
+use std::collections::BTreeMap;
+use std::io::{Cursor, Read, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+const CERTIFICATE_FILE: &str = "_certificate.md";
+const FRONT_MATTER: &str = "---";
+const QUESTION_MARKER: &str = "<!-- quiznote:question -->";
+const OPTION_MARKER: &str = "<!-- quiznote:option -->";
+const OPTION_EXPLANATION_MARKER: &str = "<!-- quiznote:option-explanation -->";
+const EXPLANATION_MARKER: &str = "<!-- quiznote:explanation -->";
+const IMAGE_FOLDER: &str = "images";
+/// 문제 파일에서 첨부 이미지를 가리키는 경로 앞부분
+pub const IMAGE_REF_PREFIX: &str = "../images/";
+
+/// (순번, 파일 이름) → 문제 파일 내용
+type QuestionFiles = BTreeMap<(u64, String), String>;
+
+pub struct MarkdownBundle;
+
+impl MarkdownBundle {
+    /// 자격증 목록을 마크다운 파일 묶음(zip)으로 만듭니다.
+    pub fn write_zip(certificates: &[ExportedCertificate], images: &ImageBundle) -> Result<Vec<u8>, String> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+
+        for exported in certificates {
+            let folder = Self::folder_name(&exported.certificate);
+
+            zip.start_file(format!("{}/{}", folder, CERTIFICATE_FILE), options)
+                .map_err(|e| e.to_string())?;
+            zip.write_all(Self::render_certificate(&exported.certificate).as_bytes())
+                .map_err(|e| e.to_string())?;
+
+            // 파일 이름순이 곧 문제 순서가 되도록 순번 자릿수를 맞춥니다.
+            let width = exported.questions.len().to_string().len().max(3);
+            for (index, question) in exported.questions.iter().enumerate() {
+                let file_name = format!("{}/{:0width$}-{}.md", folder, index + 1, Self::short_id(&question.id));
+                let question = images.localize(question, IMAGE_REF_PREFIX);
+                zip.start_file(file_name, options).map_err(|e| e.to_string())?;
+                zip.write_all(Self::render_question(&question, &exported.certificate).as_bytes())
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+
+        for image in images.images() {
+            zip.start_file(format!("{}/{}", IMAGE_FOLDER, image.file_name), options)
+                .map_err(|e| e.to_string())?;
+            zip.write_all(&image.bytes).map_err(|e| e.to_string())?;
+        }
+
+        zip.finish().map(|cursor| cursor.into_inner()).map_err(|e| e.to_string())
+    }
+
+    /// `write_zip`으로 만든 zip을 다시 자격증 목록으로 읽습니다.
+    pub fn read_zip(bytes: &[u8]) -> Result<Vec<ExportedCertificate>, String> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("zip 파일을 열 수 없습니다: {}", e))?;
+
+        // 폴더 이름 → (자격증 파일, 문제 파일 순번순 목록)
+        let mut folders: BTreeMap<String, (Option<String>, QuestionFiles)> = BTreeMap::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
+            if file.is_dir() || !file.name().ends_with(".md") {
+                continue;
+            }
+            let path = file.name().to_string();
+            let Some((folder, file_name)) = path.rsplit_once('/') else {
+                continue;
+            };
+
+            let mut text = String::new();
+            file.read_to_string(&mut text)
+                .map_err(|e| format!("{}: {}", path, e))?;
+            // Windows에서 체크아웃한 저장소는 줄바꿈이 CRLF일 수 있습니다.
+            let text = text.replace("\r\n", "\n");
+
+            let entry = folders.entry(folder.to_string()).or_default();
+            if file_name == CERTIFICATE_FILE {
+                entry.0 = Some(text);
+            } else {
+                entry.1.insert((Self::sequence_number(file_name), file_name.to_string()), text);
+            }
+        }
+
+        folders
+            .into_iter()
+            .map(|(folder, (certificate_file, question_files))| {
+                let certificate_file = certificate_file
+                    .ok_or_else(|| format!("{}: {} 파일이 없습니다.", folder, CERTIFICATE_FILE))?;
+                let certificate = Self::parse_certificate(&certificate_file)
+                    .map_err(|e| format!("{}/{}: {}", folder, CERTIFICATE_FILE, e))?;
+                let questions = question_files
+                    .iter()
+                    .map(|((_, name), text)| {
+                        Self::parse_question(text).map_err(|e| format!("{}/{}: {}", folder, name, e))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(ExportedCertificate { certificate, questions })
+            })
+            .collect()
+    }
+
+    /// zip의 `images/` 폴더에 든 첨부 이미지 (파일 이름 → 내용)
+    pub fn read_images(bytes: &[u8]) -> Result<BTreeMap<String, Vec<u8>>, String> {
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("zip 파일을 열 수 없습니다: {}", e))?;
+        let mut images = BTreeMap::new();
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i).map_err(|e| e.to_string())?;
+            let path = file.name().to_string();
+            let Some(name) = path.strip_prefix(IMAGE_FOLDER).and_then(|rest| rest.strip_prefix('/')) else {
+                continue;
+            };
+            if file.is_dir() || name.is_empty() || name.contains('/') {
+                continue;
+            }
+            let mut content = Vec::new();
+            file.read_to_end(&mut content).map_err(|e| format!("{}: {}", path, e))?;
+            images.insert(name.to_string(), content);
+        }
+        Ok(images)
+    }
+
+    pub fn render_certificate(certificate: &Certificate) -> String {
+        let mut out = String::new();
+        out.push_str(FRONT_MATTER);
+        out.push('\n');
+        Self::push_field(&mut out, "id", &certificate.id);
+        Self::push_field(&mut out, "name", &certificate.name);
+        out.push_str(FRONT_MATTER);
+        out.push('\n');
+        out.push_str(&certificate.description);
+        out
+    }
+
+    pub fn render_question(question: &Question, certificate: &Certificate) -> String {
+        let correct: Vec<usize> = question
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.is_correct)
+            .map(|(i, _)| i + 1)
+            .collect();
+        let option_ids: Vec<&str> = question.options.iter().map(|o| o.id.as_str()).collect();
+
+        let mut out = String::new();
+        out.push_str(FRONT_MATTER);
+        out.push('\n');
+        Self::push_field(&mut out, "id", &question.id);
+        Self::push_field(&mut out, "certificate_id", &question.certificate_id);
+        out.push_str(&format!("answer: {}\n", serde_json::to_string(&correct).unwrap_or_default()));
+        out.push_str(&format!("options: {}\n", serde_json::to_string(&option_ids).unwrap_or_default()));
+        out.push_str(&format!("tags: {}\n", serde_json::to_string(&[&certificate.name]).unwrap_or_default()));
+        out.push_str(FRONT_MATTER);
+        out.push('\n');
+
+        Self::push_section(&mut out, QUESTION_MARKER, "## 문제", &question.content);
+        for (index, option) in question.options.iter().enumerate() {
+            let heading = if option.is_correct {
+                format!("### 보기 {} (정답)", index + 1)
+            } else {
+                format!("### 보기 {}", index + 1)
+            };
+            Self::push_section(&mut out, OPTION_MARKER, &heading, &option.content);
+            if !option.explanation.is_empty() {
+                Self::push_section(&mut out, OPTION_EXPLANATION_MARKER, "#### 보기 해설", &option.explanation);
+            }
+        }
+        if !question.explanation.is_empty() {
+            Self::push_section(&mut out, EXPLANATION_MARKER, "## 해설", &question.explanation);
+        }
+        out
+    }
+
+    pub fn parse_certificate(text: &str) -> Result<Certificate, String> {
+        let (fields, body) = Self::split_front_matter(text)?;
+        let mut certificate = Certificate::new(Self::string_field(&fields, "name")?, body.to_string());
+        certificate.id = Self::string_field(&fields, "id")?;
+        Ok(certificate)
+    }
+
+    pub fn parse_question(text: &str) -> Result<Question, String> {
+        let (fields, body) = Self::split_front_matter(text)?;
+        let mut question = Question::new(Self::string_field(&fields, "certificate_id")?, String::new());
+        question.id = Self::string_field(&fields, "id")?;
+
+        let answer: Vec<usize> = Self::json_field(&fields, "answer")?;
+        let option_ids: Vec<String> = Self::json_field(&fields, "options").unwrap_or_default();
+
+        for (marker, content) in Self::split_sections(body)? {
+            match marker {
+                QUESTION_MARKER => question.content = content,
+                OPTION_MARKER => {
+                    let index = question.options.len();
+                    let mut option = QuestionOption::new(content, answer.contains(&(index + 1)));
+                    if let Some(id) = option_ids.get(index) {
+                        option.id = id.clone();
+                    }
+                    option.question_id = question.id.clone();
+                    option.display_order = index as i32;
+                    question.options.push(option);
+                }
+                OPTION_EXPLANATION_MARKER => match question.options.last_mut() {
+                    Some(option) => option.explanation = content,
+                    None => return Err("보기 없이 보기 해설이 있습니다.".to_string()),
+                },
+                _ => question.explanation = content,
+            }
+        }
+        Ok(question)
+    }
+
+    fn push_field(out: &mut String, key: &str, value: &str) {
+        // JSON 문자열 표기는 YAML의 큰따옴표 문자열과 호환됩니다.
+        out.push_str(&format!("{}: {}\n", key, serde_json::to_string(value).unwrap_or_default()));
+    }
+
+    fn push_section(out: &mut String, marker: &str, heading: &str, content: &str) {
+        out.push('\n');
+        out.push_str(marker);
+        out.push('\n');
+        out.push_str(heading);
+        out.push_str("\n\n");
+        out.push_str(content);
+        out.push('\n');
+    }
+
+    fn split_front_matter(text: &str) -> Result<(BTreeMap<String, String>, &str), String> {
+        let rest = text
+            .strip_prefix("---\n")
+            .ok_or_else(|| "front-matter(---)로 시작해야 합니다.".to_string())?;
+        let end = rest
+            .find("\n---\n")
+            .ok_or_else(|| "front-matter가 닫히지 않았습니다.".to_string())?;
+
+        let fields = rest[..end]
+            .lines()
+            .filter_map(|line| line.split_once(':'))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+        Ok((fields, &rest[end + "\n---\n".len()..]))
+    }
+
+    /// 구역 표시 줄을 기준으로 본문을 나눕니다. 각 구역의 제목 줄과
+    /// `push_section`이 덧붙인 줄바꿈은 정확히 그만큼만 제거해 원문을 보존합니다.
+    fn split_sections(body: &str) -> Result<Vec<(&'static str, String)>, String> {
+        const MARKERS: [&str; 4] = [QUESTION_MARKER, OPTION_MARKER, OPTION_EXPLANATION_MARKER, EXPLANATION_MARKER];
+
+        let mut sections: Vec<(&'static str, String)> = Vec::new();
+        let mut rest = body;
+        while let Some(start) = rest.find("\n<!-- quiznote:") {
+            let after = &rest[start + 1..];
+            let marker = MARKERS
+                .iter()
+                .find(|m| after.starts_with(&format!("{}\n", m)))
+                .ok_or_else(|| "알 수 없는 구역 표시가 있습니다.".to_string())?;
+
+            let section = &after[marker.len() + 1..];
+            let content_start = section
+                .find("\n\n")
+                .ok_or_else(|| format!("{} 구역의 제목이 없습니다.", marker))?;
+            sections.push((marker, String::new()));
+            rest = &section[content_start + 2..];
+
+            // 다음 구역 표시 직전까지가 본문이며, 마지막 줄바꿈 한 개는 구분자입니다.
+            match rest.find("\n\n<!-- quiznote:") {
+                Some(next) => {
+                    sections.last_mut().unwrap().1 = rest[..next].to_string();
+                    rest = &rest[next + 1..];
+                }
+                None => {
+                    sections.last_mut().unwrap().1 = rest.strip_suffix('\n').unwrap_or(rest).to_string();
+                    rest = "";
+                }
+            }
+        }
+        Ok(sections)
+    }
+
+    fn string_field(fields: &BTreeMap<String, String>, key: &str) -> Result<String, String> {
+        Self::json_field(fields, key)
+    }
+
+    fn json_field<T: serde::de::DeserializeOwned>(fields: &BTreeMap<String, String>, key: &str) -> Result<T, String> {
+        let raw = fields.get(key).ok_or_else(|| format!("'{}' 항목이 없습니다.", key))?;
+        serde_json::from_str(raw).map_err(|e| format!("'{}' 항목을 읽을 수 없습니다: {}", key, e))
+    }
+
+    fn folder_name(certificate: &Certificate) -> String {
+        let name: String = certificate
+            .name
+            .chars()
+            .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+            .collect();
+        format!("{}-{}", name.trim(), Self::short_id(&certificate.id))
+    }
+
+    /// 문제 파일 이름 앞의 순번. 자릿수가 다른 파일(예전 3자리 묶음의 1000번째 이후)도 숫자 순서로 읽고,
+    /// 저장소에서 직접 추가해 순번이 없는 파일은 맨 뒤에 둡니다.
+    fn sequence_number(file_name: &str) -> u64 {
+        file_name
+            .split_once('-')
+            .and_then(|(number, _)| number.parse().ok())
+            .unwrap_or(u64::MAX)
+    }
+
+    fn short_id(id: &str) -> &str {
+        id.get(..8).unwrap_or(id)
+    }
+}
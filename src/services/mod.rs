@@ -1,11 +1,41 @@
+pub mod account_service;
+pub mod anki_package;
 pub mod auth;
+pub mod backup_crypto;
 pub mod certificate_service;
 pub mod question_service;
 pub mod supabase_client;
 pub mod data_service;
+pub mod data_store;
+pub mod exam_sheet;
+pub mod exam_text_parser;
+pub mod guest_store;
+pub mod image_service;
+pub mod local_db;
+pub mod markdown_bundle;
+pub mod moodle_format;
+pub mod qti_package;
+pub mod schema_service;
+pub mod snapshot_service;
+pub mod sqlite_writer;
+pub mod storage_client;
+pub mod trash_service;
 
+pub use account_service::AccountService;
+pub use anki_package::AnkiPackage;
 pub use auth::AuthService;
+pub use backup_crypto::BackupCrypto;
 pub use certificate_service::CertificateService;
 pub use question_service::QuestionService;
 pub use supabase_client::SupabaseClient;
-pub use data_service::DataService;
\ No newline at end of file
+pub use data_service::DataService;
+pub use data_store::DataStore;
+pub use exam_sheet::ExamSheet;
+pub use exam_text_parser::ExamTextParser;
+pub use image_service::{ImageBundle, ImageService};
+pub use markdown_bundle::MarkdownBundle;
+pub use moodle_format::{AikenFormat, GiftFormat};
+pub use qti_package::QtiPackage;
+pub use schema_service::SchemaService;
+pub use snapshot_service::SnapshotService;
+pub use trash_service::TrashService;
@@ -0,0 +1,549 @@
+// Moodle GIFT / Aiken 형식 가져오기·내보내기
+//
+// 두 형식 모두 객관식 외의 문제 유형(단답형, 짝짓기, 숫자형, 서술형 등)을 지원하지만
+// QuizNote에는 대응하는 구조가 없으므로 가져올 때 건너뛰고 warnings로 알려줍니다.
+
+use crate::models::{Certificate, ExportedCertificate, Question, QuestionOption};
+
+/// 가져오기 결과와 변환하지 못한 항목 목록
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FormatImport {
+    pub certificates: Vec<ExportedCertificate>,
+    pub warnings: Vec<String>,
+}
+
+/// 내보내기 결과와 형식 제약 때문에 빠진 항목 목록
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FormatExport {
+    pub text: String,
+    pub warnings: Vec<String>,
+}
+
+impl FormatImport {
+    /// 이름이 같은 자격증을 찾거나 새로 만들어 문제를 추가합니다.
+    fn push_question(&mut self, certificate_name: &str, mut question: Question) {
+        let index = match self.certificates.iter().position(|c| c.certificate.name == certificate_name) {
+            Some(index) => index,
+            None => {
+                self.certificates.push(ExportedCertificate {
+                    certificate: Certificate::new(certificate_name.to_string(), String::new()),
+                    questions: Vec::new(),
+                });
+                self.certificates.len() - 1
+            }
+        };
+        let exported = &mut self.certificates[index];
+        question.certificate_id = exported.certificate.id.clone();
+        for (i, option) in question.options.iter_mut().enumerate() {
+            option.display_order = i as i32;
+        }
+        exported.questions.push(question);
+    }
+}
+
+pub struct GiftFormat;
+
+impl GiftFormat {
+    const TRUE_LABEL: &'static str = "참";
+    const FALSE_LABEL: &'static str = "거짓";
+
+    /// GIFT 텍스트를 읽습니다. `$CATEGORY:`가 없으면 `default_name` 자격증에 넣습니다.
+    pub fn parse(text: &str, default_name: &str) -> FormatImport {
+        let mut result = FormatImport::default();
+        let mut category = default_name.to_string();
+
+        for (number, block) in Self::split_blocks(text).into_iter().enumerate() {
+            let label = format!("GIFT {}번째 항목", number + 1);
+
+            if let Some(rest) = block.strip_prefix("$CATEGORY:") {
+                // "$course$/top/정보처리기사" 형태면 마지막 경로만 사용합니다.
+                category = rest.trim().rsplit('/').next().unwrap_or_default().trim().to_string();
+                if category.is_empty() {
+                    category = default_name.to_string();
+                }
+                continue;
+            }
+
+            match Self::parse_question(&block, &label, &mut result.warnings) {
+                Some(question) => result.push_question(&category, question),
+                None => continue,
+            }
+        }
+        result
+    }
+
+    pub fn write(certificates: &[ExportedCertificate]) -> FormatExport {
+        let mut export = FormatExport::default();
+        export.text.push_str("// QuizNote에서 내보낸 GIFT 파일\n\n");
+
+        for exported in certificates {
+            export.text.push_str(&format!("$CATEGORY: {}\n\n", exported.certificate.name.replace('/', "／")));
+            if !exported.certificate.description.is_empty() {
+                export.warnings.push(format!("{}: 자격증 설명은 GIFT에 포함되지 않습니다.", exported.certificate.name));
+            }
+
+            let correct_total = |q: &Question| q.options.iter().filter(|o| o.is_correct).count();
+            for question in &exported.questions {
+                let correct_count = correct_total(question);
+                // 제목은 생략합니다. Moodle은 문제 내용 앞부분을 제목으로 사용합니다.
+                export.text.push_str("[markdown]");
+                export.text.push_str(&Self::escape(&question.content));
+                export.text.push_str(" {\n");
+
+                for option in &question.options {
+                    let prefix = match (correct_count > 1, option.is_correct) {
+                        (false, true) => "=".to_string(),
+                        (false, false) => "~".to_string(),
+                        (true, true) => format!("~%{}%", Self::weight(correct_count)),
+                        (true, false) => "~%-100%".to_string(),
+                    };
+                    export.text.push('\t');
+                    export.text.push_str(&prefix);
+                    export.text.push_str(&Self::escape(&option.content));
+                    if !option.explanation.is_empty() {
+                        export.text.push('#');
+                        export.text.push_str(&Self::escape(&option.explanation));
+                    }
+                    export.text.push('\n');
+                }
+                if !question.explanation.is_empty() {
+                    export.text.push_str("\t####");
+                    export.text.push_str(&Self::escape(&question.explanation));
+                    export.text.push('\n');
+                }
+                export.text.push_str("}\n\n");
+            }
+        }
+        export
+    }
+
+    fn parse_question(block: &str, label: &str, warnings: &mut Vec<String>) -> Option<Question> {
+        let mut rest = block;
+
+        // ::제목:: 은 QuizNote에 대응하는 필드가 없습니다.
+        if let Some(after) = rest.strip_prefix("::") {
+            if let Some(end) = Self::find_unescaped(after, "::") {
+                warnings.push(format!("{}: 문제 제목 '{}'은(는) 가져오지 않았습니다.", label, Self::unescape(&after[..end])));
+                rest = after[end + 2..].trim_start();
+            }
+        }
+
+        // [markdown] / [html] / [moodle] / [plain] 서식 표시. 마크다운과 HTML은 그대로 사용할 수 있습니다.
+        if rest.starts_with('[') {
+            if let Some(end) = rest.find(']') {
+                let format = &rest[1..end];
+                if matches!(format, "markdown" | "html" | "moodle" | "plain") {
+                    rest = rest[end + 1..].trim_start();
+                }
+            }
+        }
+
+        let Some(open) = Self::find_unescaped(rest, "{") else {
+            warnings.push(format!("{}: 답안이 없는 설명 항목이라 건너뛰었습니다.", label));
+            return None;
+        };
+        let Some(close) = Self::find_unescaped(&rest[open + 1..], "}").map(|i| i + open + 1) else {
+            warnings.push(format!("{}: 닫는 중괄호(}})가 없어 건너뛰었습니다.", label));
+            return None;
+        };
+
+        let before = rest[..open].trim();
+        let after = rest[close + 1..].trim();
+        let answer = rest[open + 1..close].trim();
+
+        let mut content = Self::unescape(before);
+        if !after.is_empty() {
+            // "A는 {=x ~y} 이다." 같은 빈칸형은 빈칸을 밑줄로 바꿔 이어 붙입니다.
+            content = format!("{} ____ {}", content, Self::unescape(after)).trim().to_string();
+            warnings.push(format!("{}: 빈칸형 문제를 '____'가 들어간 일반 문제로 바꿨습니다.", label));
+        }
+
+        let (answer, general_feedback) = match Self::find_unescaped(answer, "####") {
+            Some(i) => (answer[..i].trim(), Some(Self::unescape(answer[i + 4..].trim()))),
+            None => (answer, None),
+        };
+
+        let options = if answer.is_empty() {
+            warnings.push(format!("{}: 서술형 문제는 가져올 수 없습니다.", label));
+            return None;
+        } else if answer.starts_with('#') {
+            warnings.push(format!("{}: 숫자형 문제는 가져올 수 없습니다.", label));
+            return None;
+        } else if let Some(options) = Self::parse_true_false(answer) {
+            options
+        } else {
+            Self::parse_choices(answer, label, warnings)?
+        };
+
+        let mut question = Question::new(String::new(), content);
+        question.options = options;
+        question.explanation = general_feedback.unwrap_or_default();
+        Some(question)
+    }
+
+    /// {T}, {FALSE#오답 피드백#정답 피드백} 형식의 참/거짓 문제
+    fn parse_true_false(answer: &str) -> Option<Vec<QuestionOption>> {
+        let parts = Self::split_unescaped(answer, '#');
+        let is_true = match parts[0].trim() {
+            "T" | "TRUE" => true,
+            "F" | "FALSE" => false,
+            _ => return None,
+        };
+        let wrong_feedback = parts.get(1).map(|s| Self::unescape(s.trim())).unwrap_or_default();
+        let right_feedback = parts.get(2).map(|s| Self::unescape(s.trim())).unwrap_or_default();
+
+        let mut true_option = QuestionOption::new(Self::TRUE_LABEL.to_string(), is_true);
+        let mut false_option = QuestionOption::new(Self::FALSE_LABEL.to_string(), !is_true);
+        let (correct, wrong) = if is_true {
+            (&mut true_option, &mut false_option)
+        } else {
+            (&mut false_option, &mut true_option)
+        };
+        correct.explanation = right_feedback;
+        wrong.explanation = wrong_feedback;
+        Some(vec![true_option, false_option])
+    }
+
+    /// =정답 ~오답 ~%50%부분정답 형식의 객관식 보기
+    fn parse_choices(answer: &str, label: &str, warnings: &mut Vec<String>) -> Option<Vec<QuestionOption>> {
+        let mut items: Vec<(char, String)> = Vec::new();
+        let mut chars = answer.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some(last) = items.last_mut() {
+                        last.1.push(c);
+                        if let Some(next) = chars.next() {
+                            last.1.push(next);
+                        }
+                    }
+                }
+                '=' | '~' => items.push((c, String::new())),
+                _ => {
+                    if let Some(last) = items.last_mut() {
+                        last.1.push(c);
+                    }
+                }
+            }
+        }
+
+        if items.iter().any(|(_, text)| Self::find_unescaped(text, "->").is_some()) {
+            warnings.push(format!("{}: 짝짓기 문제는 가져올 수 없습니다.", label));
+            return None;
+        }
+        if !items.iter().any(|(prefix, _)| *prefix == '~') {
+            warnings.push(format!("{}: 단답형 문제는 가져올 수 없습니다.", label));
+            return None;
+        }
+
+        let mut partial_credit = false;
+        let options = items
+            .into_iter()
+            .map(|(prefix, text)| {
+                let mut text = text.trim();
+                let mut is_correct = prefix == '=';
+                if let Some(after) = text.strip_prefix('%') {
+                    if let Some(end) = after.find('%') {
+                        let weight: f64 = after[..end].trim().parse().unwrap_or(0.0);
+                        is_correct = weight > 0.0;
+                        partial_credit |= weight > 0.0 && weight < 100.0;
+                        text = after[end + 1..].trim();
+                    }
+                }
+
+                let parts = Self::split_unescaped(text, '#');
+                let mut option = QuestionOption::new(Self::unescape(parts[0].trim()), is_correct);
+                option.explanation = parts[1..].iter().map(|p| Self::unescape(p.trim())).collect::<Vec<_>>().join("\n");
+                option
+            })
+            .collect::<Vec<_>>();
+
+        if partial_credit {
+            warnings.push(format!("{}: 부분 점수는 정답/오답으로만 변환했습니다.", label));
+        }
+        if !options.iter().any(|o| o.is_correct) {
+            warnings.push(format!("{}: 정답이 없어 건너뛰었습니다.", label));
+            return None;
+        }
+        Some(options)
+    }
+
+    /// 빈 줄로 문제를 나눕니다. 주석(//) 줄은 버리고, 중괄호 안의 빈 줄은 나누지 않습니다.
+    fn split_blocks(text: &str) -> Vec<String> {
+        let mut blocks = Vec::new();
+        let mut current = String::new();
+        let mut depth = 0i32;
+
+        for line in text.replace("\r\n", "\n").lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with("//") {
+                continue;
+            }
+            if trimmed.is_empty() && depth == 0 {
+                if !current.trim().is_empty() {
+                    blocks.push(current.trim().to_string());
+                }
+                current.clear();
+                continue;
+            }
+            if trimmed.starts_with("$CATEGORY:") && depth == 0 {
+                if !current.trim().is_empty() {
+                    blocks.push(current.trim().to_string());
+                }
+                blocks.push(trimmed.to_string());
+                current.clear();
+                continue;
+            }
+
+            let mut escaped = false;
+            for c in line.chars() {
+                match c {
+                    _ if escaped => escaped = false,
+                    '\\' => escaped = true,
+                    '{' => depth += 1,
+                    '}' => depth -= 1,
+                    _ => {}
+                }
+            }
+            if !current.is_empty() {
+                current.push('\n');
+            }
+            current.push_str(line);
+        }
+        if !current.trim().is_empty() {
+            blocks.push(current.trim().to_string());
+        }
+        blocks
+    }
+
+    fn find_unescaped(text: &str, pattern: &str) -> Option<usize> {
+        let mut escaped = false;
+        for (i, c) in text.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if text[i..].starts_with(pattern) {
+                return Some(i);
+            }
+        }
+        None
+    }
+
+    fn split_unescaped(text: &str, separator: char) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut start = 0;
+        let mut escaped = false;
+        for (i, c) in text.char_indices() {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == separator {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+        }
+        parts.push(&text[start..]);
+        parts
+    }
+
+    fn unescape(text: &str) -> String {
+        let mut out = String::new();
+        let mut chars = text.chars();
+        while let Some(c) = chars.next() {
+            if c == '\\' {
+                match chars.next() {
+                    Some('n') => out.push('\n'),
+                    Some(next) => out.push(next),
+                    None => out.push('\\'),
+                }
+            } else {
+                out.push(c);
+            }
+        }
+        out
+    }
+
+    fn escape(text: &str) -> String {
+        let mut out = String::new();
+        for c in text.chars() {
+            match c {
+                '\\' | '~' | '=' | '#' | '{' | '}' | ':' => {
+                    out.push('\\');
+                    out.push(c);
+                }
+                '\n' => out.push_str("\\n"),
+                '\r' => {}
+                _ => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// 복수 정답 문제의 정답 가중치. 합이 100%가 되도록 나눕니다.
+    fn weight(correct_count: usize) -> String {
+        let weight = 100.0 / correct_count as f64;
+        format!("{:.5}", weight).trim_end_matches('0').trim_end_matches('.').to_string()
+    }
+}
+
+pub struct AikenFormat;
+
+impl AikenFormat {
+    const LETTERS: &'static str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    /// Aiken 텍스트를 읽습니다. Aiken에는 분류가 없으므로 모두 `certificate_name`에 넣습니다.
+    pub fn parse(text: &str, certificate_name: &str) -> FormatImport {
+        let mut result = FormatImport::default();
+        let mut stem: Vec<String> = Vec::new();
+        let mut options: Vec<QuestionOption> = Vec::new();
+        let mut number = 1;
+
+        for line in text.replace("\r\n", "\n").lines() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(answer) = line.strip_prefix("ANSWER:") {
+                let label = format!("Aiken {}번 문제", number);
+                number += 1;
+                let answer = answer.trim();
+                let index = answer.chars().next().and_then(|c| Self::LETTERS.find(c));
+
+                match index {
+                    _ if stem.is_empty() || options.len() < 2 => {
+                        result.warnings.push(format!("{}: 문제나 보기가 부족해 건너뛰었습니다.", label));
+                    }
+                    Some(index) if index < options.len() && answer.len() == 1 => {
+                        options[index].is_correct = true;
+                        let mut question = Question::new(String::new(), stem.join("\n"));
+                        question.options = std::mem::take(&mut options);
+                        result.push_question(certificate_name, question);
+                    }
+                    _ => result.warnings.push(format!("{}: 정답 '{}'을(를) 보기와 맞출 수 없습니다.", label, answer)),
+                }
+                stem.clear();
+                options.clear();
+                continue;
+            }
+
+            match Self::split_option(line) {
+                Some(content) if options.len() == Self::LETTERS.find(line.chars().next().unwrap_or(' ')).unwrap_or(usize::MAX) => {
+                    options.push(QuestionOption::new(content.to_string(), false));
+                }
+                _ if options.is_empty() => stem.push(line.to_string()),
+                _ => {
+                    result.warnings.push(format!(
+                        "Aiken {}번 문제: 보기 형식이 아닌 줄 '{}'을(를) 무시했습니다.",
+                        number, line
+                    ));
+                }
+            }
+        }
+
+        if !stem.is_empty() || !options.is_empty() {
+            result.warnings.push(format!("Aiken {}번 문제: ANSWER: 줄이 없어 건너뛰었습니다.", number));
+        }
+        result
+    }
+
+    pub fn write(certificates: &[ExportedCertificate]) -> FormatExport {
+        let mut export = FormatExport::default();
+        let mut dropped_explanations = 0;
+
+        for exported in certificates {
+            for (index, question) in exported.questions.iter().enumerate() {
+                let label = format!("{} {}번", exported.certificate.name, index + 1);
+                let correct: Vec<usize> = question
+                    .options
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, o)| o.is_correct)
+                    .map(|(i, _)| i)
+                    .collect();
+
+                if correct.len() != 1 {
+                    export.warnings.push(format!("{}: Aiken은 정답이 하나인 문제만 지원해 제외했습니다.", label));
+                    continue;
+                }
+                if question.options.len() > Self::LETTERS.len() {
+                    export.warnings.push(format!("{}: 보기가 26개를 넘어 제외했습니다.", label));
+                    continue;
+                }
+                if !question.explanation.is_empty() || question.options.iter().any(|o| !o.explanation.is_empty()) {
+                    dropped_explanations += 1;
+                }
+                let mut flattened: Vec<&str> = Vec::new();
+                for text in std::iter::once(&question.content).chain(question.options.iter().map(|o| &o.content)) {
+                    if let Some(kind) = Self::multi_line_kind(text) {
+                        if !flattened.contains(&kind) {
+                            flattened.push(kind);
+                        }
+                    }
+                }
+                if !flattened.is_empty() {
+                    export.warnings.push(format!(
+                        "{}: Aiken은 한 줄만 지원해 {}을(를) 한 줄로 합쳤습니다.",
+                        label,
+                        flattened.join(", ")
+                    ));
+                }
+
+                export.text.push_str(&Self::single_line(&question.content));
+                export.text.push('\n');
+                for (i, option) in question.options.iter().enumerate() {
+                    export.text.push_str(&format!("{}. {}\n", &Self::LETTERS[i..i + 1], Self::single_line(&option.content)));
+                }
+                export.text.push_str(&format!("ANSWER: {}\n\n", &Self::LETTERS[correct[0]..correct[0] + 1]));
+            }
+        }
+
+        if dropped_explanations > 0 {
+            export.warnings.push(format!(
+                "Aiken은 해설을 지원하지 않아 {}개 문제의 해설이 빠졌습니다.",
+                dropped_explanations
+            ));
+        }
+        export
+    }
+
+    /// "A. 보기" / "A) 보기" 형식이면 보기 내용을 반환합니다.
+    fn split_option(line: &str) -> Option<&str> {
+        let mut chars = line.chars();
+        let letter = chars.next()?;
+        if !letter.is_ascii_uppercase() {
+            return None;
+        }
+        let rest = chars.as_str();
+        let rest = rest.strip_prefix('.').or_else(|| rest.strip_prefix(')'))?;
+        rest.strip_prefix(' ').map(str::trim)
+    }
+
+    /// 한 줄로 합치면 모양이 사라지는 구성. 한 줄이면 `None`
+    fn multi_line_kind(text: &str) -> Option<&'static str> {
+        let text = text.trim();
+        if !text.contains('\n') {
+            return None;
+        }
+        let is_list_item = |line: &str| {
+            let line = line.trim_start();
+            line.starts_with("- ")
+                || line.starts_with("* ")
+                || line.split_once(". ").is_some_and(|(n, _)| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+        };
+        Some(if text.contains("```") || text.contains("~~~") {
+            "코드 블록"
+        } else if text.lines().any(is_list_item) {
+            "목록"
+        } else {
+            "여러 줄 문장"
+        })
+    }
+
+    /// Aiken은 문제와 보기가 한 줄이어야 합니다.
+    fn single_line(text: &str) -> String {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    }
+}
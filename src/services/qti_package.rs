@@ -0,0 +1,244 @@
+// IMS QTI 2.1 콘텐츠 패키지(zip) 내보내기
+//
+// 패키지 구성:
+//   imsmanifest.xml              문항 목록 (IMS Content Packaging 1.1)
+//   items/<문제 ID>.xml           문제 한 개당 assessmentItem 한 개
+//   images/<UUID>.<확장자>        첨부 이미지 (문항에서는 `../images/...`로 가리킵니다)
+//
+// 정답이 하나면 단일 선택(cardinality="single"), 여러 개면 다중 선택으로 만듭니다.
+// 보기 해설은 해당 보기를 골랐을 때 보이는 modalFeedback, 문제 해설은 항상 보이는
+// modalFeedback이 됩니다.
+
+use crate::models::{Certificate, ExportedCertificate, Question};
+use crate::services::ImageBundle;
+use pulldown_cmark::{html, Event, Options, Parser};
+use std::io::{Cursor, Write};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+const QTI_NAMESPACE: &str = "http://www.imsglobal.org/xsd/imsqti_v2p1";
+const QTI_SCHEMA: &str = "http://www.imsglobal.org/xsd/qti/qtiv2p1/imsqti_v2p1.xsd";
+const CP_NAMESPACE: &str = "http://www.imsglobal.org/xsd/imscp_v1p1";
+const CP_SCHEMA: &str = "http://www.imsglobal.org/xsd/imscp_v1p1.xsd";
+const MD_NAMESPACE: &str = "http://www.imsglobal.org/xsd/imsmd_v1p2";
+const MD_SCHEMA: &str = "http://www.imsglobal.org/xsd/imsmd_v1p2p4.xsd";
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+const GENERAL_FEEDBACK: &str = "GENERAL";
+const IMAGE_FOLDER: &str = "images";
+
+pub struct QtiPackage;
+
+impl QtiPackage {
+    pub fn build(certificates: &[ExportedCertificate], images: &ImageBundle) -> Result<Vec<u8>, String> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut resources = String::new();
+
+        for exported in certificates {
+            for (index, question) in exported.questions.iter().enumerate() {
+                let title = format!("{} {}번", exported.certificate.name, index + 1);
+                let href = format!("items/{}.xml", question.id);
+
+                let files: Vec<String> = images
+                    .used_by(question)
+                    .map(|image| format!("{}/{}", IMAGE_FOLDER, image.file_name))
+                    .collect();
+                let localized = images.localize(question, &format!("../{}/", IMAGE_FOLDER));
+
+                zip.start_file(href.as_str(), options).map_err(|e| e.to_string())?;
+                zip.write_all(Self::render_item(&localized, &title).as_bytes())
+                    .map_err(|e| e.to_string())?;
+                resources.push_str(&Self::render_resource(question, &exported.certificate, &title, &href, &files));
+            }
+        }
+
+        for image in images.images() {
+            zip.start_file(format!("{}/{}", IMAGE_FOLDER, image.file_name), options)
+                .map_err(|e| e.to_string())?;
+            zip.write_all(&image.bytes).map_err(|e| e.to_string())?;
+        }
+
+        zip.start_file("imsmanifest.xml", options).map_err(|e| e.to_string())?;
+        zip.write_all(Self::render_manifest(&resources).as_bytes())
+            .map_err(|e| e.to_string())?;
+
+        zip.finish().map(|cursor| cursor.into_inner()).map_err(|e| e.to_string())
+    }
+
+    fn render_manifest(resources: &str) -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<manifest xmlns="{cp}" xmlns:imsmd="{md}" xmlns:xsi="{xsi}" identifier="MANIFEST-{id}" xsi:schemaLocation="{cp} {cp_schema} {md} {md_schema} {qti} {qti_schema}">
+  <metadata>
+    <schema>IMS Content</schema>
+    <schemaversion>1.1.4</schemaversion>
+  </metadata>
+  <organizations/>
+  <resources>
+{resources}  </resources>
+</manifest>
+"#,
+            cp = CP_NAMESPACE,
+            md = MD_NAMESPACE,
+            xsi = XSI_NAMESPACE,
+            qti = QTI_NAMESPACE,
+            cp_schema = CP_SCHEMA,
+            md_schema = MD_SCHEMA,
+            qti_schema = QTI_SCHEMA,
+            id = uuid::Uuid::new_v4(),
+            resources = resources,
+        )
+    }
+
+    /// 매니페스트의 resource 항목. 자격증 이름은 LOM 키워드로 남겨 LMS에서 분류할 수 있게 합니다.
+    /// `files`는 문항이 가리키는 첨부 이미지 경로입니다.
+    fn render_resource(question: &Question, certificate: &Certificate, title: &str, href: &str, files: &[String]) -> String {
+        let extra_files: String = files
+            .iter()
+            .map(|file| format!("      <file href=\"{}\"/>\n", Self::escape(file)))
+            .collect();
+        format!(
+            r#"    <resource identifier="RES-{id}" type="imsqti_item_xmlv2p1" href="{href}">
+      <metadata>
+        <imsmd:lom>
+          <imsmd:general>
+            <imsmd:title><imsmd:langstring xml:lang="ko">{title}</imsmd:langstring></imsmd:title>
+            <imsmd:keyword><imsmd:langstring xml:lang="ko">{keyword}</imsmd:langstring></imsmd:keyword>
+          </imsmd:general>
+        </imsmd:lom>
+      </metadata>
+      <file href="{href}"/>
+{extra_files}    </resource>
+"#,
+            id = question.id,
+            href = Self::escape(href),
+            title = Self::escape(title),
+            keyword = Self::escape(&certificate.name),
+            extra_files = extra_files,
+        )
+    }
+
+    fn render_item(question: &Question, title: &str) -> String {
+        let correct: Vec<String> = question
+            .options
+            .iter()
+            .enumerate()
+            .filter(|(_, o)| o.is_correct)
+            .map(|(i, _)| Self::choice_id(i))
+            .collect();
+        let (cardinality, max_choices) = if correct.len() > 1 { ("multiple", 0) } else { ("single", 1) };
+        let has_general_feedback = !question.explanation.trim().is_empty();
+
+        let mut out = String::new();
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        out.push_str(&format!(
+            "<assessmentItem xmlns=\"{ns}\" xmlns:xsi=\"{xsi}\" xsi:schemaLocation=\"{ns} {schema}\" \
+             identifier=\"ITEM-{id}\" title=\"{title}\" adaptive=\"false\" timeDependent=\"false\">\n",
+            ns = QTI_NAMESPACE,
+            xsi = XSI_NAMESPACE,
+            schema = QTI_SCHEMA,
+            id = question.id,
+            title = Self::escape(title),
+        ));
+
+        // 응답 및 점수 선언
+        out.push_str(&format!(
+            "  <responseDeclaration identifier=\"RESPONSE\" cardinality=\"{}\" baseType=\"identifier\">\n    <correctResponse>\n",
+            cardinality
+        ));
+        for id in &correct {
+            out.push_str(&format!("      <value>{}</value>\n", id));
+        }
+        out.push_str("    </correctResponse>\n  </responseDeclaration>\n");
+        out.push_str(
+            "  <outcomeDeclaration identifier=\"SCORE\" cardinality=\"single\" baseType=\"float\">\n    \
+             <defaultValue><value>0</value></defaultValue>\n  </outcomeDeclaration>\n",
+        );
+        out.push_str("  <outcomeDeclaration identifier=\"FEEDBACK\" cardinality=\"multiple\" baseType=\"identifier\"/>\n");
+
+        // 문제 본문과 보기
+        out.push_str("  <itemBody>\n");
+        out.push_str(&format!("    <div>{}</div>\n", Self::xhtml(&question.content)));
+        out.push_str(&format!(
+            "    <choiceInteraction responseIdentifier=\"RESPONSE\" shuffle=\"false\" maxChoices=\"{}\">\n",
+            max_choices
+        ));
+        for (index, option) in question.options.iter().enumerate() {
+            out.push_str(&format!(
+                "      <simpleChoice identifier=\"{}\">{}</simpleChoice>\n",
+                Self::choice_id(index),
+                Self::xhtml(&option.content)
+            ));
+        }
+        out.push_str("    </choiceInteraction>\n  </itemBody>\n");
+
+        // 채점: 정답과 일치하면 1점, 고른 보기와 전체 해설을 FEEDBACK에 담습니다.
+        out.push_str(
+            "  <responseProcessing>\n    <responseCondition>\n      <responseIf>\n        \
+             <match><variable identifier=\"RESPONSE\"/><correct identifier=\"RESPONSE\"/></match>\n        \
+             <setOutcomeValue identifier=\"SCORE\"><baseValue baseType=\"float\">1</baseValue></setOutcomeValue>\n      \
+             </responseIf>\n      <responseElse>\n        \
+             <setOutcomeValue identifier=\"SCORE\"><baseValue baseType=\"float\">0</baseValue></setOutcomeValue>\n      \
+             </responseElse>\n    </responseCondition>\n    <setOutcomeValue identifier=\"FEEDBACK\">\n      \
+             <multiple>\n        <variable identifier=\"RESPONSE\"/>\n",
+        );
+        if has_general_feedback {
+            out.push_str(&format!(
+                "        <baseValue baseType=\"identifier\">{}</baseValue>\n",
+                GENERAL_FEEDBACK
+            ));
+        }
+        out.push_str("      </multiple>\n    </setOutcomeValue>\n  </responseProcessing>\n");
+
+        // 피드백
+        for (index, option) in question.options.iter().enumerate() {
+            if option.explanation.trim().is_empty() {
+                continue;
+            }
+            out.push_str(&format!(
+                "  <modalFeedback outcomeIdentifier=\"FEEDBACK\" identifier=\"{}\" showHide=\"show\">{}</modalFeedback>\n",
+                Self::choice_id(index),
+                Self::xhtml(&option.explanation)
+            ));
+        }
+        if has_general_feedback {
+            out.push_str(&format!(
+                "  <modalFeedback outcomeIdentifier=\"FEEDBACK\" identifier=\"{}\" showHide=\"show\">{}</modalFeedback>\n",
+                GENERAL_FEEDBACK,
+                Self::xhtml(&question.explanation)
+            ));
+        }
+
+        out.push_str("</assessmentItem>\n");
+        out
+    }
+
+    /// 마크다운을 QTI 본문에 넣을 수 있는 XHTML로 변환합니다.
+    /// QTI의 XHTML 부분집합에 없는 취소선·체크박스는 끄고, 원본 HTML은 잘 닫혀 있다는
+    /// 보장이 없으므로 글자 그대로 이스케이프합니다.
+    fn xhtml(content: &str) -> String {
+        let mut options = Options::empty();
+        options.insert(Options::ENABLE_TABLES);
+        options.insert(Options::ENABLE_FOOTNOTES);
+
+        let parser = Parser::new_ext(content, options).map(|event| match event {
+            Event::Html(raw) => Event::Text(raw),
+            other => other,
+        });
+        let mut output = String::new();
+        html::push_html(&mut output, parser);
+        output.trim_end().to_string()
+    }
+
+    fn choice_id(index: usize) -> String {
+        format!("CHOICE_{}", index + 1)
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;")
+            .replace('"', "&quot;")
+    }
+}
@@ -1,45 +1,59 @@
 use crate::models::Question;
-use crate::services::SupabaseClient;
+use crate::services::{DataStore, ImageService, SnapshotService};
 
 pub struct QuestionService;
 
 impl QuestionService {
     pub async fn get_by_certificate(certificate_id: &str) -> Result<Vec<Question>, String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         let mut quests = client.get_questions_by_certificate(certificate_id).await?;
-        quests.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        quests.sort_by_key(|q| std::cmp::Reverse(q.created_at));
         Ok(quests)
     }
 
     pub async fn get_by_id(id: &str) -> Result<Question, String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         client.get_question_by_id(id).await
     }
 
     pub async fn create(mut question: Question) -> Result<Question, String> {
         Self::validate_question(&mut question)?;
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         client.create_question(&question).await?;
         Ok(question)
     }
 
     pub async fn update(mut question: Question) -> Result<Question, String> {
         Self::validate_question(&mut question)?;
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         client.update_question(&question).await?;
         Ok(question)
     }
 
     pub async fn update_stats(question: &Question) -> Result<(), String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         client.update_question_stats(question).await
     }
 
+    /// 휴지통으로 옮깁니다.
     pub async fn delete(id: &str) -> Result<(), String> {
-        let client = SupabaseClient::new();
+        let client = DataStore::current();
         client.delete_question(id).await
     }
 
+    pub async fn restore(id: &str) -> Result<(), String> {
+        let client = DataStore::current();
+        client.restore_question(id).await
+    }
+
+    pub async fn purge(id: &str) -> Result<(), String> {
+        SnapshotService::take_before("문제 영구 삭제 전").await;
+        let client = DataStore::current();
+        client.purge_question(id).await?;
+        ImageService::sweep_after_purge().await;
+        Ok(())
+    }
+
     fn validate_question(question: &mut Question) -> Result<(), String> {
         if question.options.len() < 2 {
             return Err("최소 2개의 보기가 필요합니다.".to_string());
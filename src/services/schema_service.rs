@@ -0,0 +1,158 @@
+// 서버 스키마 호환성 확인
+//
+// 앱을 시작할 때 서버에 앱이 읽고 쓰는 테이블과 열이 모두 있는지 확인합니다. 행은 읽지 않고(`limit=0`)
+// 열 이름만 물어보므로 로그인하지 않아도 됩니다. 빠진 열이 있으면 그 열을 만드는 마이그레이션
+// (`supabase/migrations/`) 이름과 함께 알려줍니다.
+
+use crate::config::SUPABASE_CONFIG;
+use futures::future::join_all;
+use gloo_net::http::Request;
+use serde::Deserialize;
+
+const INITIAL: &str = "20261019000000_initial_schema.sql";
+const TRASH: &str = "20261019000100_trash.sql";
+const IMPORTED: &str = "20261019000200_imported_certificates.sql";
+
+/// 테이블마다 (열, 그 열을 만드는 마이그레이션)
+const REQUIRED_SCHEMA: &[(&str, &[(&str, &str)])] = &[
+    (
+        "certificates",
+        &[
+            ("id", INITIAL),
+            ("name", INITIAL),
+            ("description", INITIAL),
+            ("question_count", INITIAL),
+            ("created_at", INITIAL),
+            ("deleted_at", TRASH),
+            ("imported", IMPORTED),
+        ],
+    ),
+    (
+        "questions",
+        &[
+            ("id", INITIAL),
+            ("certificate_id", INITIAL),
+            ("content", INITIAL),
+            ("explanation", INITIAL),
+            ("created_at", INITIAL),
+            ("last_attempt", INITIAL),
+            ("attempt_count", INITIAL),
+            ("correct_count", INITIAL),
+            ("deleted_at", TRASH),
+        ],
+    ),
+    (
+        "question_options",
+        &[
+            ("id", INITIAL),
+            ("question_id", INITIAL),
+            ("content", INITIAL),
+            ("is_correct", INITIAL),
+            ("explanation", INITIAL),
+            ("display_order", INITIAL),
+        ],
+    ),
+];
+
+#[derive(Deserialize, Default)]
+struct PostgrestError {
+    #[serde(default)]
+    code: String,
+    #[serde(default)]
+    message: String,
+}
+
+/// 한 번의 확인 결과
+enum Probe {
+    Ok,
+    /// 테이블이 없음
+    MissingTable,
+    /// 열이 없음 (어느 열인지는 하나씩 다시 물어봐야 압니다)
+    MissingColumn,
+    /// 서버가 키를 받아주지 않음
+    Unauthorized(String),
+    /// 네트워크 오류 등 스키마와 상관없는 실패
+    Unavailable(String),
+}
+
+pub struct SchemaService;
+
+impl SchemaService {
+    /// 스키마가 맞지 않으면 관리자에게 보여줄 문장을 돌려줍니다.
+    /// 서버에 연결할 수 없을 때는 판단할 수 없으므로 통과시킵니다. (게스트 모드는 서버 없이도 씁니다)
+    pub async fn check() -> Result<(), String> {
+        let results = join_all(REQUIRED_SCHEMA.iter().map(|(table, columns)| async move {
+            let names: Vec<&str> = columns.iter().map(|(name, _)| *name).collect();
+            (*table, Self::probe(table, &names.join(",")).await)
+        }))
+        .await;
+
+        let mut problems = Vec::new();
+        let mut migrations = Vec::new();
+        for (table, probe) in results {
+            let columns = REQUIRED_SCHEMA.iter().find(|(name, _)| *name == table).map(|(_, c)| *c).unwrap_or_default();
+            match probe {
+                Probe::Ok => {}
+                Probe::MissingTable => {
+                    problems.push(format!("{} 테이블이 없습니다.", table));
+                    migrations.push(INITIAL);
+                }
+                Probe::MissingColumn => {
+                    for (column, migration) in columns {
+                        if let Probe::MissingColumn = Self::probe(table, column).await {
+                            problems.push(format!("{}.{} 열이 없습니다.", table, column));
+                            migrations.push(*migration);
+                        }
+                    }
+                }
+                Probe::Unauthorized(message) => {
+                    return Err(format!(
+                        "서버가 anon key를 받아주지 않습니다. 서버 주소와 키가 맞는지 확인해주세요. ({})",
+                        message
+                    ));
+                }
+                Probe::Unavailable(message) => {
+                    web_sys::console::warn_1(&format!("스키마를 확인하지 못했습니다: {}", message).into());
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+        migrations.sort();
+        migrations.dedup();
+        Err(format!(
+            "서버 데이터베이스가 이 버전의 앱과 맞지 않습니다. {} supabase/migrations의 {}부터 적용해주세요.",
+            problems.join(" "),
+            migrations[0]
+        ))
+    }
+
+    async fn probe(table: &str, columns: &str) -> Probe {
+        let url = format!("{}/rest/v1/{}?select={}&limit=0", SUPABASE_CONFIG.url, table, columns);
+        let mut request = Request::get(&url).header("apikey", SUPABASE_CONFIG.anon_key);
+        // JWT 형식의 anon key는 Authorization으로도 보내야 PostgREST가 anon 역할로 받습니다.
+        if SUPABASE_CONFIG.anon_key.starts_with("eyJ") {
+            request = request.header("Authorization", &format!("Bearer {}", SUPABASE_CONFIG.anon_key));
+        }
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return Probe::Unavailable(e.to_string()),
+        };
+        if response.ok() {
+            return Probe::Ok;
+        }
+
+        let status = response.status();
+        let error: PostgrestError = response.json().await.unwrap_or_default();
+        match (status, error.code.as_str()) {
+            (_, "42P01" | "PGRST205") | (404, _) => Probe::MissingTable,
+            (_, "42703" | "PGRST204") => Probe::MissingColumn,
+            // anon 역할에 읽기 권한이 없으면 테이블은 있지만 열은 확인할 수 없습니다.
+            (_, "42501") => Probe::Unavailable(error.message),
+            (401 | 403, _) => Probe::Unauthorized(error.message),
+            _ => Probe::Unavailable(format!("{} {}", status, error.message)),
+        }
+    }
+}
@@ -0,0 +1,370 @@
+// 로컬 스냅샷 (IndexedDB)
+//
+// 삭제·복원처럼 되돌릴 수 없는 작업 직전과 일정 주기마다 전체 데이터를 브라우저에 저장해 두고,
+// 현재 데이터와 비교하거나 특정 시점으로 되돌릴 수 있게 합니다.
+
+use crate::models::{ExportedCertificate, Question, Snapshot, SnapshotMeta};
+use crate::services::local_db::{LocalDb, SNAPSHOT_META_STORE, SNAPSHOT_STORE};
+use crate::services::{AuthService, DataService, DataStore, QuestionService};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// 사용자별로 보관할 최대 스냅샷 수. 넘으면 오래된 것부터 지웁니다.
+const MAX_SNAPSHOTS: usize = 30;
+/// 자동 스냅샷 주기
+const SCHEDULE_INTERVAL_HOURS: i64 = 6;
+
+const SCHEDULED_REASON: &str = "자동 스냅샷";
+/// 게스트 모드 스냅샷의 사용자 ID
+const GUEST_USER_ID: &str = "guest";
+
+/// IndexedDB에 저장하는 스냅샷. 모델은 통계와 생성 시각을 직렬화하지 않으므로(`skip_serializing`)
+/// 문제별로 따로 담아 둡니다. 예전 스냅샷에는 `question_stats`가 없습니다.
+#[derive(Serialize, Deserialize)]
+struct StoredSnapshot {
+    id: String,
+    certificates: Vec<ExportedCertificate>,
+    #[serde(default)]
+    question_stats: HashMap<String, StoredStats>,
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct StoredStats {
+    created_at: DateTime<Utc>,
+    last_attempt: Option<DateTime<Utc>>,
+    attempt_count: u32,
+    correct_count: u32,
+}
+
+impl StoredSnapshot {
+    fn new(id: String, certificates: Vec<ExportedCertificate>) -> Self {
+        let question_stats = certificates
+            .iter()
+            .flat_map(|c| &c.questions)
+            .map(|q| {
+                let stats = StoredStats {
+                    created_at: q.created_at,
+                    last_attempt: q.last_attempt,
+                    attempt_count: q.attempt_count,
+                    correct_count: q.correct_count,
+                };
+                (q.id.clone(), stats)
+            })
+            .collect();
+        Self { id, certificates, question_stats }
+    }
+
+    fn into_snapshot(self) -> Snapshot {
+        let mut certificates = self.certificates;
+        for question in certificates.iter_mut().flat_map(|c| c.questions.iter_mut()) {
+            if let Some(stats) = self.question_stats.get(&question.id) {
+                question.created_at = stats.created_at;
+                question.last_attempt = stats.last_attempt;
+                question.attempt_count = stats.attempt_count;
+                question.correct_count = stats.correct_count;
+            }
+        }
+        Snapshot { id: self.id, certificates }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiffStatus {
+    Unchanged,
+    Changed,
+    /// 스냅샷에는 있지만 지금은 없음
+    Removed,
+    /// 스냅샷 이후에 생김
+    Added,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CertificateDiff {
+    pub certificate_id: String,
+    pub name: String,
+    pub status: DiffStatus,
+    /// 스냅샷 이후 새로 생긴 문제 수
+    pub questions_added: usize,
+    /// 스냅샷 이후 지워진 문제 수
+    pub questions_removed: usize,
+    pub questions_changed: usize,
+}
+
+pub struct SnapshotService;
+
+impl SnapshotService {
+    /// 현재 데이터를 스냅샷으로 저장합니다.
+    pub async fn take(reason: &str) -> Result<SnapshotMeta, String> {
+        let user_id = Self::user_id()?;
+        let certificates = DataService::collect_export().await?;
+        Self::save(&user_id, reason, certificates).await
+    }
+
+    /// 되돌릴 수 없는 작업 직전에 호출합니다. 스냅샷을 만들 수 없는 환경(시크릿 모드 등)에서도
+    /// 원래 작업은 진행되도록 실패는 기록만 합니다.
+    pub async fn take_before(reason: &str) {
+        if let Err(e) = Self::take(reason).await {
+            web_sys::console::warn_1(&format!("스냅샷을 만들지 못했습니다: {}", e).into());
+        }
+    }
+
+    /// 마지막 스냅샷이 주기보다 오래됐고 그 사이 내용이 바뀌었으면 자동 스냅샷을 만듭니다.
+    pub async fn take_scheduled() -> Result<Option<SnapshotMeta>, String> {
+        let user_id = Self::user_id()?;
+        let latest = Self::list().await?.into_iter().next();
+        if let Some(latest) = &latest {
+            if Utc::now() - latest.created_at < Duration::hours(SCHEDULE_INTERVAL_HOURS) {
+                return Ok(None);
+            }
+        }
+
+        let certificates = DataService::collect_export().await?;
+        if latest.is_some_and(|l| l.fingerprint == Self::fingerprint(&certificates)) {
+            return Ok(None);
+        }
+        Self::save(&user_id, SCHEDULED_REASON, certificates).await.map(Some)
+    }
+
+    /// 현재 사용자의 스냅샷 목록 (최신순)
+    pub async fn list() -> Result<Vec<SnapshotMeta>, String> {
+        let user_id = Self::user_id()?;
+        let db = LocalDb::open().await?;
+        let mut metas: Vec<SnapshotMeta> = db.get_all(SNAPSHOT_META_STORE).await?;
+        metas.retain(|m| m.user_id == user_id);
+        metas.sort_by_key(|m| std::cmp::Reverse(m.created_at));
+        Ok(metas)
+    }
+
+    pub async fn load(id: &str) -> Result<Snapshot, String> {
+        let db = LocalDb::open().await?;
+        db.get::<StoredSnapshot>(SNAPSHOT_STORE, id)
+            .await?
+            .map(StoredSnapshot::into_snapshot)
+            .ok_or_else(|| "스냅샷을 찾을 수 없습니다.".to_string())
+    }
+
+    pub async fn delete(id: &str) -> Result<(), String> {
+        let db = LocalDb::open().await?;
+        db.delete(SNAPSHOT_STORE, id).await?;
+        db.delete(SNAPSHOT_META_STORE, id).await
+    }
+
+    /// 현재 사용자의 스냅샷을 모두 지웁니다. (계정 삭제)
+    pub async fn delete_all() -> Result<(), String> {
+        for meta in Self::list().await? {
+            Self::delete(&meta.id).await?;
+        }
+        Ok(())
+    }
+
+    /// 스냅샷과 현재 데이터를 자격증 단위로 비교합니다.
+    pub fn diff(snapshot: &Snapshot, current: &[ExportedCertificate]) -> Vec<CertificateDiff> {
+        let mut diffs: Vec<CertificateDiff> = snapshot
+            .certificates
+            .iter()
+            .map(|old| match current.iter().find(|c| c.certificate.id == old.certificate.id) {
+                None => CertificateDiff {
+                    certificate_id: old.certificate.id.clone(),
+                    name: old.certificate.name.clone(),
+                    status: DiffStatus::Removed,
+                    questions_added: 0,
+                    questions_removed: old.questions.len(),
+                    questions_changed: 0,
+                },
+                Some(new) => {
+                    let find = |list: &[Question], id: &str| list.iter().position(|q| q.id == id);
+                    let questions_added = new.questions.iter().filter(|q| find(&old.questions, &q.id).is_none()).count();
+                    let questions_removed = old.questions.iter().filter(|q| find(&new.questions, &q.id).is_none()).count();
+                    let questions_changed = old
+                        .questions
+                        .iter()
+                        .filter_map(|q| find(&new.questions, &q.id).map(|i| (q, &new.questions[i])))
+                        .filter(|(a, b)| !Self::same_question(a, b))
+                        .count();
+                    let renamed = old.certificate.name != new.certificate.name
+                        || old.certificate.description != new.certificate.description;
+                    let status = if renamed || questions_added + questions_removed + questions_changed > 0 {
+                        DiffStatus::Changed
+                    } else {
+                        DiffStatus::Unchanged
+                    };
+                    CertificateDiff {
+                        certificate_id: old.certificate.id.clone(),
+                        name: new.certificate.name.clone(),
+                        status,
+                        questions_added,
+                        questions_removed,
+                        questions_changed,
+                    }
+                }
+            })
+            .collect();
+
+        for new in current {
+            if !snapshot.certificates.iter().any(|c| c.certificate.id == new.certificate.id) {
+                diffs.push(CertificateDiff {
+                    certificate_id: new.certificate.id.clone(),
+                    name: new.certificate.name.clone(),
+                    status: DiffStatus::Added,
+                    questions_added: new.questions.len(),
+                    questions_removed: 0,
+                    questions_changed: 0,
+                });
+            }
+        }
+        diffs
+    }
+
+    /// 모든 데이터를 스냅샷 시점으로 되돌립니다. 스냅샷 이후 만든 자격증은 휴지통으로 옮깁니다.
+    pub async fn restore_all(snapshot: &Snapshot) -> Result<String, String> {
+        Self::take_before("전체 복원 전").await;
+        let current = DataService::collect_export().await?;
+        let client = DataStore::current();
+
+        for exported in &current {
+            if !snapshot.certificates.iter().any(|c| c.certificate.id == exported.certificate.id) {
+                client.delete_certificate(&exported.certificate.id).await?;
+            }
+        }
+        for exported in &snapshot.certificates {
+            let existing = current.iter().find(|c| c.certificate.id == exported.certificate.id);
+            Self::restore_into(exported, existing).await?;
+        }
+        Ok(format!("{}개 자격증을 스냅샷 시점으로 복원했습니다.", snapshot.certificates.len()))
+    }
+
+    /// 자격증 하나만 스냅샷 시점으로 되돌립니다.
+    pub async fn restore_certificate(snapshot: &Snapshot, certificate_id: &str) -> Result<String, String> {
+        let exported = snapshot
+            .certificates
+            .iter()
+            .find(|c| c.certificate.id == certificate_id)
+            .ok_or_else(|| "스냅샷에 해당 자격증이 없습니다.".to_string())?;
+
+        Self::take_before(&format!("'{}' 복원 전", exported.certificate.name)).await;
+        let current = DataService::collect_export().await?;
+        let existing = current.iter().find(|c| c.certificate.id == certificate_id);
+        Self::restore_into(exported, existing).await?;
+        Ok(format!("'{}' 자격증을 스냅샷 시점으로 복원했습니다.", exported.certificate.name))
+    }
+
+    /// 같은 ID를 유지한 채 자격증과 문제를 스냅샷 내용으로 맞춥니다.
+    /// 휴지통에 있는 항목은 같은 ID로 새로 만들 수 없으므로 먼저 휴지통에서 꺼냅니다.
+    async fn restore_into(snapshot: &ExportedCertificate, current: Option<&ExportedCertificate>) -> Result<(), String> {
+        let client = DataStore::current();
+        let trashed;
+        let current = match current {
+            Some(current) => current,
+            None => {
+                client.restore_certificate(&snapshot.certificate.id).await?;
+                match client.get_certificate_by_id(&snapshot.certificate.id).await {
+                    Ok(certificate) => {
+                        let questions = QuestionService::get_by_certificate(&certificate.id).await?;
+                        trashed = ExportedCertificate { certificate, questions };
+                        &trashed
+                    }
+                    Err(_) => {
+                        client.create_certificate(&snapshot.certificate).await?;
+                        for question in &snapshot.questions {
+                            Self::create_or_untrash(&client, question).await?;
+                        }
+                        return Ok(());
+                    }
+                }
+            }
+        };
+
+        if current.certificate.name != snapshot.certificate.name
+            || current.certificate.description != snapshot.certificate.description
+        {
+            client.update_certificate(&snapshot.certificate).await?;
+        }
+        for question in &current.questions {
+            if !snapshot.questions.iter().any(|q| q.id == question.id) {
+                client.delete_question(&question.id).await?;
+            }
+        }
+        for question in &snapshot.questions {
+            match current.questions.iter().find(|q| q.id == question.id) {
+                Some(existing) if Self::same_question(existing, question) => {}
+                Some(_) => {
+                    QuestionService::update(question.clone()).await?;
+                }
+                None => Self::create_or_untrash(&client, question).await?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 휴지통에 있던 문제는 통계가 그대로 남아 있고, 영구 삭제된 문제는 새로 만든 뒤 스냅샷의 통계를 되살립니다.
+    async fn create_or_untrash(client: &DataStore, question: &Question) -> Result<(), String> {
+        client.restore_question(&question.id).await?;
+        match client.get_question_by_id(&question.id).await {
+            Ok(_) => QuestionService::update(question.clone()).await.map(|_| ()),
+            Err(_) => {
+                QuestionService::create(question.clone()).await?;
+                if question.attempt_count > 0 || question.last_attempt.is_some() {
+                    client.update_question_stats(question).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    async fn save(user_id: &str, reason: &str, certificates: Vec<ExportedCertificate>) -> Result<SnapshotMeta, String> {
+        let meta = SnapshotMeta {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            created_at: Utc::now(),
+            reason: reason.to_string(),
+            certificate_count: certificates.len(),
+            question_count: certificates.iter().map(|c| c.questions.len()).sum(),
+            fingerprint: Self::fingerprint(&certificates),
+        };
+        let snapshot = StoredSnapshot::new(meta.id.clone(), certificates);
+
+        let db = LocalDb::open().await?;
+        db.put(SNAPSHOT_STORE, &snapshot.id, &snapshot).await?;
+        db.put(SNAPSHOT_META_STORE, &meta.id, &meta).await?;
+
+        // 오래된 스냅샷 정리
+        for old in Self::list().await?.iter().skip(MAX_SNAPSHOTS) {
+            Self::delete(&old.id).await?;
+        }
+        Ok(meta)
+    }
+
+    /// 통계처럼 스냅샷에 저장하지 않는 필드는 빼고 비교합니다.
+    pub(crate) fn same_question(a: &Question, b: &Question) -> bool {
+        a.content == b.content
+            && a.explanation == b.explanation
+            && a.options.len() == b.options.len()
+            && a.options.iter().zip(&b.options).all(|(x, y)| {
+                x.content == y.content && x.is_correct == y.is_correct && x.explanation == y.explanation
+            })
+    }
+
+    /// 풀이 통계도 스냅샷에 담기므로 통계만 바뀌어도 다른 값이 됩니다.
+    fn fingerprint(certificates: &[ExportedCertificate]) -> String {
+        let stats: Vec<(u32, u32, Option<DateTime<Utc>>)> = certificates
+            .iter()
+            .flat_map(|c| &c.questions)
+            .map(|q| (q.attempt_count, q.correct_count, q.last_attempt))
+            .collect();
+        let json = serde_json::to_string(&(certificates, stats)).unwrap_or_default();
+        Sha1::digest(json.as_bytes()).iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// 스냅샷 주인. 게스트 모드의 스냅샷은 `GUEST_USER_ID`로 따로 모읍니다.
+    fn user_id() -> Result<String, String> {
+        if AuthService::is_guest() {
+            return Ok(GUEST_USER_ID.to_string());
+        }
+        AuthService::get_current_user()
+            .map(|u| u.id)
+            .ok_or_else(|| "로그인이 필요합니다".to_string())
+    }
+}
@@ -0,0 +1,439 @@
+// 브라우저에서 SQLite 데이터베이스 파일을 만들기 위한 최소한의 작성기
+//
+// SQLite 파일 포맷(https://www.sqlite.org/fileformat2.html)에 맞춰 테이블 B-tree만
+// 직접 기록합니다. 인덱스, 빈 페이지 목록, WAL은 지원하지 않으며 한 번 만들고
+// 끝나는 내보내기 파일(예: Anki 컬렉션) 용도로만 사용합니다.
+
+const PAGE_SIZE: usize = 4096;
+const HEADER_SIZE: usize = 100;
+const LEAF_TABLE_PAGE: u8 = 0x0D;
+const INTERIOR_TABLE_PAGE: u8 = 0x05;
+const SQLITE_VERSION_NUMBER: u32 = 3_045_000;
+const INTERIOR_CHILDREN_PER_PAGE: usize = 250;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Integer(i64),
+    Text(String),
+}
+
+impl From<i64> for SqlValue {
+    fn from(value: i64) -> Self {
+        SqlValue::Integer(value)
+    }
+}
+
+impl From<&str> for SqlValue {
+    fn from(value: &str) -> Self {
+        SqlValue::Text(value.to_string())
+    }
+}
+
+impl From<String> for SqlValue {
+    fn from(value: String) -> Self {
+        SqlValue::Text(value)
+    }
+}
+
+struct Table {
+    name: String,
+    sql: String,
+    rows: Vec<(i64, Vec<SqlValue>)>,
+}
+
+#[derive(Default)]
+pub struct SqliteWriter {
+    tables: Vec<Table>,
+    pages: Vec<Vec<u8>>,
+}
+
+impl SqliteWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 테이블을 추가합니다. `INTEGER PRIMARY KEY` 열은 rowid의 별칭이므로
+    /// `insert`할 때 해당 열 값은 `SqlValue::Null`로 넘겨야 합니다.
+    pub fn create_table(&mut self, name: &str, sql: &str) {
+        self.tables.push(Table {
+            name: name.to_string(),
+            sql: sql.to_string(),
+            rows: Vec::new(),
+        });
+    }
+
+    pub fn insert(&mut self, table: &str, rowid: i64, values: Vec<SqlValue>) -> Result<(), String> {
+        let table = self
+            .tables
+            .iter_mut()
+            .find(|t| t.name == table)
+            .ok_or_else(|| format!("테이블이 없습니다: {}", table))?;
+        table.rows.push((rowid, values));
+        Ok(())
+    }
+
+    /// 데이터베이스 파일 전체를 바이트로 만듭니다.
+    pub fn finish(mut self) -> Result<Vec<u8>, String> {
+        // 1번 페이지는 sqlite_master 전용으로 먼저 확보합니다.
+        self.allocate_page();
+
+        let tables = std::mem::take(&mut self.tables);
+        let mut schema_rows = Vec::new();
+        for (index, mut table) in tables.into_iter().enumerate() {
+            table.rows.sort_by_key(|(rowid, _)| *rowid);
+            if table.rows.windows(2).any(|w| w[0].0 == w[1].0) {
+                return Err(format!("{} 테이블에 중복된 rowid가 있습니다.", table.name));
+            }
+            let root = self.write_table(&table.rows)?;
+            schema_rows.push((
+                index as i64 + 1,
+                vec![
+                    SqlValue::from("table"),
+                    SqlValue::from(table.name.as_str()),
+                    SqlValue::from(table.name.as_str()),
+                    SqlValue::Integer(root as i64),
+                    SqlValue::from(table.sql),
+                ],
+            ));
+        }
+
+        let cells = schema_rows
+            .iter()
+            .map(|(rowid, values)| self.leaf_cell(*rowid, values))
+            .collect::<Vec<_>>();
+        let capacity = PAGE_SIZE - HEADER_SIZE - 8;
+        if cells.iter().map(|c| c.len() + 2).sum::<usize>() > capacity {
+            return Err("스키마가 너무 커서 첫 페이지에 들어가지 않습니다.".to_string());
+        }
+        Self::write_btree_page(&mut self.pages[0], HEADER_SIZE, LEAF_TABLE_PAGE, &cells, None);
+        self.write_file_header();
+
+        Ok(self.pages.concat())
+    }
+
+    fn allocate_page(&mut self) -> u32 {
+        self.pages.push(vec![0; PAGE_SIZE]);
+        self.pages.len() as u32
+    }
+
+    /// 행들을 테이블 B-tree로 기록하고 루트 페이지 번호를 반환합니다.
+    fn write_table(&mut self, rows: &[(i64, Vec<SqlValue>)]) -> Result<u32, String> {
+        let cells: Vec<(i64, Vec<u8>)> = rows
+            .iter()
+            .map(|(rowid, values)| (*rowid, self.leaf_cell(*rowid, values)))
+            .collect();
+
+        // 1단계: 리프 페이지. (페이지 번호, 해당 페이지의 최대 rowid)
+        let mut level: Vec<(u32, i64)> = Vec::new();
+        for chunk in Self::pack(&cells, PAGE_SIZE - 8) {
+            let page = self.allocate_page();
+            let page_cells: Vec<Vec<u8>> = chunk.iter().map(|(_, c)| c.clone()).collect();
+            Self::write_btree_page(&mut self.pages[page as usize - 1], 0, LEAF_TABLE_PAGE, &page_cells, None);
+            level.push((page, chunk.last().map(|(rowid, _)| *rowid).unwrap_or(0)));
+        }
+        if level.is_empty() {
+            let page = self.allocate_page();
+            Self::write_btree_page(&mut self.pages[page as usize - 1], 0, LEAF_TABLE_PAGE, &[], None);
+            return Ok(page);
+        }
+
+        // 2단계 이후: 루트가 하나 남을 때까지 내부 페이지를 쌓습니다.
+        // 내부 셀은 최대 13바이트라 개수 기준으로 고르게 나눠도 페이지를 넘지 않습니다.
+        while level.len() > 1 {
+            let page_count = level.len().div_ceil(INTERIOR_CHILDREN_PER_PAGE);
+            let per_page = level.len().div_ceil(page_count);
+
+            let mut next_level = Vec::new();
+            for children in level.chunks(per_page) {
+                // 마지막 자식은 셀 대신 오른쪽 끝 포인터로 기록합니다.
+                let (right_child, max_rowid) = children[children.len() - 1];
+                let page_cells: Vec<Vec<u8>> = children[..children.len() - 1]
+                    .iter()
+                    .map(|(child, child_max)| {
+                        let mut cell = child.to_be_bytes().to_vec();
+                        put_varint(&mut cell, *child_max as u64);
+                        cell
+                    })
+                    .collect();
+                let page = self.allocate_page();
+                Self::write_btree_page(
+                    &mut self.pages[page as usize - 1],
+                    0,
+                    INTERIOR_TABLE_PAGE,
+                    &page_cells,
+                    Some(right_child),
+                );
+                next_level.push((page, max_rowid));
+            }
+            level = next_level;
+        }
+        Ok(level[0].0)
+    }
+
+    /// 리프 셀(+ 포인터 2바이트)을 페이지 용량에 맞게 나눕니다.
+    fn pack(cells: &[(i64, Vec<u8>)], capacity: usize) -> Vec<&[(i64, Vec<u8>)]> {
+        let mut chunks = Vec::new();
+        let mut start = 0;
+        let mut used = 0;
+        for (i, (_, cell)) in cells.iter().enumerate() {
+            if used + cell.len() + 2 > capacity && i > start {
+                chunks.push(&cells[start..i]);
+                start = i;
+                used = 0;
+            }
+            used += cell.len() + 2;
+        }
+        if start < cells.len() {
+            chunks.push(&cells[start..]);
+        }
+        chunks
+    }
+
+    /// 테이블 리프 셀을 만듭니다. 페이로드가 크면 넘치는 부분을 오버플로 페이지에 씁니다.
+    fn leaf_cell(&mut self, rowid: i64, values: &[SqlValue]) -> Vec<u8> {
+        let payload = encode_record(values);
+        let usable = PAGE_SIZE;
+        let max_local = usable - 35;
+        let min_local = ((usable - 12) * 32 / 255) - 23;
+
+        let mut cell = Vec::new();
+        put_varint(&mut cell, payload.len() as u64);
+        put_varint(&mut cell, rowid as u64);
+
+        if payload.len() <= max_local {
+            cell.extend_from_slice(&payload);
+            return cell;
+        }
+
+        let k = min_local + (payload.len() - min_local) % (usable - 4);
+        let local = if k <= max_local { k } else { min_local };
+        cell.extend_from_slice(&payload[..local]);
+
+        let chunks: Vec<&[u8]> = payload[local..].chunks(usable - 4).collect();
+        let first_page = self.pages.len() as u32 + 1;
+        for (i, chunk) in chunks.iter().enumerate() {
+            let page = self.allocate_page();
+            let next = if i + 1 < chunks.len() { page + 1 } else { 0 };
+            let buf = &mut self.pages[page as usize - 1];
+            buf[..4].copy_from_slice(&next.to_be_bytes());
+            buf[4..4 + chunk.len()].copy_from_slice(chunk);
+        }
+        cell.extend_from_slice(&first_page.to_be_bytes());
+        cell
+    }
+
+    fn write_btree_page(page: &mut [u8], offset: usize, page_type: u8, cells: &[Vec<u8>], right_child: Option<u32>) {
+        let header_len = if right_child.is_some() { 12 } else { 8 };
+        let mut content_start = PAGE_SIZE;
+        for (i, cell) in cells.iter().enumerate() {
+            content_start -= cell.len();
+            page[content_start..content_start + cell.len()].copy_from_slice(cell);
+            let pointer = offset + header_len + i * 2;
+            page[pointer..pointer + 2].copy_from_slice(&(content_start as u16).to_be_bytes());
+        }
+
+        page[offset] = page_type;
+        page[offset + 1..offset + 3].copy_from_slice(&0u16.to_be_bytes());
+        page[offset + 3..offset + 5].copy_from_slice(&(cells.len() as u16).to_be_bytes());
+        // 셀 영역 시작이 65536이면 0으로 기록하지만, 4096 페이지에서는 해당되지 않습니다.
+        page[offset + 5..offset + 7].copy_from_slice(&(content_start as u16).to_be_bytes());
+        page[offset + 7] = 0;
+        if let Some(right_child) = right_child {
+            page[offset + 8..offset + 12].copy_from_slice(&right_child.to_be_bytes());
+        }
+    }
+
+    fn write_file_header(&mut self) {
+        let page_count = self.pages.len() as u32;
+        let header = &mut self.pages[0][..HEADER_SIZE];
+        header[..16].copy_from_slice(b"SQLite format 3\0");
+        header[16..18].copy_from_slice(&(PAGE_SIZE as u16).to_be_bytes());
+        header[18] = 1; // 쓰기 버전 (legacy)
+        header[19] = 1; // 읽기 버전 (legacy)
+        header[20] = 0; // 페이지 끝 예약 공간
+        header[21] = 64; // 최대 페이로드 비율
+        header[22] = 32; // 최소 페이로드 비율
+        header[23] = 32; // 리프 페이로드 비율
+        header[24..28].copy_from_slice(&1u32.to_be_bytes()); // 변경 카운터
+        header[28..32].copy_from_slice(&page_count.to_be_bytes());
+        header[40..44].copy_from_slice(&1u32.to_be_bytes()); // 스키마 쿠키
+        header[44..48].copy_from_slice(&4u32.to_be_bytes()); // 스키마 포맷
+        header[56..60].copy_from_slice(&1u32.to_be_bytes()); // 텍스트 인코딩: UTF-8
+        header[92..96].copy_from_slice(&1u32.to_be_bytes()); // version-valid-for
+        header[96..100].copy_from_slice(&SQLITE_VERSION_NUMBER.to_be_bytes());
+    }
+}
+
+fn encode_record(values: &[SqlValue]) -> Vec<u8> {
+    let mut types = Vec::new();
+    let mut body = Vec::new();
+
+    for value in values {
+        match value {
+            SqlValue::Null => put_varint(&mut types, 0),
+            SqlValue::Integer(0) => put_varint(&mut types, 8),
+            SqlValue::Integer(1) => put_varint(&mut types, 9),
+            SqlValue::Integer(n) => {
+                let (serial_type, len) = match *n {
+                    -0x80..=0x7f => (1, 1),
+                    -0x8000..=0x7fff => (2, 2),
+                    -0x80_0000..=0x7f_ffff => (3, 3),
+                    -0x8000_0000..=0x7fff_ffff => (4, 4),
+                    -0x8000_0000_0000..=0x7fff_ffff_ffff => (5, 6),
+                    _ => (6, 8),
+                };
+                put_varint(&mut types, serial_type);
+                body.extend_from_slice(&n.to_be_bytes()[8 - len..]);
+            }
+            SqlValue::Text(text) => {
+                put_varint(&mut types, text.len() as u64 * 2 + 13);
+                body.extend_from_slice(text.as_bytes());
+            }
+        }
+    }
+
+    // 헤더 크기 varint는 자기 자신의 길이도 포함합니다.
+    let mut header_len = types.len() + 1;
+    while varint_len(header_len as u64) + types.len() > header_len {
+        header_len += 1;
+    }
+
+    let mut record = Vec::with_capacity(header_len + body.len());
+    put_varint(&mut record, header_len as u64);
+    record.extend_from_slice(&types);
+    record.extend_from_slice(&body);
+    record
+}
+
+fn varint_len(value: u64) -> usize {
+    let mut buf = Vec::new();
+    put_varint(&mut buf, value);
+    buf.len()
+}
+
+/// SQLite 가변 길이 정수(빅엔디언, 최대 9바이트)를 기록합니다.
+fn put_varint(out: &mut Vec<u8>, value: u64) {
+    if value > 0x00ff_ffff_ffff_ffff {
+        let mut buf = [0u8; 9];
+        buf[8] = value as u8;
+        let mut rest = value >> 8;
+        for byte in buf[..8].iter_mut().rev() {
+            *byte = (rest & 0x7f) as u8 | 0x80;
+            rest >>= 7;
+        }
+        out.extend_from_slice(&buf);
+        return;
+    }
+
+    let mut groups = Vec::new();
+    let mut rest = value;
+    loop {
+        groups.push((rest & 0x7f) as u8);
+        rest >>= 7;
+        if rest == 0 {
+            break;
+        }
+    }
+    let last = groups.len() - 1;
+    for (i, group) in groups.iter().rev().enumerate() {
+        out.push(if i < last { group | 0x80 } else { *group });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+
+    /// 만든 파일을 임시 경로에 써서 SQLite로 엽니다.
+    fn open(bytes: &[u8], name: &str) -> Connection {
+        let path = std::env::temp_dir().join(format!("quiz-note-sqlite-writer-{}-{}.db", name, std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        let conn = Connection::open(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        conn
+    }
+
+    fn integrity(conn: &Connection) -> String {
+        conn.query_row("PRAGMA integrity_check", [], |row| row.get(0)).unwrap()
+    }
+
+    #[test]
+    fn small_table_reads_back() {
+        let mut writer = SqliteWriter::new();
+        writer.create_table("notes", "CREATE TABLE notes (id INTEGER PRIMARY KEY, title TEXT, score INTEGER, memo TEXT)");
+        writer.insert("notes", 1, vec![SqlValue::Null, "첫 문제".into(), 0.into(), SqlValue::Null]).unwrap();
+        writer.insert("notes", 2, vec![SqlValue::Null, "두 번째".into(), (-70_000).into(), "메모".into()]).unwrap();
+
+        let conn = open(&writer.finish().unwrap(), "small");
+        assert_eq!(integrity(&conn), "ok");
+        let rows: Vec<(i64, String, i64, Option<String>)> = conn
+            .prepare("SELECT id, title, score, memo FROM notes ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(
+            rows,
+            vec![
+                (1, "첫 문제".to_string(), 0, None),
+                (2, "두 번째".to_string(), -70_000, Some("메모".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    fn long_value_spills_into_overflow_pages() {
+        let long: String = "가나다라마바사아자차카타파하".repeat(1_000);
+        let mut writer = SqliteWriter::new();
+        writer.create_table("notes", "CREATE TABLE notes (id INTEGER PRIMARY KEY, body TEXT)");
+        writer.insert("notes", 1, vec![SqlValue::Null, "short".into()]).unwrap();
+        writer.insert("notes", 2, vec![SqlValue::Null, long.clone().into()]).unwrap();
+        writer.insert("notes", 3, vec![SqlValue::Null, "after".into()]).unwrap();
+
+        let conn = open(&writer.finish().unwrap(), "overflow");
+        assert_eq!(integrity(&conn), "ok");
+        let body: String = conn.query_row("SELECT body FROM notes WHERE id = 2", [], |row| row.get(0)).unwrap();
+        assert_eq!(body, long);
+        let after: String = conn.query_row("SELECT body FROM notes WHERE id = 3", [], |row| row.get(0)).unwrap();
+        assert_eq!(after, "after");
+    }
+
+    #[test]
+    fn many_rows_build_a_multi_level_btree() {
+        // 리프 페이지가 INTERIOR_CHILDREN_PER_PAGE개를 넘으면 내부 페이지가 두 단계 이상 생깁니다.
+        let rows = 30_000;
+        let mut writer = SqliteWriter::new();
+        writer.create_table("cards", "CREATE TABLE cards (id INTEGER PRIMARY KEY, front TEXT, ord INTEGER)");
+        writer.create_table("decks", "CREATE TABLE decks (id INTEGER PRIMARY KEY, name TEXT)");
+        for rowid in 1..=rows {
+            let front = format!("문제 {} {}", rowid, "x".repeat(60));
+            writer.insert("cards", rowid, vec![SqlValue::Null, front.into(), (rowid * 3).into()]).unwrap();
+        }
+        writer.insert("decks", 1, vec![SqlValue::Null, "기본".into()]).unwrap();
+
+        let bytes = writer.finish().unwrap();
+        assert!(bytes.len() / PAGE_SIZE > INTERIOR_CHILDREN_PER_PAGE * 2);
+        let conn = open(&bytes, "multilevel");
+        assert_eq!(integrity(&conn), "ok");
+        let (count, sum): (i64, i64) =
+            conn.query_row("SELECT count(*), sum(ord) FROM cards", [], |row| Ok((row.get(0)?, row.get(1)?))).unwrap();
+        assert_eq!(count, rows);
+        assert_eq!(sum, 3 * rows * (rows + 1) / 2);
+        let front: String = conn.query_row("SELECT front FROM cards WHERE id = 12345", [], |row| row.get(0)).unwrap();
+        assert!(front.starts_with("문제 12345 "));
+        let name: String = conn.query_row("SELECT name FROM decks", [], |row| row.get(0)).unwrap();
+        assert_eq!(name, "기본");
+    }
+
+    #[test]
+    fn duplicate_rowids_are_rejected() {
+        let mut writer = SqliteWriter::new();
+        writer.create_table("notes", "CREATE TABLE notes (id INTEGER PRIMARY KEY)");
+        writer.insert("notes", 1, vec![SqlValue::Null]).unwrap();
+        writer.insert("notes", 1, vec![SqlValue::Null]).unwrap();
+        assert!(writer.finish().is_err());
+    }
+}
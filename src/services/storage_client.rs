@@ -0,0 +1,136 @@
+// Supabase Storage 클라이언트
+//
+// 문제에 첨부하는 이미지를 저장합니다. 요청 경로는 Supabase Storage API를 그대로 따르므로
+// 같은 경로를 흉내 내는 로컬 스텁 서버로도 주소만 바꿔 시험할 수 있습니다.
+
+use crate::config::SUPABASE_CONFIG;
+use crate::services::SupabaseClient;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use serde_json::json;
+
+/// 문제 이미지를 담는 공개 버킷. 읽기는 누구나, 쓰기·삭제는 본인 폴더(`<user_id>/`)만 가능합니다.
+pub const IMAGE_BUCKET: &str = "question-images";
+
+/// 목록 조회 한 번에 받아오는 최대 개수
+const LIST_PAGE_SIZE: usize = 1000;
+
+/// 버킷 안의 파일 정보. 폴더는 `id`가 없습니다.
+#[derive(Clone, Debug, Deserialize)]
+pub struct StorageObject {
+    pub name: String,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone)]
+pub struct StorageClient {
+    base_url: String,
+    bucket: String,
+}
+
+impl StorageClient {
+    pub fn new() -> Self {
+        Self::with_base_url(SUPABASE_CONFIG.url, IMAGE_BUCKET)
+    }
+
+    /// 다른 서버(로컬 스텁 등)나 버킷을 쓸 때 사용합니다.
+    pub fn with_base_url(base_url: &str, bucket: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            bucket: bucket.to_string(),
+        }
+    }
+
+    /// 로그인 없이 읽을 수 있는 공개 주소
+    pub fn public_url(&self, path: &str) -> String {
+        format!("{}/{}", self.public_prefix(), path)
+    }
+
+    /// 이 버킷의 공개 주소이면 버킷 안의 경로를 돌려줍니다.
+    pub fn path_from_url<'a>(&self, url: &'a str) -> Option<&'a str> {
+        url.strip_prefix(&self.public_prefix())?
+            .strip_prefix('/')
+            .filter(|path| !path.is_empty())
+    }
+
+    fn public_prefix(&self) -> String {
+        format!("{}/storage/v1/object/public/{}", self.base_url, self.bucket)
+    }
+
+    pub async fn upload(&self, path: &str, bytes: &[u8], content_type: &str) -> Result<(), String> {
+        let url = format!("{}/storage/v1/object/{}/{}", self.base_url, self.bucket, path);
+        let body = js_sys::Uint8Array::from(bytes);
+        let response = SupabaseClient::new()
+            .request_builder("POST", &url).await?
+            .header("Content-Type", content_type)
+            .header("Cache-Control", "max-age=31536000")
+            .body(body).map_err(|e| e.to_string())?
+            .send().await.map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            Ok(())
+        } else {
+            Err(format!("이미지 업로드 실패: {}", response.text().await.unwrap_or_default()))
+        }
+    }
+
+    /// `prefix` 폴더 바로 아래의 파일을 모두 가져옵니다.
+    pub async fn list(&self, prefix: &str) -> Result<Vec<StorageObject>, String> {
+        let url = format!("{}/storage/v1/object/list/{}", self.base_url, self.bucket);
+        let mut objects = Vec::new();
+        loop {
+            let body = json!({ "prefix": prefix, "limit": LIST_PAGE_SIZE, "offset": objects.len() });
+            let response = SupabaseClient::new()
+                .request_builder("POST", &url).await?
+                .header("Content-Type", "application/json")
+                .json(&body).map_err(|e| e.to_string())?
+                .send().await.map_err(|e| e.to_string())?;
+            // Storage가 없는 서버(자체 호스팅 서버)에는 올린 파일도 없습니다.
+            if response.status() == 404 {
+                break;
+            }
+            if !response.ok() {
+                return Err(format!("이미지 목록 조회 실패: {}", response.text().await.unwrap_or_default()));
+            }
+            let page: Vec<StorageObject> = response.json().await.map_err(|e| e.to_string())?;
+            let is_last = page.len() < LIST_PAGE_SIZE;
+            objects.extend(page);
+            if is_last {
+                break;
+            }
+        }
+        Ok(objects.into_iter().filter(|o| o.id.is_some()).collect())
+    }
+
+    pub async fn remove(&self, paths: &[String]) -> Result<(), String> {
+        if paths.is_empty() {
+            return Ok(());
+        }
+        let url = format!("{}/storage/v1/object/{}", self.base_url, self.bucket);
+        let response = SupabaseClient::new()
+            .request_builder("DELETE", &url).await?
+            .header("Content-Type", "application/json")
+            .json(&json!({ "prefixes": paths })).map_err(|e| e.to_string())?
+            .send().await.map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            Ok(())
+        } else {
+            Err(format!("이미지 삭제 실패: {}", response.text().await.unwrap_or_default()))
+        }
+    }
+
+    /// 공개 주소의 파일을 내려받습니다. 내보내기 파일에 이미지를 함께 넣을 때 씁니다.
+    pub async fn download(&self, url: &str) -> Result<(Vec<u8>, String), String> {
+        let response = gloo_net::http::Request::get(url).send().await.map_err(|e| e.to_string())?;
+        if !response.ok() {
+            return Err(format!("이미지 다운로드 실패 ({}): {}", response.status(), url));
+        }
+        let content_type = response.headers().get("Content-Type").unwrap_or_default();
+        let bytes = response.binary().await.map_err(|e| e.to_string())?;
+        Ok((bytes, content_type))
+    }
+}
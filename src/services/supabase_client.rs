@@ -2,6 +2,7 @@ use crate::config::SUPABASE_CONFIG;
 use crate::models::{Certificate, Question, QuestionOption};
 use crate::services::AuthService;
 use gloo_net::http::{RequestBuilder, Request};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde_json::json;
 
 #[derive(Clone)]
@@ -22,7 +23,7 @@ impl SupabaseClient {
             .ok_or_else(|| "로그인이 필요합니다".to_string())
     }
 
-    async fn request_builder(&self, method: &str, url: &str) -> Result<RequestBuilder, String> {
+    pub(crate) async fn request_builder(&self, method: &str, url: &str) -> Result<RequestBuilder, String> {
         let auth_header = self.get_auth_header_string().await?;
 
         let builder = match method {
@@ -41,7 +42,7 @@ impl SupabaseClient {
     // --- Certificate CRUD ---
     // (get_all_certificates, get_certificate_by_id, create_certificate, delete_certificate 함수는 변경 없음)
     pub async fn get_all_certificates(&self) -> Result<Vec<Certificate>, String> {
-        let url = format!("{}/rest/v1/certificates?select=*", SUPABASE_CONFIG.url);
+        let url = format!("{}/rest/v1/certificates?deleted_at=is.null&select=*", SUPABASE_CONFIG.url);
         let response = self.request_builder("GET", &url).await?.send().await.map_err(|e| e.to_string())?;
 
         if response.ok() {
@@ -52,7 +53,7 @@ impl SupabaseClient {
     }
     
     pub async fn get_certificate_by_id(&self, id: &str) -> Result<Certificate, String> {
-        let url = format!("{}/rest/v1/certificates?id=eq.{}&select=*&limit=1", SUPABASE_CONFIG.url, id);
+        let url = format!("{}/rest/v1/certificates?id=eq.{}&deleted_at=is.null&select=*&limit=1", SUPABASE_CONFIG.url, id);
         let response = self.request_builder("GET", &url).await?.send().await.map_err(|e| e.to_string())?;
 
         if response.ok() {
@@ -78,14 +79,55 @@ impl SupabaseClient {
         }
     }
 
+    pub async fn update_certificate(&self, cert: &Certificate) -> Result<(), String> {
+        let url = format!("{}/rest/v1/certificates?id=eq.{}", SUPABASE_CONFIG.url, cert.id);
+        let body = json!({
+            "name": cert.name,
+            "description": cert.description
+        });
+        let response = self.request_builder("PATCH", &url).await?
+            .header("Content-Type", "application/json")
+            .json(&body).map_err(|e| e.to_string())?
+            .send().await.map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            Ok(())
+        } else {
+            Err(format!("자격증 수정 실패: {}", response.text().await.unwrap_or_default()))
+        }
+    }
+
+    /// 자격증을 휴지통으로 옮깁니다. 문제는 그대로 두고 자격증과 함께 숨겨집니다.
     pub async fn delete_certificate(&self, id: &str) -> Result<(), String> {
+        self.set_deleted_at("certificates", id, Some(Utc::now())).await
+            .map_err(|e| format!("자격증 삭제 실패: {}", e))
+    }
+
+    pub async fn restore_certificate(&self, id: &str) -> Result<(), String> {
+        self.set_deleted_at("certificates", id, None).await
+            .map_err(|e| format!("자격증 복원 실패: {}", e))
+    }
+
+    /// 자격증과 문제를 DB에서 완전히 지웁니다.
+    pub async fn purge_certificate(&self, id: &str) -> Result<(), String> {
         let url = format!("{}/rest/v1/certificates?id=eq.{}", SUPABASE_CONFIG.url, id);
         let response = self.request_builder("DELETE", &url).await?.send().await.map_err(|e| e.to_string())?;
 
         if response.ok() {
             Ok(())
         } else {
-            Err(format!("자격증 삭제 실패: {}", response.text().await.unwrap_or_default()))
+            Err(format!("자격증 영구 삭제 실패: {}", response.text().await.unwrap_or_default()))
+        }
+    }
+
+    pub async fn get_deleted_certificates(&self) -> Result<Vec<Certificate>, String> {
+        let url = format!("{}/rest/v1/certificates?deleted_at=not.is.null&select=*", SUPABASE_CONFIG.url);
+        let response = self.request_builder("GET", &url).await?.send().await.map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            response.json().await.map_err(|e| e.to_string())
+        } else {
+            Err(format!("휴지통 불러오기 실패: {}", response.text().await.unwrap_or_default()))
         }
     }
 
@@ -94,7 +136,7 @@ impl SupabaseClient {
     // (get_questions_by_certificate, get_question_by_id 함수는 변경 없음)
     pub async fn get_questions_by_certificate(&self, cert_id: &str) -> Result<Vec<Question>, String> {
         let url = format!(
-            "{}/rest/v1/questions?certificate_id=eq.{}&select=*,question_options(*)",
+            "{}/rest/v1/questions?certificate_id=eq.{}&deleted_at=is.null&select=*,question_options(*)",
             SUPABASE_CONFIG.url, cert_id
         );
         let response = self.request_builder("GET", &url).await?.send().await.map_err(|e| e.to_string())?;
@@ -112,7 +154,7 @@ impl SupabaseClient {
 
     pub async fn get_question_by_id(&self, id: &str) -> Result<Question, String> {
         let url = format!(
-            "{}/rest/v1/questions?id=eq.{}&select=*,question_options(*)",
+            "{}/rest/v1/questions?id=eq.{}&deleted_at=is.null&select=*,question_options(*)",
             SUPABASE_CONFIG.url, id
         );
         let response = self.request_builder("GET", &url).await?.send().await.map_err(|e| e.to_string())?;
@@ -236,11 +278,73 @@ impl SupabaseClient {
         else { Err(format!("문제 통계 업데이트 실패: {}", response.text().await.unwrap_or_default())) }
     }
 
+    /// 문제를 휴지통으로 옮깁니다.
     pub async fn delete_question(&self, id: &str) -> Result<(), String> {
+        self.set_deleted_at("questions", id, Some(Utc::now())).await
+            .map_err(|e| format!("문제 삭제 실패: {}", e))
+    }
+
+    pub async fn restore_question(&self, id: &str) -> Result<(), String> {
+        self.set_deleted_at("questions", id, None).await
+            .map_err(|e| format!("문제 복원 실패: {}", e))
+    }
+
+    pub async fn purge_question(&self, id: &str) -> Result<(), String> {
         let url = format!("{}/rest/v1/questions?id=eq.{}", SUPABASE_CONFIG.url, id);
         let response = self.request_builder("DELETE", &url).await?.send().await.map_err(|e| e.to_string())?;
 
         if response.ok() { Ok(()) } 
-        else { Err(format!("문제 삭제 실패: {}", response.text().await.unwrap_or_default())) }
+        else { Err(format!("문제 영구 삭제 실패: {}", response.text().await.unwrap_or_default())) }
+    }
+
+    pub async fn get_deleted_questions(&self) -> Result<Vec<Question>, String> {
+        let url = format!(
+            "{}/rest/v1/questions?deleted_at=not.is.null&select=*,question_options(*)",
+            SUPABASE_CONFIG.url
+        );
+        let response = self.request_builder("GET", &url).await?.send().await.map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            response.json().await.map_err(|e| e.to_string())
+        } else {
+            Err(format!("휴지통 불러오기 실패: {}", response.text().await.unwrap_or_default()))
+        }
+    }
+
+    /// 휴지통에 있는 것까지 포함한 내 모든 문제
+    pub async fn get_all_questions(&self) -> Result<Vec<Question>, String> {
+        let url = format!("{}/rest/v1/questions?select=*,question_options(*)", SUPABASE_CONFIG.url);
+        let response = self.request_builder("GET", &url).await?.send().await.map_err(|e| e.to_string())?;
+
+        if response.ok() {
+            response.json().await.map_err(|e| e.to_string())
+        } else {
+            Err(format!("문제 불러오기 실패: {}", response.text().await.unwrap_or_default()))
+        }
+    }
+
+    /// `before`보다 먼저 휴지통에 들어간 자격증과 문제를 영구 삭제합니다.
+    pub async fn purge_deleted_before(&self, before: DateTime<Utc>) -> Result<(), String> {
+        let cutoff = before.to_rfc3339_opts(SecondsFormat::Secs, true);
+        for table in ["certificates", "questions"] {
+            let url = format!("{}/rest/v1/{}?deleted_at=lt.{}", SUPABASE_CONFIG.url, table, cutoff);
+            let response = self.request_builder("DELETE", &url).await?.send().await.map_err(|e| e.to_string())?;
+            if !response.ok() {
+                return Err(format!("휴지통 정리 실패: {}", response.text().await.unwrap_or_default()));
+            }
+        }
+        Ok(())
+    }
+
+    async fn set_deleted_at(&self, table: &str, id: &str, deleted_at: Option<DateTime<Utc>>) -> Result<(), String> {
+        let url = format!("{}/rest/v1/{}?id=eq.{}", SUPABASE_CONFIG.url, table, id);
+        let body = json!({ "deleted_at": deleted_at });
+        let response = self.request_builder("PATCH", &url).await?
+            .header("Content-Type", "application/json")
+            .json(&body).map_err(|e| e.to_string())?
+            .send().await.map_err(|e| e.to_string())?;
+
+        if response.ok() { Ok(()) }
+        else { Err(response.text().await.unwrap_or_default()) }
     }
 }
\ No newline at end of file
@@ -0,0 +1,64 @@
+use crate::models::{Certificate, Question};
+use crate::services::{DataStore, ImageService, SnapshotService};
+use chrono::{DateTime, Duration, Utc};
+
+/// 휴지통 목록. 자격증과 함께 휴지통에 들어간 문제는 자격증에 포함된 것으로 보고 따로 보여주지 않습니다.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TrashContents {
+    pub certificates: Vec<Certificate>,
+    /// (문제, 소속 자격증 이름)
+    pub questions: Vec<(Question, String)>,
+}
+
+pub struct TrashService;
+
+impl TrashService {
+    /// 휴지통 보관 기간. 지나면 영구 삭제됩니다.
+    pub const RETENTION_DAYS: i64 = 30;
+
+    pub async fn list() -> Result<TrashContents, String> {
+        let client = DataStore::current();
+        let mut certificates = client.get_deleted_certificates().await?;
+        certificates.sort_by_key(|c| std::cmp::Reverse(c.deleted_at));
+
+        let active = client.get_all_certificates().await?;
+        let mut questions: Vec<(Question, String)> = client
+            .get_deleted_questions()
+            .await?
+            .into_iter()
+            .filter_map(|q| {
+                let name = active.iter().find(|c| c.id == q.certificate_id)?.name.clone();
+                Some((q, name))
+            })
+            .collect();
+        questions.sort_by_key(|(q, _)| std::cmp::Reverse(q.deleted_at));
+
+        Ok(TrashContents { certificates, questions })
+    }
+
+    /// 보관 기간이 지난 항목을 영구 삭제합니다.
+    pub async fn purge_expired() -> Result<(), String> {
+        let client = DataStore::current();
+        client
+            .purge_deleted_before(Utc::now() - Duration::days(Self::RETENTION_DAYS))
+            .await?;
+        ImageService::sweep_after_purge().await;
+        Ok(())
+    }
+
+    /// 휴지통의 모든 항목을 영구 삭제합니다.
+    pub async fn empty() -> Result<(), String> {
+        SnapshotService::take_before("휴지통 비우기 전").await;
+        let client = DataStore::current();
+        client.purge_deleted_before(Utc::now()).await?;
+        ImageService::sweep_after_purge().await;
+        Ok(())
+    }
+
+    /// 영구 삭제까지 남은 일수
+    pub fn days_left(deleted_at: Option<DateTime<Utc>>) -> i64 {
+        deleted_at
+            .map(|at| (at + Duration::days(Self::RETENTION_DAYS) - Utc::now()).num_days().max(0))
+            .unwrap_or(Self::RETENTION_DAYS)
+    }
+}
@@ -0,0 +1,77 @@
+-- 자격증, 문제, 보기
+--
+-- 앱 모델(src/models)과 같은 이름의 열을 씁니다. 행의 주인(`user_id`)은 넣을 때 로그인한 사용자로 채워지고,
+-- 자격증을 지우면 문제와 보기가, 문제를 지우면 보기가 함께 지워집니다.
+-- 이미 테이블을 만들어 쓰던 프로젝트에도 그대로 적용할 수 있게 `if not exists`로 씁니다.
+
+create table if not exists public.certificates (
+    id uuid primary key,
+    user_id uuid not null default auth.uid() references auth.users (id) on delete cascade,
+    name text not null,
+    description text not null default '',
+    -- 문제 수. 아래 트리거가 관리합니다.
+    question_count integer not null default 0,
+    created_at timestamptz not null default now()
+);
+
+create table if not exists public.questions (
+    id uuid primary key,
+    user_id uuid not null default auth.uid() references auth.users (id) on delete cascade,
+    certificate_id uuid not null references public.certificates (id) on delete cascade,
+    content text not null,
+    explanation text not null default '',
+    created_at timestamptz not null default now(),
+    last_attempt timestamptz,
+    attempt_count integer not null default 0,
+    correct_count integer not null default 0
+);
+
+create table if not exists public.question_options (
+    id uuid primary key,
+    user_id uuid not null default auth.uid() references auth.users (id) on delete cascade,
+    question_id uuid not null references public.questions (id) on delete cascade,
+    content text not null,
+    is_correct boolean not null default false,
+    explanation text not null default '',
+    display_order integer not null default 0
+);
+
+-- 예전에 cascade 없이 만든 외래 키를 바꿉니다.
+alter table public.questions drop constraint if exists questions_certificate_id_fkey;
+alter table public.questions add constraint questions_certificate_id_fkey
+    foreign key (certificate_id) references public.certificates (id) on delete cascade;
+alter table public.question_options drop constraint if exists question_options_question_id_fkey;
+alter table public.question_options add constraint question_options_question_id_fkey
+    foreign key (question_id) references public.questions (id) on delete cascade;
+
+create index if not exists certificates_user_id_idx on public.certificates (user_id);
+create index if not exists questions_user_id_idx on public.questions (user_id);
+create index if not exists questions_certificate_id_idx on public.questions (certificate_id);
+create index if not exists question_options_question_id_idx on public.question_options (question_id);
+
+-- `certificates.question_count`
+create or replace function public.refresh_question_count()
+returns trigger
+language plpgsql
+security definer
+set search_path = public
+as $$
+begin
+    if tg_op in ('UPDATE', 'DELETE') then
+        update certificates
+        set question_count = (select count(*) from questions where certificate_id = old.certificate_id)
+        where id = old.certificate_id;
+    end if;
+    if tg_op in ('INSERT', 'UPDATE') then
+        update certificates
+        set question_count = (select count(*) from questions where certificate_id = new.certificate_id)
+        where id = new.certificate_id;
+    end if;
+    return null;
+end;
+$$;
+
+drop trigger if exists questions_question_count on public.questions;
+create trigger questions_question_count
+    after insert or delete or update of certificate_id on public.questions
+    for each row execute function public.refresh_question_count();
@@ -0,0 +1,43 @@
+-- 휴지통
+--
+-- 휴지통으로 옮긴 시각(`deleted_at`). 휴지통에 있는 문제는 문제 수에서 뺍니다.
+
+alter table public.certificates add column if not exists deleted_at timestamptz;
+alter table public.questions add column if not exists deleted_at timestamptz;
+
+create index if not exists certificates_deleted_at_idx on public.certificates (deleted_at) where deleted_at is not null;
+create index if not exists questions_deleted_at_idx on public.questions (deleted_at) where deleted_at is not null;
+
+create or replace function public.refresh_question_count()
+returns trigger
+language plpgsql
+security definer
+set search_path = public
+as $$
+begin
+    if tg_op in ('UPDATE', 'DELETE') then
+        update certificates
+        set question_count = (
+            select count(*) from questions where certificate_id = old.certificate_id and deleted_at is null
+        )
+        where id = old.certificate_id;
+    end if;
+    if tg_op in ('INSERT', 'UPDATE') then
+        update certificates
+        set question_count = (
+            select count(*) from questions where certificate_id = new.certificate_id and deleted_at is null
+        )
+        where id = new.certificate_id;
+    end if;
+    return null;
+end;
+$$;
+
+drop trigger if exists questions_question_count on public.questions;
+create trigger questions_question_count
+    after insert or delete or update of certificate_id, deleted_at on public.questions
+    for each row execute function public.refresh_question_count();
+
+-- 이미 휴지통에 있던 문제를 반영해 다시 셉니다.
+update public.certificates c
+set question_count = (select count(*) from public.questions q where q.certificate_id = c.id and q.deleted_at is null);
@@ -0,0 +1,5 @@
+-- 파일 가져오기로 만든 자격증 표시
+--
+-- 가져온 자격증은 마크다운의 원시 HTML을 다르게 처리합니다.
+
+alter table public.certificates add column if not exists imported boolean not null default false;
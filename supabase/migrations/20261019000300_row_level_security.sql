@@ -0,0 +1,38 @@
+-- 행 보안 정책
+--
+-- 로그인한 사용자는 자기 행만 읽고 쓸 수 있습니다. 문제와 보기는 부모(자격증, 문제)도 자기 것이어야 넣을 수 있고,
+-- 로그인하지 않은 요청(anon)에는 아무 행도 보이지 않습니다.
+
+alter table public.certificates enable row level security;
+alter table public.questions enable row level security;
+alter table public.question_options enable row level security;
+
+drop policy if exists "own certificates" on public.certificates;
+create policy "own certificates" on public.certificates
+    for all to authenticated
+    using (user_id = (select auth.uid()))
+    with check (user_id = (select auth.uid()));
+
+drop policy if exists "own questions" on public.questions;
+create policy "own questions" on public.questions
+    for all to authenticated
+    using (user_id = (select auth.uid()))
+    with check (
+        user_id = (select auth.uid())
+        and exists (
+            select 1 from public.certificates c
+            where c.id = certificate_id and c.user_id = (select auth.uid())
+        )
+    );
+
+drop policy if exists "own question options" on public.question_options;
+create policy "own question options" on public.question_options
+    for all to authenticated
+    using (user_id = (select auth.uid()))
+    with check (
+        user_id = (select auth.uid())
+        and exists (
+            select 1 from public.questions q
+            where q.id = question_id and q.user_id = (select auth.uid())
+        )
+    );
@@ -0,0 +1,19 @@
+-- 문제 이미지 (Storage)
+--
+-- 공개 버킷 `question-images`. 사용자는 자기 폴더(`<사용자 ID>/`)에만 쓰고 지울 수 있습니다.
+
+insert into storage.buckets (id, name, public)
+values ('question-images', 'question-images', true)
+on conflict (id) do nothing;
+
+drop policy if exists "own images insert" on storage.objects;
+create policy "own images insert" on storage.objects for insert to authenticated
+    with check (bucket_id = 'question-images' and (storage.foldername(name))[1] = (select auth.uid())::text);
+
+drop policy if exists "own images select" on storage.objects;
+create policy "own images select" on storage.objects for select to authenticated
+    using (bucket_id = 'question-images' and (storage.foldername(name))[1] = (select auth.uid())::text);
+
+drop policy if exists "own images delete" on storage.objects;
+create policy "own images delete" on storage.objects for delete to authenticated
+    using (bucket_id = 'question-images' and (storage.foldername(name))[1] = (select auth.uid())::text);
@@ -0,0 +1,32 @@
+-- 계정 삭제
+--
+-- 앱의 계정 화면에서 `POST /rest/v1/rpc/delete_account`로 부릅니다. 로그인한 사용자 자신의 데이터와
+-- 인증 사용자(`auth.users`)를 지웁니다. 세션과 Refresh Token도 함께 지워지므로 다른 기기는 다음 토큰 갱신 때
+-- 로그아웃됩니다. 첨부 이미지(Storage)는 SQL로 지울 수 없어 앱이 이 함수를 부르기 전에 지웁니다.
+-- 앱은 이미지를 지우기 전에 `dry_run`으로 불러 이 함수가 있는지 먼저 확인합니다. 이때는 아무것도 지우지 않습니다.
+
+create or replace function public.delete_account(dry_run boolean default false)
+returns void
+language plpgsql
+security definer
+set search_path = ''
+as $$
+declare
+    uid uuid := auth.uid();
+begin
+    if uid is null then
+        raise exception 'not authenticated' using errcode = '42501';
+    end if;
+    if dry_run then
+        return;
+    end if;
+    -- `on delete cascade`가 없던 예전 테이블에서도 남는 행이 없도록 먼저 직접 지웁니다.
+    delete from public.question_options where user_id = uid;
+    delete from public.questions where user_id = uid;
+    delete from public.certificates where user_id = uid;
+    delete from auth.users where id = uid;
+end;
+$$;
+
+revoke execute on function public.delete_account(boolean) from public, anon;
+grant execute on function public.delete_account(boolean) to authenticated;